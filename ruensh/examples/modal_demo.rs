@@ -14,7 +14,7 @@ use ratatui::prelude::*;
 use ratatui::widgets::Paragraph;
 use ruensh::components::{Component, Modal};
 use ruensh::components::modal::ButtonFocus;
-use ruensh::events::{Event, EventHandler, start_event_loop};
+use ruensh::events::Event;
 use ruensh::style::Theme;
 use ruensh::terminal::Terminal;
 use std::io;
@@ -175,9 +175,7 @@ impl App {
 
 #[tokio::main]
 async fn main() -> io::Result<()> {
-    let _terminal = Terminal::new()?;
-    let (_event_handler, _tx) = EventHandler::new();
-    start_event_loop(_tx).await;
+    let terminal = Terminal::new()?;
 
     let mut app = App::new();
     let mut running = true;
@@ -186,21 +184,25 @@ async fn main() -> io::Result<()> {
     let mut tui = ratatui::Terminal::new(backend)?;
 
     while running {
-        tui.draw(|frame| app.render(frame))?;
+        tui.draw(|frame| {
+            app.render(frame);
+            terminal.adapt_frame(frame);
+        })?;
 
         // Update modal animations
         app.modal.update_animation();
 
         // Handle events
         if crossterm::event::poll(std::time::Duration::from_millis(16))? {
-            if let Ok(crossterm::event::Event::Key(key)) = crossterm::event::read() {
-                let event = Event::Key(key);
+            let event = match crossterm::event::read()? {
+                crossterm::event::Event::Key(key) => Some(Event::Key(key)),
+                crossterm::event::Event::Mouse(mouse) => Some(Event::Mouse(mouse)),
+                _ => None,
+            };
+            if let Some(event) = event {
                 if app.handle_event(&event) {
                     running = false;
                 }
-            } else if let Ok(crossterm::event::Event::Mouse(mouse)) = crossterm::event::read() {
-                let event = Event::Mouse(mouse);
-                let _ = app.handle_event(&event);
             }
         }
     }
@@ -1,13 +1,10 @@
-/// REPL Example - Simple Read-Eval-Print Loop with RuenSH
-///
-/// This example demonstrates how to use RuenSH components to build a simple
-/// REPL interface. It shows integration with the Modal component for
-/// confirmations and basic command execution.
-
-use crossterm::event::KeyCode;
-use ratatui::prelude::*;
-use ruensh::components::Modal;
-use ruensh::events::{Event, EventHandler};
+//! REPL Example - Simple Read-Eval-Print Loop with RuenSH
+//!
+//! This example demonstrates how to use RuenSH components to build a simple
+//! REPL interface. It shows integration with the Modal component for
+//! confirmations and basic command execution.
+
+use ruensh::events::{start_event_loop, EventHandler};
 use std::io;
 
 #[tokio::main]
@@ -29,14 +26,14 @@ async fn main() -> io::Result<()> {
 
     // Demonstrate event handling
     println!("\n--- Event System Demo ---");
-    let mut event_handler = EventHandler::new();
-    let _ = event_handler.spawn_event_loop();
+    let (_event_handler, tx) = EventHandler::new();
+    start_event_loop(tx).await;
 
     println!("Event system initialized (async polling active)");
     println!("Press Ctrl+C to exit\n");
 
     // Simulate REPL input
-    let commands = vec![
+    let commands = [
         "(+ 1 2)       ; => 3",
         "(defn greet [name] (str \"Hello, \" name))",
         "(greet \"World\")  ; => \"Hello, World\"",
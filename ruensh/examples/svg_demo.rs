@@ -35,7 +35,7 @@
 //! - **←/→/Home/End**: Move cursor (REPL mode)
 //! - **Backspace/Delete**: Edit input (REPL mode)
 
-use ruensh::svg::{SvgCanvas, ColorScheme, Transition, TransitionPresets, Keyframe, Easing};
+use ruensh::svg::{SvgCanvas, ColorScheme, Transition, TransitionPresets, Keyframe};
 use ruensh::terminal::Terminal;
 use ruensh::events::{EventHandler, start_event_loop};
 use ratatui::style::Color;
@@ -204,14 +204,14 @@ impl ReplState {
                 let sum: i32 = nums.iter().sum();
                 return vec![
                     (format!("  => {}", sum), Color::Cyan),
-                    (format!("  [type: Integer]"), Color::DarkGray),
+                    ("  [type: Integer]".to_string(), Color::DarkGray),
                 ];
             }
         } else if expr.starts_with("(def ") {
             // Variable definition
             return vec![
                 (format!("  => #'user/{}", expr.split_whitespace().nth(1).unwrap_or("var")), Color::Yellow),
-                (format!("  [Var defined]"), Color::DarkGray),
+                ("  [Var defined]".to_string(), Color::DarkGray),
             ];
         } else if expr.starts_with("(map ") {
             // Map function demo
@@ -231,7 +231,7 @@ impl ReplState {
 
         vec![
             (format!("  => '{}'", expr), Color::White),
-            (format!("  [type: Symbol]"), Color::DarkGray),
+            ("  [type: Symbol]".to_string(), Color::DarkGray),
         ]
     }
 
@@ -263,31 +263,31 @@ impl ReplState {
     
     // Get current transition values (non-mutating)
     fn get_pulse_scale(&self) -> f32 {
-        self.pulse_transition.current_value.clone().unwrap_or(1.0)
+        self.pulse_transition.current_value.unwrap_or(1.0)
     }
     
     fn get_rainbow_color(&self) -> Color {
-        self.color_transition.current_value.clone().unwrap_or(Color::White)
+        self.color_transition.current_value.unwrap_or(Color::White)
     }
     
     fn get_slide_offset(&self) -> i16 {
-        self.slide_transition.current_value.clone().unwrap_or(0)
+        self.slide_transition.current_value.unwrap_or(0)
     }
     
     fn get_fade_alpha(&self) -> f32 {
-        self.mode_transition.current_value.clone().unwrap_or(1.0)
+        self.mode_transition.current_value.unwrap_or(1.0)
     }
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize terminal
-    let _terminal = Terminal::new()?;
+    let terminal = Terminal::new()?;
     let (_event_handler, tx) = EventHandler::new();
     start_event_loop(tx).await;
 
     // Color schemes to cycle through
-    let themes = vec![
+    let themes = [
         ("CyberPunk", ColorScheme::cyberpunk()),
         ("Neon Tokyo", ColorScheme::neon_tokyo()),
         ("Matrix", ColorScheme::matrix()),
@@ -324,7 +324,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
             match repl_state.mode {
                 ReplMode::Visualizer => {
-                    draw_visualizer_mode(&mut canvas, area.width, area.height, elapsed, current_theme, &mut repl_state);
+                    draw_visualizer_mode(&mut canvas, area.width, area.height, elapsed, current_theme, &repl_state);
                 }
                 ReplMode::Repl => {
                     draw_repl_mode(&mut canvas, area.width, area.height, &repl_state, current_theme);
@@ -356,7 +356,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             );
 
             // Render to frame
-            canvas.render(frame, area);
+            frame.render_widget(&canvas, area);
+            terminal.adapt_frame(frame);
         })?;
 
         // Handle events
@@ -469,7 +470,7 @@ fn draw_visualizer_mode(canvas: &mut SvgCanvas, width: u16, height: u16, elapsed
     canvas.draw_text(2, trans_y, "┌─ ACTIVE TRANSITIONS ─┐", Some(Color::Cyan));
     canvas.draw_text(2, trans_y + 1, &format!("│ Pulse: {:.2}x", pulse_scale), Some(Color::Gray));
     canvas.draw_text(2, trans_y + 2, "│ Rainbow: Active", Some(Color::Gray));
-    canvas.draw_text(2, trans_y + 3, &format!("│ Keyframes: Running"), Some(Color::Gray));
+    canvas.draw_text(2, trans_y + 3, "│ Keyframes: Running", Some(Color::Gray));
     canvas.draw_text(2, trans_y + 4, "└────────────────────┘", Some(Color::Cyan));
 }
 
@@ -0,0 +1,442 @@
+//! Composition helpers for nesting components
+//!
+//! - `ComponentExt::map` lifts a child's messages into the parent's message
+//!   type, so a `List` can live inside an app with its own `Message` enum.
+//! - `AnyComponent` is an object-safe view of `Component`, letting children
+//!   of different types be stored as `Box<dyn AnyComponent<M>>`.
+//! - `Children` holds boxed children, places them with a `layout::Layout`,
+//!   and routes events to the focused child or the one under the mouse.
+
+use super::Component;
+use crate::events::Event;
+use crate::layout::Layout;
+use crate::state::Action;
+use crate::style::Theme;
+use ratatui::prelude::*;
+use std::any::Any;
+use std::cell::RefCell;
+
+/// Component adapter translating between child and parent messages
+pub struct Map<C, F, G> {
+    inner: C,
+    lift: F,
+    unlift: G,
+}
+
+impl<C, F, G> Map<C, F, G> {
+    /// Get the wrapped component
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Get the wrapped component mutably
+    pub fn inner_mut(&mut self) -> &mut C {
+        &mut self.inner
+    }
+}
+
+impl<C, P, F, G> Component for Map<C, F, G>
+where
+    C: Component,
+    F: Fn(C::Message) -> P,
+    G: Fn(P) -> Option<C::Message>,
+{
+    type Message = P;
+
+    fn update(&mut self, msg: P) -> Option<Action> {
+        let msg = (self.unlift)(msg)?;
+        self.inner.update(msg)
+    }
+
+    fn render(&self, frame: &mut Frame<'_>) {
+        self.inner.render(frame);
+    }
+
+    fn render_in(&self, frame: &mut Frame<'_>, area: Rect) {
+        self.inner.render_in(frame, area);
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.inner.set_theme(theme);
+    }
+
+    fn handle_event(&mut self, event: &Event) -> Option<P> {
+        self.inner.handle_event(event).map(&self.lift)
+    }
+}
+
+/// Extension methods available on every component
+pub trait ComponentExt: Component + Sized {
+    /// Lift child messages into a parent type
+    ///
+    /// `lift` wraps messages coming out of `handle_event`; `unlift` picks
+    /// this child's messages out of the parent type for `update`:
+    ///
+    /// ```
+    /// # use ruensh::components::{compose::ComponentExt, List, list::ListMessage};
+    /// enum AppMessage {
+    ///     Files(ListMessage),
+    ///     Quit,
+    /// }
+    ///
+    /// let files = List::new(vec!["main.rs".to_string()]).map(AppMessage::Files, |msg| match msg {
+    ///     AppMessage::Files(msg) => Some(msg),
+    ///     _ => None,
+    /// });
+    /// ```
+    fn map<P, F, G>(self, lift: F, unlift: G) -> Map<Self, F, G>
+    where
+        F: Fn(Self::Message) -> P,
+        G: Fn(P) -> Option<Self::Message>,
+    {
+        Map {
+            inner: self,
+            lift,
+            unlift,
+        }
+    }
+}
+
+impl<C: Component> ComponentExt for C {}
+
+/// Object-safe component with a fixed message type
+///
+/// Every `Component` is an `AnyComponent` of its own message type; use
+/// `ComponentExt::map` first to give children a shared parent message.
+/// Methods carry an `_any` suffix so they never clash with `Component`'s;
+/// boxed children implement `Component` themselves.
+pub trait AnyComponent<M> {
+    /// Update the component with a message
+    fn update_any(&mut self, msg: M) -> Option<Action>;
+
+    /// Render the component into an area
+    fn render_any(&self, frame: &mut Frame<'_>, area: Rect);
+
+    /// Handle keyboard/mouse events
+    fn handle_event_any(&mut self, event: &Event) -> Option<M>;
+
+    /// Apply a new theme at runtime
+    fn set_theme_any(&mut self, theme: &Theme);
+
+    /// Access the concrete component for downcasting
+    fn as_any(&self) -> &dyn Any;
+
+    /// Access the concrete component mutably for downcasting
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<C: Component + 'static> AnyComponent<C::Message> for C {
+    fn update_any(&mut self, msg: C::Message) -> Option<Action> {
+        self.update(msg)
+    }
+
+    fn render_any(&self, frame: &mut Frame<'_>, area: Rect) {
+        self.render_in(frame, area);
+    }
+
+    fn handle_event_any(&mut self, event: &Event) -> Option<C::Message> {
+        self.handle_event(event)
+    }
+
+    fn set_theme_any(&mut self, theme: &Theme) {
+        self.set_theme(theme);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl<M: 'static> dyn AnyComponent<M> {
+    /// Get the concrete component if it is a `C`
+    pub fn downcast_ref<C: 'static>(&self) -> Option<&C> {
+        self.as_any().downcast_ref()
+    }
+
+    /// Get the concrete component mutably if it is a `C`
+    pub fn downcast_mut<C: 'static>(&mut self) -> Option<&mut C> {
+        self.as_any_mut().downcast_mut()
+    }
+}
+
+/// Boxed children can be used anywhere a component is expected
+impl<M> Component for Box<dyn AnyComponent<M>> {
+    type Message = M;
+
+    fn update(&mut self, msg: M) -> Option<Action> {
+        (**self).update_any(msg)
+    }
+
+    fn render(&self, frame: &mut Frame<'_>) {
+        let area = frame.area();
+        (**self).render_any(frame, area);
+    }
+
+    fn render_in(&self, frame: &mut Frame<'_>, area: Rect) {
+        (**self).render_any(frame, area);
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        (**self).set_theme_any(theme);
+    }
+
+    fn handle_event(&mut self, event: &Event) -> Option<M> {
+        (**self).handle_event_any(event)
+    }
+}
+
+/// Message for the child at `index` in a `Children` container
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChildMessage<M> {
+    pub index: usize,
+    pub message: M,
+}
+
+/// Container placing heterogeneous children with a layout
+///
+/// Child `i` is drawn into the `i`-th area of the layout. Mouse events go
+/// to the child under the pointer, other events to the focused child.
+pub struct Children<M> {
+    children: Vec<Box<dyn AnyComponent<M>>>,
+    layout: Layout,
+    focused: usize,
+    /// Child areas at the last render
+    areas: RefCell<Vec<Rect>>,
+}
+
+impl<M: 'static> Children<M> {
+    /// Create an empty container placing children with `layout`
+    pub fn new(layout: Layout) -> Self {
+        Children {
+            children: Vec::new(),
+            layout,
+            focused: 0,
+            areas: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Add a child
+    pub fn child(mut self, child: impl AnyComponent<M> + 'static) -> Self {
+        self.children.push(Box::new(child));
+        self
+    }
+
+    /// Add a child; returns its index
+    pub fn push(&mut self, child: impl AnyComponent<M> + 'static) -> usize {
+        self.push_boxed(Box::new(child))
+    }
+
+    /// Add an already boxed child; returns its index
+    pub fn push_boxed(&mut self, child: Box<dyn AnyComponent<M>>) -> usize {
+        self.children.push(child);
+        self.children.len() - 1
+    }
+
+    /// Replace the layout
+    pub fn set_layout(&mut self, layout: Layout) {
+        self.layout = layout;
+    }
+
+    /// Number of children
+    pub fn len(&self) -> usize {
+        self.children.len()
+    }
+
+    /// Check whether there are no children
+    pub fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    /// Get the focused child index
+    pub fn focused(&self) -> usize {
+        self.focused
+    }
+
+    /// Focus a child
+    pub fn focus(&mut self, index: usize) {
+        if index < self.children.len() {
+            self.focused = index;
+        }
+    }
+
+    /// Get a child as its concrete type
+    pub fn get<C: 'static>(&self, index: usize) -> Option<&C> {
+        self.children.get(index)?.downcast_ref()
+    }
+
+    /// Get a child mutably as its concrete type
+    pub fn get_mut<C: 'static>(&mut self, index: usize) -> Option<&mut C> {
+        self.children.get_mut(index)?.downcast_mut()
+    }
+
+    /// Area of a child at the last render
+    pub fn area(&self, index: usize) -> Option<Rect> {
+        self.areas.borrow().get(index).copied()
+    }
+
+    fn forward(&mut self, index: usize, event: &Event) -> Option<ChildMessage<M>> {
+        let child = self.children.get_mut(index)?;
+        let message = child.handle_event_any(event)?;
+        Some(ChildMessage { index, message })
+    }
+}
+
+impl<M: 'static> Component for Children<M> {
+    type Message = ChildMessage<M>;
+
+    fn update(&mut self, msg: Self::Message) -> Option<Action> {
+        let child = self.children.get_mut(msg.index)?;
+        self.focused = msg.index;
+        child.update_any(msg.message)
+    }
+
+    fn render(&self, frame: &mut Frame<'_>) {
+        let area = frame.area();
+        self.render_in(frame, area);
+    }
+
+    fn render_in(&self, frame: &mut Frame<'_>, area: Rect) {
+        let areas = self.layout.split(area);
+        for (child, child_area) in self.children.iter().zip(&areas) {
+            child.render_any(frame, *child_area);
+        }
+        *self.areas.borrow_mut() = areas;
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        for child in &mut self.children {
+            child.set_theme_any(theme);
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> Option<Self::Message> {
+        match event {
+            Event::Mouse(mouse_event) => {
+                let position = Position::new(mouse_event.column, mouse_event.row);
+                let index = self.areas.borrow().iter().position(|area| area.contains(position))?;
+                self.forward(index, event)
+            }
+            _ => self.forward(self.focused, event),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::list::ListMessage;
+    use crate::components::List;
+    use crate::layout::Constraint;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+    use ratatui::backend::TestBackend;
+
+    #[derive(Debug, PartialEq)]
+    enum AppMessage {
+        Files(ListMessage),
+        Count,
+    }
+
+    /// Counter reacting to any key
+    struct Counter {
+        count: usize,
+    }
+
+    impl Component for Counter {
+        type Message = ();
+
+        fn update(&mut self, _msg: ()) -> Option<Action> {
+            self.count += 1;
+            None
+        }
+
+        fn render(&self, _frame: &mut Frame<'_>) {}
+
+        fn handle_event(&mut self, event: &Event) -> Option<()> {
+            matches!(event, Event::Key(_) | Event::Mouse(_)).then_some(())
+        }
+    }
+
+    fn files() -> impl Component<Message = AppMessage> + 'static {
+        List::new(vec!["a".to_string(), "b".to_string()]).map(AppMessage::Files, |msg| match msg {
+            AppMessage::Files(msg) => Some(msg),
+            _ => None,
+        })
+    }
+
+    fn counter() -> impl Component<Message = AppMessage> + 'static {
+        Counter { count: 0 }.map(|()| AppMessage::Count, |msg| match msg {
+            AppMessage::Count => Some(()),
+            _ => None,
+        })
+    }
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::from(code))
+    }
+
+    #[test]
+    fn test_map_lifts_and_unlifts_messages() {
+        let mut files = files();
+        let msg = files.handle_event(&key(KeyCode::Down));
+        assert_eq!(msg, Some(AppMessage::Files(ListMessage::Down)));
+        files.update(msg.unwrap());
+        // Messages for other children are ignored
+        assert_eq!(files.update(AppMessage::Count), None);
+    }
+
+    #[test]
+    fn test_children_route_keys_to_focus_and_mouse_to_area() {
+        let mut children = Children::new(Layout::horizontal([
+            Constraint::Percentage(50),
+            Constraint::Percentage(50),
+        ]))
+        .child(files())
+        .child(counter());
+
+        let mut terminal = ratatui::Terminal::new(TestBackend::new(20, 5)).unwrap();
+        terminal.draw(|frame| children.render(frame)).unwrap();
+        assert_eq!(children.area(1), Some(Rect::new(10, 0, 10, 5)));
+
+        let msg = children.handle_event(&key(KeyCode::Down)).unwrap();
+        assert_eq!(msg.index, 0);
+        children.update(msg);
+
+        let click = Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: 15,
+            row: 2,
+            modifiers: KeyModifiers::NONE,
+        });
+        let msg = children.handle_event(&click).unwrap();
+        assert_eq!(msg, ChildMessage { index: 1, message: AppMessage::Count });
+        children.update(msg);
+        assert_eq!(children.focused(), 1);
+        assert!(children.get::<List>(0).is_none());
+    }
+
+    #[test]
+    fn test_boxed_children_downcast_and_act_as_components() {
+        let mut boxed: Vec<Box<dyn AnyComponent<()>>> = vec![Box::new(Counter { count: 0 })];
+        assert_eq!(boxed[0].handle_event(&key(KeyCode::Enter)), Some(()));
+        boxed[0].update(());
+
+        let counter = boxed[0].downcast_ref::<Counter>().unwrap();
+        assert_eq!(counter.count, 1);
+        assert!(boxed[0].downcast_ref::<List>().is_none());
+    }
+
+    #[test]
+    fn test_set_theme_reaches_mapped_children() {
+        let mut children = Children::new(Layout::vertical([Constraint::Percentage(100)])).child(files());
+        let theme = Theme::matrix();
+        children.set_theme(&theme);
+
+        let mut terminal = ratatui::Terminal::new(TestBackend::new(10, 4)).unwrap();
+        terminal.draw(|frame| children.render(frame)).unwrap();
+        assert_eq!(terminal.backend().buffer()[(0, 0)].fg, theme.secondary);
+    }
+}
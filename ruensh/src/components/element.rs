@@ -0,0 +1,530 @@
+//! Declarative view tree
+//!
+//! An `Element` describes what to draw instead of drawing it: text, rows and
+//! columns of child elements with flex layout props, layered stacks, and
+//! slots for components or widgets. `Element::render` lays the tree out
+//! with `layout::Flex` and draws it, so components build their view without
+//! computing `Rect`s by hand.
+//!
+//! Elements can carry a key. `Element::layout` reports where each keyed
+//! element lands, which is how components hit-test their parts and how two
+//! frames can be compared: the same key is the same element, wherever it
+//! moved.
+//!
+//! ```
+//! # use ruensh::components::element::{column, row, text, Element};
+//! # use ruensh::components::List;
+//! # let files = List::new(vec!["a.rs".to_string()]);
+//! # let symbols = List::new(vec!["main".to_string()]);
+//! let view = column([
+//!     text("Charm™ Crush"),
+//!     row([Element::component(&files).grow(1.0), Element::component(&symbols).grow(1.0)])
+//!         .grow(1.0)
+//!         .gap(1),
+//!     text("Ready..."),
+//! ]);
+//! ```
+
+use super::Component;
+use crate::layout::{Align, Flex, Justify};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Padding, Paragraph};
+
+/// Draws a slot into its area
+type SlotFn<'a> = Box<dyn Fn(&mut Frame<'_>, Rect) + 'a>;
+
+/// Draws a widget into its area
+type WidgetFn<'a> = Box<dyn Fn(Rect, &mut Buffer) + 'a>;
+
+/// What an element draws
+enum ElementKind<'a> {
+    Empty,
+    Text(Text<'a>),
+    /// Children laid out along a direction
+    Container {
+        direction: Direction,
+        children: Vec<Element<'a>>,
+    },
+    /// Children drawn on top of each other in the same area
+    Stack(Vec<Element<'a>>),
+    /// Component or closure drawn into whatever area it gets
+    Slot(SlotFn<'a>),
+    /// Widget drawn into whatever area it gets
+    Widget(WidgetFn<'a>),
+}
+
+/// Node in a declarative view tree
+pub struct Element<'a> {
+    kind: ElementKind<'a>,
+    key: Option<String>,
+    style: Style,
+    block: Option<Block<'a>>,
+    // Sizing inside the parent container
+    basis: Option<u16>,
+    grow: f32,
+    shrink: f32,
+    min: u16,
+    max: u16,
+    cross_size: Option<u16>,
+    optional: bool,
+    // Container properties
+    gap: u16,
+    padding: Padding,
+    justify: Justify,
+    align: Align,
+    wrap: bool,
+}
+
+/// Create a text element
+pub fn text<'a>(content: impl Into<Text<'a>>) -> Element<'a> {
+    Element::text(content)
+}
+
+/// Create a container laying out children left to right
+pub fn row<'a>(children: impl IntoIterator<Item = Element<'a>>) -> Element<'a> {
+    Element::row(children)
+}
+
+/// Create a container laying out children top to bottom
+pub fn column<'a>(children: impl IntoIterator<Item = Element<'a>>) -> Element<'a> {
+    Element::column(children)
+}
+
+impl<'a> Element<'a> {
+    fn new(kind: ElementKind<'a>) -> Self {
+        Element {
+            kind,
+            key: None,
+            style: Style::default(),
+            block: None,
+            basis: None,
+            grow: 0.0,
+            shrink: 1.0,
+            min: 0,
+            max: u16::MAX,
+            cross_size: None,
+            optional: false,
+            gap: 0,
+            padding: Padding::ZERO,
+            justify: Justify::default(),
+            align: Align::default(),
+            wrap: false,
+        }
+    }
+
+    /// Create an element that draws nothing
+    pub fn empty() -> Self {
+        Self::new(ElementKind::Empty)
+    }
+
+    /// Create a text element from a string, span, line or text
+    pub fn text(content: impl Into<Text<'a>>) -> Self {
+        Self::new(ElementKind::Text(content.into()))
+    }
+
+    /// Create a container laying out children left to right
+    pub fn row(children: impl IntoIterator<Item = Element<'a>>) -> Self {
+        Self::new(ElementKind::Container {
+            direction: Direction::Horizontal,
+            children: children.into_iter().collect(),
+        })
+    }
+
+    /// Create a container laying out children top to bottom
+    pub fn column(children: impl IntoIterator<Item = Element<'a>>) -> Self {
+        Self::new(ElementKind::Container {
+            direction: Direction::Vertical,
+            children: children.into_iter().collect(),
+        })
+    }
+
+    /// Create a stack drawing children over each other, first at the bottom
+    pub fn stack(children: impl IntoIterator<Item = Element<'a>>) -> Self {
+        Self::new(ElementKind::Stack(children.into_iter().collect()))
+    }
+
+    /// Create a slot rendering a component into its area
+    pub fn component<C: Component>(component: &'a C) -> Self {
+        Self::slot(move |frame, area| component.render_in(frame, area))
+    }
+
+    /// Create a slot rendering a ratatui widget into its area
+    pub fn widget<W: Widget + Clone + 'a>(widget: W) -> Self {
+        Self::new(ElementKind::Widget(Box::new(move |area, buffer| widget.clone().render(area, buffer))))
+    }
+
+    /// Create a slot drawn by a closure
+    pub fn slot(draw: impl Fn(&mut Frame<'_>, Rect) + 'a) -> Self {
+        Self::new(ElementKind::Slot(Box::new(draw)))
+    }
+
+    /// Draw `overlay` on top of this element, if present
+    pub fn with_overlay(self, overlay: Option<Element<'a>>) -> Self {
+        match overlay {
+            Some(overlay) => {
+                let (basis, grow, shrink) = (self.basis, self.grow, self.shrink);
+                let mut stack = Self::stack([self, overlay]);
+                stack.basis = basis;
+                stack.grow = grow;
+                stack.shrink = shrink;
+                stack
+            }
+            None => self,
+        }
+    }
+
+    /// Identify the element in `layout`; keys should be unique within a tree
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Set the base style of the element's area
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Surround the element with a block; content goes inside its borders
+    pub fn block(mut self, block: Block<'a>) -> Self {
+        self.block = Some(block);
+        self
+    }
+
+    /// Set the preferred size along the parent's main axis
+    ///
+    /// Defaults to the content size for text and containers and 0 for slots.
+    pub fn basis(mut self, basis: u16) -> Self {
+        self.basis = Some(basis);
+        self
+    }
+
+    /// Set the share of free space taken in the parent
+    pub fn grow(mut self, grow: f32) -> Self {
+        self.grow = grow;
+        self
+    }
+
+    /// Set how much this element gives up when space is short
+    pub fn shrink(mut self, shrink: f32) -> Self {
+        self.shrink = shrink;
+        self
+    }
+
+    /// Set the minimum size along the parent's main axis
+    pub fn min(mut self, min: u16) -> Self {
+        self.min = min;
+        self
+    }
+
+    /// Set the maximum size along the parent's main axis
+    pub fn max(mut self, max: u16) -> Self {
+        self.max = max;
+        self
+    }
+
+    /// Set a fixed size on the parent's cross axis
+    pub fn cross_size(mut self, size: u16) -> Self {
+        self.cross_size = Some(size);
+        self
+    }
+
+    /// Hide the element when its parent cannot fit every minimum size
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+
+    /// Set spacing between children of a container
+    pub fn gap(mut self, gap: u16) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Set inner padding of a container
+    pub fn padding(mut self, padding: Padding) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Set main axis alignment of a container's children
+    pub fn justify(mut self, justify: Justify) -> Self {
+        self.justify = justify;
+        self
+    }
+
+    /// Set cross axis alignment of a container's children
+    pub fn align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Wrap a container's children onto new lines
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Lay out and draw the tree into `area`
+    pub fn render(&self, frame: &mut Frame<'_>, area: Rect) {
+        self.visit(area, &mut |element, area| {
+            let buffer = frame.buffer_mut();
+            element.draw(buffer, area);
+            if let ElementKind::Slot(draw) = &element.kind {
+                draw(frame, element.inner(area));
+            }
+        });
+    }
+
+    /// Lay out and draw the tree into a buffer, e.g. an off-screen one
+    ///
+    /// Slots need a `Frame` and are left empty; text, widgets and
+    /// containers are drawn as by `render`.
+    pub fn render_buffer(&self, buffer: &mut Buffer, area: Rect) {
+        self.visit(area, &mut |element, area| element.draw(buffer, area));
+    }
+
+    /// Areas of the keyed elements when the tree is laid out in `area`
+    ///
+    /// Elements are listed in drawing order; elements that get no space are
+    /// left out.
+    pub fn layout(&self, area: Rect) -> Vec<(String, Rect)> {
+        let mut areas = Vec::new();
+        self.visit(area, &mut |element, area| {
+            if let Some(key) = &element.key {
+                areas.push((key.clone(), area));
+            }
+        });
+        areas
+    }
+
+    /// Call `f` with each element and its area, parents before children
+    fn visit(&self, area: Rect, f: &mut dyn FnMut(&Element<'a>, Rect)) {
+        if area.is_empty() {
+            return;
+        }
+        f(self, area);
+        let inner = self.inner(area);
+        match &self.kind {
+            ElementKind::Stack(children) => {
+                for child in children {
+                    child.visit(inner, f);
+                }
+            }
+            ElementKind::Container { direction, children } => {
+                let root = match direction {
+                    Direction::Horizontal => Flex::horizontal(),
+                    Direction::Vertical => Flex::vertical(),
+                };
+                let areas = root
+                    .gap(self.gap)
+                    .padding(self.padding)
+                    .justify(self.justify)
+                    .align(self.align)
+                    .wrap(self.wrap)
+                    .children(children.iter().enumerate().map(|(index, child)| child.flex_item(index, *direction)))
+                    .compute(inner);
+                for (index, child) in children.iter().enumerate() {
+                    if let Some(child_area) = areas.get(&index) {
+                        child.visit(*child_area, f);
+                    }
+                }
+            }
+            ElementKind::Empty | ElementKind::Text(_) | ElementKind::Slot(_) | ElementKind::Widget(_) => {}
+        }
+    }
+
+    /// Area inside the element's block
+    fn inner(&self, area: Rect) -> Rect {
+        match &self.block {
+            Some(block) => block.inner(area),
+            None => area,
+        }
+    }
+
+    /// Draw the element's own style, block and content, but not its children
+    fn draw(&self, buffer: &mut Buffer, area: Rect) {
+        buffer.set_style(area.intersection(buffer.area), self.style);
+        if let Some(block) = &self.block {
+            block.clone().render(area, buffer);
+        }
+        let inner = self.inner(area);
+        match &self.kind {
+            ElementKind::Text(text) => Paragraph::new(text.clone()).render(inner, buffer),
+            ElementKind::Widget(draw) => draw(inner, buffer),
+            ElementKind::Empty | ElementKind::Slot(_) | ElementKind::Stack(_) | ElementKind::Container { .. } => {}
+        }
+    }
+
+    /// Flex item describing this element inside a container
+    fn flex_item(&self, key: usize, direction: Direction) -> Flex<usize> {
+        let mut item = Flex::item(key)
+            .basis(self.natural_size(direction))
+            .grow(self.grow)
+            .shrink(self.shrink)
+            .min(self.min)
+            .max(self.max);
+        if let Some(size) = self.cross_size {
+            item = item.cross_size(size);
+        }
+        if self.optional {
+            item = item.optional();
+        }
+        item
+    }
+
+    /// Size of the content along `direction`, including block and padding
+    fn natural_size(&self, direction: Direction) -> u16 {
+        if let Some(basis) = self.basis {
+            return basis;
+        }
+        let horizontal = direction == Direction::Horizontal;
+        let content = match &self.kind {
+            ElementKind::Empty | ElementKind::Slot(_) | ElementKind::Widget(_) => 0,
+            ElementKind::Text(text) => {
+                let size = if horizontal { text.width() } else { text.height() };
+                size.min(u16::MAX as usize) as u16
+            }
+            ElementKind::Stack(children) => children
+                .iter()
+                .map(|child| child.natural_size(direction))
+                .max()
+                .unwrap_or(0),
+            ElementKind::Container { direction: own, children } => {
+                let sizes = children.iter().map(|child| child.natural_size(direction));
+                let padding = if horizontal {
+                    self.padding.left + self.padding.right
+                } else {
+                    self.padding.top + self.padding.bottom
+                };
+                let content = if *own == direction {
+                    let gaps = self.gap.saturating_mul(children.len().saturating_sub(1) as u16);
+                    sizes.fold(gaps, u16::saturating_add)
+                } else {
+                    sizes.max().unwrap_or(0)
+                };
+                content.saturating_add(padding)
+            }
+        };
+        content.saturating_add(self.block_size(direction))
+    }
+
+    /// Cells taken by the block's borders and title along `direction`
+    fn block_size(&self, direction: Direction) -> u16 {
+        let Some(block) = &self.block else {
+            return 0;
+        };
+        let probe = Rect::new(0, 0, 100, 100);
+        let inner = block.inner(probe);
+        match direction {
+            Direction::Horizontal => probe.width - inner.width,
+            Direction::Vertical => probe.height - inner.height,
+        }
+    }
+}
+
+impl Default for Element<'_> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<'a> From<&'a str> for Element<'a> {
+    fn from(content: &'a str) -> Self {
+        Self::text(content)
+    }
+}
+
+impl From<String> for Element<'_> {
+    fn from(content: String) -> Self {
+        Self::text(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::List;
+    use ratatui::backend::TestBackend;
+    use ratatui::widgets::Borders;
+
+    fn draw(element: &Element<'_>, width: u16, height: u16) -> Buffer {
+        let mut terminal = ratatui::Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|frame| element.render(frame, frame.area())).unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    fn row_text(buffer: &Buffer, y: u16) -> String {
+        (0..buffer.area.width).map(|x| buffer[(x, y)].symbol()).collect()
+    }
+
+    #[test]
+    fn test_text_takes_natural_size_and_slots_grow() {
+        let view = column([
+            text("header"),
+            Element::widget(Block::default().borders(Borders::ALL)).grow(1.0),
+            text(Line::from(vec![Span::raw("status "), Span::styled("ok", Style::default().fg(Color::Green))])),
+        ]);
+        let buffer = draw(&view, 12, 6);
+        assert_eq!(row_text(&buffer, 0).trim_end(), "header");
+        assert_eq!(buffer[(0, 1)].symbol(), "┌");
+        assert_eq!(buffer[(0, 4)].symbol(), "└");
+        assert_eq!(row_text(&buffer, 5).trim_end(), "status ok");
+        assert_eq!(buffer[(7, 5)].fg, Color::Green);
+    }
+
+    #[test]
+    fn test_row_with_gap_and_block() {
+        let view = row([
+            text("left").block(Block::default().borders(Borders::ALL)),
+            text("right").grow(1.0),
+        ])
+        .gap(1);
+        let buffer = draw(&view, 20, 3);
+        assert_eq!(row_text(&buffer, 0), "┌────┐ right        ");
+        assert_eq!(row_text(&buffer, 1), "│left│              ");
+    }
+
+    #[test]
+    fn test_component_slot_and_overlay() {
+        let list = List::new(vec!["one".to_string()]);
+        let view = column([Element::component(&list).grow(1.0)])
+            .with_overlay(Some(text("popup").basis(1)));
+        let buffer = draw(&view, 10, 3);
+        assert!(row_text(&buffer, 0).starts_with("popup"));
+        assert_eq!(buffer[(9, 0)].symbol(), "╮");
+        assert_eq!(buffer[(0, 1)].symbol(), "│");
+    }
+
+    #[test]
+    fn test_natural_size_of_nested_containers() {
+        let inner = row([text("ab"), text("cde")]).gap(1).padding(Padding::horizontal(1));
+        assert_eq!(inner.natural_size(Direction::Horizontal), 8);
+        let outer = column([inner, text("x\ny")]);
+        assert_eq!(outer.natural_size(Direction::Vertical), 3);
+        assert_eq!(outer.natural_size(Direction::Horizontal), 8);
+    }
+
+    #[test]
+    fn test_layout_reports_keyed_areas() {
+        let view = column([
+            text("title").key("title"),
+            row([text("a").grow(1.0).key("left"), text("b").grow(1.0).key("right")])
+                .grow(1.0)
+                .block(Block::default().borders(Borders::ALL)),
+        ]);
+        assert_eq!(
+            view.layout(Rect::new(0, 0, 10, 4)),
+            [
+                ("title".to_string(), Rect::new(0, 0, 10, 1)),
+                ("left".to_string(), Rect::new(1, 2, 4, 1)),
+                ("right".to_string(), Rect::new(5, 2, 4, 1)),
+            ]
+        );
+
+        let area = Rect::new(0, 0, 10, 4);
+        let mut buffer = Buffer::empty(area);
+        view.render_buffer(&mut buffer, area);
+        assert_eq!(row_text(&buffer, 2), "│a   b   │");
+    }
+}
@@ -0,0 +1,1157 @@
+//! Form component with typed fields, validation and submission
+//!
+//! A `Form` is a vertical stack of labelled fields. Each field can carry
+//! validators, the form can carry cross-field validators, and a valid form
+//! is turned into any `serde::Deserialize` type from its field map.
+
+use super::hit_test::HitRegions;
+use crate::events::Event;
+use crate::state::Action;
+use crate::style::{Slot, StyleSheet, Theme, Variant};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEventKind};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Clear, Paragraph};
+use serde::de::{self, value::MapDeserializer, IntoDeserializer, Visitor};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Value held by a field
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldValue {
+    Text(String),
+    Bool(bool),
+    Number(f64),
+    /// A number field left blank
+    Empty,
+}
+
+impl FieldValue {
+    /// Get the value as text, for text, radio and select fields
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            FieldValue::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Get the value as a bool, for checkboxes
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            FieldValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Get the value as a number, for number fields
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            FieldValue::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Check whether the value is blank text or an empty number
+    pub fn is_empty(&self) -> bool {
+        match self {
+            FieldValue::Text(text) => text.trim().is_empty(),
+            FieldValue::Empty => true,
+            FieldValue::Bool(_) | FieldValue::Number(_) => false,
+        }
+    }
+}
+
+/// Field values keyed by field name
+pub type FieldMap = BTreeMap<String, FieldValue>;
+
+/// Validator for a single field value
+pub type FieldValidator = Box<dyn Fn(&FieldValue) -> Result<(), String>>;
+
+/// Validator over all field values
+pub type FormValidator = Box<dyn Fn(&FieldMap) -> Result<(), ValidationError>>;
+
+/// Validation failure, attached to a field or to the whole form
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    pub field: Option<String>,
+    pub message: String,
+}
+
+impl ValidationError {
+    /// Error shown under the named field
+    pub fn field(name: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: Some(name.into()),
+            message: message.into(),
+        }
+    }
+
+    /// Error shown for the form as a whole
+    pub fn form(message: impl Into<String>) -> Self {
+        Self {
+            field: None,
+            message: message.into(),
+        }
+    }
+}
+
+/// Error returned by `Form::submit`
+#[derive(Debug)]
+pub enum FormError {
+    /// One or more validators failed; errors are shown in the form
+    Invalid(Vec<ValidationError>),
+    /// The field map could not be turned into the requested type
+    Deserialize(de::value::Error),
+}
+
+impl fmt::Display for FormError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormError::Invalid(errors) => write!(f, "form has {} invalid field(s)", errors.len()),
+            FormError::Deserialize(err) => write!(f, "cannot read form values: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for FormError {}
+
+/// Input widget backing a field
+#[derive(Debug, Clone)]
+enum FieldKind {
+    Text {
+        value: String,
+        cursor: usize,
+        placeholder: String,
+        masked: bool,
+    },
+    Checkbox {
+        checked: bool,
+    },
+    Radio {
+        options: Vec<String>,
+        selected: usize,
+    },
+    Select {
+        options: Vec<String>,
+        selected: usize,
+        open: bool,
+    },
+    Number {
+        input: String,
+        min: Option<f64>,
+        max: Option<f64>,
+        step: f64,
+    },
+}
+
+/// A labelled form field
+pub struct Field {
+    name: String,
+    label: String,
+    kind: FieldKind,
+    required: bool,
+    validators: Vec<FieldValidator>,
+    error: Option<String>,
+    touched: bool,
+}
+
+impl Field {
+    fn with_kind(name: impl Into<String>, label: impl Into<String>, kind: FieldKind) -> Self {
+        Field {
+            name: name.into(),
+            label: label.into(),
+            kind,
+            required: false,
+            validators: Vec::new(),
+            error: None,
+            touched: false,
+        }
+    }
+
+    /// Create a single-line text input
+    pub fn text(name: impl Into<String>, label: impl Into<String>) -> Self {
+        Self::with_kind(
+            name,
+            label,
+            FieldKind::Text {
+                value: String::new(),
+                cursor: 0,
+                placeholder: String::new(),
+                masked: false,
+            },
+        )
+    }
+
+    /// Create a checkbox
+    pub fn checkbox(name: impl Into<String>, label: impl Into<String>) -> Self {
+        Self::with_kind(name, label, FieldKind::Checkbox { checked: false })
+    }
+
+    /// Create a radio group showing every option inline
+    pub fn radio<S: Into<String>>(
+        name: impl Into<String>,
+        label: impl Into<String>,
+        options: impl IntoIterator<Item = S>,
+    ) -> Self {
+        let options = options.into_iter().map(Into::into).collect();
+        Self::with_kind(name, label, FieldKind::Radio { options, selected: 0 })
+    }
+
+    /// Create a drop-down select
+    pub fn select<S: Into<String>>(
+        name: impl Into<String>,
+        label: impl Into<String>,
+        options: impl IntoIterator<Item = S>,
+    ) -> Self {
+        let options = options.into_iter().map(Into::into).collect();
+        Self::with_kind(
+            name,
+            label,
+            FieldKind::Select {
+                options,
+                selected: 0,
+                open: false,
+            },
+        )
+    }
+
+    /// Create a numeric input
+    pub fn number(name: impl Into<String>, label: impl Into<String>) -> Self {
+        Self::with_kind(
+            name,
+            label,
+            FieldKind::Number {
+                input: String::new(),
+                min: None,
+                max: None,
+                step: 1.0,
+            },
+        )
+    }
+
+    /// Set the initial text of a text or number field
+    pub fn value(mut self, value: impl Into<String>) -> Self {
+        match &mut self.kind {
+            FieldKind::Text { value: text, cursor, .. } => {
+                *text = value.into();
+                *cursor = text.chars().count();
+            }
+            FieldKind::Number { input, .. } => *input = value.into(),
+            _ => {}
+        }
+        self
+    }
+
+    /// Set the initial state of a checkbox
+    pub fn checked(mut self, value: bool) -> Self {
+        if let FieldKind::Checkbox { checked } = &mut self.kind {
+            *checked = value;
+        }
+        self
+    }
+
+    /// Set the initially selected option of a radio group or select
+    pub fn selected(mut self, index: usize) -> Self {
+        if let FieldKind::Radio { options, selected } | FieldKind::Select { options, selected, .. } =
+            &mut self.kind
+        {
+            *selected = index.min(options.len().saturating_sub(1));
+        }
+        self
+    }
+
+    /// Set placeholder text shown in an empty text field
+    pub fn placeholder(mut self, text: impl Into<String>) -> Self {
+        if let FieldKind::Text { placeholder, .. } = &mut self.kind {
+            *placeholder = text.into();
+        }
+        self
+    }
+
+    /// Mask text input, for passwords
+    pub fn password(mut self) -> Self {
+        if let FieldKind::Text { masked, .. } = &mut self.kind {
+            *masked = true;
+        }
+        self
+    }
+
+    /// Limit a number field to an inclusive range
+    pub fn range(mut self, min: f64, max: f64) -> Self {
+        if let FieldKind::Number { min: lo, max: hi, .. } = &mut self.kind {
+            *lo = Some(min);
+            *hi = Some(max);
+        }
+        self
+    }
+
+    /// Set the increment used by Left/Right on a number field
+    pub fn step(mut self, step: f64) -> Self {
+        if let FieldKind::Number { step: s, .. } = &mut self.kind {
+            *s = step;
+        }
+        self
+    }
+
+    /// Reject blank values
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    /// Add a validator for this field
+    pub fn validate(mut self, validator: impl Fn(&FieldValue) -> Result<(), String> + 'static) -> Self {
+        self.validators.push(Box::new(validator));
+        self
+    }
+
+    /// Get field name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the error shown under the field, if any
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Current value of the field
+    pub fn field_value(&self) -> FieldValue {
+        match &self.kind {
+            FieldKind::Text { value, .. } => FieldValue::Text(value.clone()),
+            FieldKind::Checkbox { checked } => FieldValue::Bool(*checked),
+            FieldKind::Radio { options, selected } | FieldKind::Select { options, selected, .. } => {
+                FieldValue::Text(options.get(*selected).cloned().unwrap_or_default())
+            }
+            FieldKind::Number { input, .. } => match input.trim().parse() {
+                Ok(number) => FieldValue::Number(number),
+                Err(_) => FieldValue::Empty,
+            },
+        }
+    }
+
+    /// Run built-in and custom validators
+    fn check(&self) -> Result<(), String> {
+        let value = self.field_value();
+        if self.required && value.is_empty() {
+            return Err(format!("{} is required", self.label));
+        }
+        if let FieldKind::Number { input, min, max, .. } = &self.kind {
+            if !input.trim().is_empty() && value == FieldValue::Empty {
+                return Err(String::from("Must be a number"));
+            }
+            if let FieldValue::Number(number) = value {
+                if min.is_some_and(|min| number < min) || max.is_some_and(|max| number > max) {
+                    return Err(format!(
+                        "Must be between {} and {}",
+                        min.unwrap_or(f64::MIN),
+                        max.unwrap_or(f64::MAX)
+                    ));
+                }
+            }
+        }
+        self.validators.iter().try_for_each(|validator| validator(&value))
+    }
+
+    /// Rows used by the field when rendered
+    fn height(&self) -> u16 {
+        let label = match self.kind {
+            FieldKind::Checkbox { .. } => 0,
+            _ => 1,
+        };
+        label + 1 + u16::from(self.error.is_some())
+    }
+
+    /// Whether the field accepts typed characters
+    fn is_textual(&self) -> bool {
+        matches!(self.kind, FieldKind::Text { .. } | FieldKind::Number { .. })
+    }
+}
+
+/// Message types for form
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormMessage {
+    FocusNext,
+    FocusPrev,
+    Focus(usize),
+    Input(char),
+    Backspace,
+    Delete,
+    CursorLeft,
+    CursorRight,
+    Home,
+    End,
+    /// Toggle a checkbox or open/close a select
+    Toggle,
+    PrevOption,
+    NextOption,
+    /// Pick an option of a radio group or select (field, option)
+    SelectOption(usize, usize),
+    Submit,
+    Cancel,
+}
+
+/// Interactive areas recorded during render
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FormRegion {
+    Field(usize),
+    Option(usize, usize),
+    Submit,
+}
+
+/// Form combining labelled fields with validation and typed submission
+pub struct Form {
+    title: String,
+    fields: Vec<Field>,
+    /// Focused field; `fields.len()` is the submit button
+    focused: usize,
+    validators: Vec<FormValidator>,
+    form_error: Option<String>,
+    submit_label: String,
+    theme: Theme,
+    styles: StyleSheet,
+    hit_regions: HitRegions<FormRegion>,
+}
+
+impl Form {
+    /// Create an empty form
+    pub fn new() -> Self {
+        Form {
+            title: String::from("Form"),
+            fields: Vec::new(),
+            focused: 0,
+            validators: Vec::new(),
+            form_error: None,
+            submit_label: String::from("Submit"),
+            theme: Theme::default(),
+            styles: StyleSheet::new(),
+            hit_regions: HitRegions::new(),
+        }
+    }
+
+    /// Set form title
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Add a field
+    pub fn field(mut self, field: Field) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    /// Add a cross-field validator
+    pub fn validate(
+        mut self,
+        validator: impl Fn(&FieldMap) -> Result<(), ValidationError> + 'static,
+    ) -> Self {
+        self.validators.push(Box::new(validator));
+        self
+    }
+
+    /// Set submit button label
+    pub fn submit_button(mut self, label: impl Into<String>) -> Self {
+        self.submit_label = label.into();
+        self
+    }
+
+    /// Set theme
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Override slot styles (`base`, `border`, `text`, `placeholder`, `error`,
+    /// `item`, `item.selected`); `text` is drawn in its `Focus` variant when focused
+    pub fn styles(mut self, styles: StyleSheet) -> Self {
+        self.styles = styles;
+        self
+    }
+
+    /// Get the fields in display order
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+
+    /// Get the index of the focused field (`fields().len()` = submit button)
+    pub fn focused(&self) -> usize {
+        self.focused
+    }
+
+    /// Get the form-level error, if any
+    pub fn form_error(&self) -> Option<&str> {
+        self.form_error.as_deref()
+    }
+
+    /// Collect the current value of every field
+    pub fn values(&self) -> FieldMap {
+        self.fields
+            .iter()
+            .map(|field| (field.name.clone(), field.field_value()))
+            .collect()
+    }
+
+    /// Run all validators, showing errors under their fields
+    pub fn validate_all(&mut self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+        for field in &mut self.fields {
+            field.touched = true;
+            field.error = field.check().err();
+            if let Some(message) = &field.error {
+                errors.push(ValidationError::field(field.name.clone(), message.clone()));
+            }
+        }
+
+        self.form_error = None;
+        if errors.is_empty() {
+            let values = self.values();
+            for validator in &self.validators {
+                if let Err(error) = validator(&values) {
+                    errors.push(error);
+                }
+            }
+            for error in &errors {
+                let target = error
+                    .field
+                    .as_ref()
+                    .and_then(|name| self.fields.iter_mut().find(|field| &field.name == name));
+                match target {
+                    Some(field) => field.error = Some(error.message.clone()),
+                    None => self.form_error = Some(error.message.clone()),
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Validate and deserialize the field map into `T`
+    pub fn submit<T: for<'de> Deserialize<'de>>(&mut self) -> Result<T, FormError> {
+        self.validate_all().map_err(FormError::Invalid)?;
+        let deserializer = MapDeserializer::new(self.values().into_iter());
+        T::deserialize(deserializer).map_err(FormError::Deserialize)
+    }
+
+    fn focused_field(&mut self) -> Option<&mut Field> {
+        self.fields.get_mut(self.focused)
+    }
+
+    /// Move focus, validating the field being left if it was edited
+    fn set_focus(&mut self, index: usize) {
+        let index = index.min(self.fields.len());
+        if index == self.focused {
+            return;
+        }
+        if let Some(field) = self.focused_field() {
+            if let FieldKind::Select { open, .. } = &mut field.kind {
+                *open = false;
+            }
+            if field.touched {
+                field.error = field.check().err();
+            }
+        }
+        self.focused = index;
+    }
+
+    /// Apply an edit to the focused field
+    fn edit(&mut self, msg: &FormMessage) {
+        let Some(field) = self.focused_field() else {
+            return;
+        };
+        field.touched = true;
+        match &mut field.kind {
+            FieldKind::Text { value, cursor, .. } => edit_text(value, cursor, msg),
+            FieldKind::Number { input, min, max, step } => match msg {
+                FormMessage::Input(ch) if ch.is_ascii_digit() || matches!(ch, '-' | '.') => {
+                    input.push(*ch)
+                }
+                FormMessage::Backspace => {
+                    input.pop();
+                }
+                FormMessage::PrevOption | FormMessage::NextOption => {
+                    let delta = if *msg == FormMessage::NextOption { *step } else { -*step };
+                    let mut number = input.trim().parse().unwrap_or(0.0) + delta;
+                    if let Some(min) = min {
+                        number = number.max(*min);
+                    }
+                    if let Some(max) = max {
+                        number = number.min(*max);
+                    }
+                    *input = number.to_string();
+                }
+                _ => {}
+            },
+            FieldKind::Checkbox { checked } => {
+                if *msg == FormMessage::Toggle {
+                    *checked = !*checked;
+                }
+            }
+            FieldKind::Radio { options, selected } => {
+                cycle_option(selected, options.len(), msg);
+            }
+            FieldKind::Select { options, selected, open } => {
+                if *msg == FormMessage::Toggle {
+                    *open = !*open;
+                } else {
+                    cycle_option(selected, options.len(), msg);
+                }
+            }
+        }
+    }
+
+    /// Draw one field; returns the drop-down to overlay if a select is open
+    fn render_field<'a>(
+        &self,
+        frame: &mut Frame<'_>,
+        index: usize,
+        field: &'a Field,
+        area: Rect,
+    ) -> Option<(usize, &'a [String], usize, Rect)> {
+        let focused = self.focused == index;
+        let mut y = area.y;
+        let mut dropdown = None;
+
+        if !matches!(field.kind, FieldKind::Checkbox { .. }) {
+            let label = Paragraph::new(field.label.as_str()).style(self.focus_style(focused));
+            frame.render_widget(label, Rect::new(area.x, y, area.width, 1));
+            y += 1;
+        }
+
+        let input_area = Rect::new(area.x, y, area.width, 1);
+        let input_style = self.focus_style(focused);
+        let line = match &field.kind {
+            FieldKind::Text {
+                value,
+                cursor,
+                placeholder,
+                masked,
+            } => {
+                let shown: String = if *masked {
+                    "•".repeat(value.chars().count())
+                } else {
+                    value.clone()
+                };
+                if shown.is_empty() && !focused {
+                    Line::from(Span::styled(
+                        format!("  {}", placeholder),
+                        self.style(Slot::Placeholder, Variant::Normal),
+                    ))
+                } else if focused {
+                    let before: String = shown.chars().take(*cursor).collect();
+                    let at: String = shown.chars().skip(*cursor).take(1).collect();
+                    let after: String = shown.chars().skip(*cursor + 1).collect();
+                    let at = if at.is_empty() { String::from(" ") } else { at };
+                    Line::from(vec![
+                        Span::styled("> ", input_style),
+                        Span::raw(before),
+                        Span::styled(at, Style::default().add_modifier(Modifier::REVERSED)),
+                        Span::raw(after),
+                    ])
+                } else {
+                    Line::from(format!("  {}", shown))
+                }
+            }
+            FieldKind::Number { input, .. } => Line::from(vec![
+                Span::styled("◂ ", input_style),
+                Span::raw(input.as_str()),
+                Span::styled(if focused { "▏" } else { " " }, input_style),
+                Span::styled(" ▸", input_style),
+            ]),
+            FieldKind::Checkbox { checked } => Line::from(vec![
+                Span::styled(if *checked { "[x] " } else { "[ ] " }, input_style),
+                Span::styled(field.label.as_str(), self.focus_style(focused)),
+            ]),
+            FieldKind::Radio { options, selected } => {
+                let mut spans = Vec::new();
+                let mut x = input_area.x;
+                for (option_index, option) in options.iter().enumerate() {
+                    let marker = if option_index == *selected { "(•) " } else { "( ) " };
+                    let text = format!("{}{}  ", marker, option);
+                    let width = text.chars().count() as u16;
+                    let option_area = Rect::new(x, input_area.y, width, 1).intersection(input_area);
+                    self.hit_regions
+                        .register(FormRegion::Option(index, option_index), option_area);
+                    x = x.saturating_add(width);
+                    let style = if option_index == *selected { input_style } else { Style::default() };
+                    spans.push(Span::styled(text, style));
+                }
+                Line::from(spans)
+            }
+            FieldKind::Select {
+                options,
+                selected,
+                open,
+            } => {
+                if *open {
+                    dropdown = Some((index, options.as_slice(), *selected, input_area));
+                }
+                let current = options.get(*selected).map(String::as_str).unwrap_or("");
+                Line::from(vec![
+                    Span::styled(format!("{} ", current), input_style),
+                    Span::styled(if *open { "▴" } else { "▾" }, input_style),
+                ])
+            }
+        };
+        frame.render_widget(Paragraph::new(line), input_area);
+        y += 1;
+
+        if let Some(error) = &field.error {
+            let error = Paragraph::new(error.as_str()).style(self.style(Slot::Error, Variant::Normal));
+            frame.render_widget(error, Rect::new(area.x, y, area.width, 1));
+        }
+        dropdown
+    }
+
+    /// Style for a slot in the given variant
+    fn style(&self, slot: Slot, variant: Variant) -> Style {
+        self.styles.resolve(&self.theme, slot, variant)
+    }
+
+    fn focus_style(&self, focused: bool) -> Style {
+        self.style(Slot::Text, if focused { Variant::Focus } else { Variant::Normal })
+    }
+}
+
+impl Default for Form {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Apply a cursor or editing message to a text buffer
+fn edit_text(value: &mut String, cursor: &mut usize, msg: &FormMessage) {
+    let byte_at = |value: &String, index: usize| {
+        value
+            .char_indices()
+            .nth(index)
+            .map(|(byte, _)| byte)
+            .unwrap_or(value.len())
+    };
+    let len = value.chars().count();
+    match msg {
+        FormMessage::Input(ch) => {
+            let byte = byte_at(value, *cursor);
+            value.insert(byte, *ch);
+            *cursor += 1;
+        }
+        FormMessage::Backspace if *cursor > 0 => {
+            *cursor -= 1;
+            let byte = byte_at(value, *cursor);
+            value.remove(byte);
+        }
+        FormMessage::Delete if *cursor < len => {
+            let byte = byte_at(value, *cursor);
+            value.remove(byte);
+        }
+        FormMessage::CursorLeft => *cursor = cursor.saturating_sub(1),
+        FormMessage::CursorRight => *cursor = (*cursor + 1).min(len),
+        FormMessage::Home => *cursor = 0,
+        FormMessage::End => *cursor = len,
+        _ => {}
+    }
+}
+
+/// Move a selection with wrap-around for Prev/NextOption
+fn cycle_option(selected: &mut usize, count: usize, msg: &FormMessage) {
+    if count == 0 {
+        return;
+    }
+    match msg {
+        FormMessage::NextOption => *selected = (*selected + 1) % count,
+        FormMessage::PrevOption => *selected = (*selected + count - 1) % count,
+        _ => {}
+    }
+}
+
+impl super::Component for Form {
+    type Message = FormMessage;
+
+    fn update(&mut self, msg: Self::Message) -> Option<Action> {
+        match msg {
+            FormMessage::FocusNext => self.set_focus((self.focused + 1) % (self.fields.len() + 1)),
+            FormMessage::FocusPrev => {
+                let count = self.fields.len() + 1;
+                self.set_focus((self.focused + count - 1) % count)
+            }
+            FormMessage::Focus(index) => self.set_focus(index),
+            FormMessage::SelectOption(index, option) => {
+                self.set_focus(index);
+                if let Some(field) = self.fields.get_mut(index) {
+                    field.touched = true;
+                    if let FieldKind::Radio { selected, options }
+                    | FieldKind::Select { selected, options, .. } = &mut field.kind
+                    {
+                        *selected = option.min(options.len().saturating_sub(1));
+                    }
+                    if let FieldKind::Select { open, .. } = &mut field.kind {
+                        *open = false;
+                    }
+                }
+            }
+            FormMessage::Submit => {
+                return self.validate_all().ok().map(|_| Action::Confirm);
+            }
+            FormMessage::Cancel => return Some(Action::Cancel),
+            edit => self.edit(&edit),
+        }
+        None
+    }
+
+    fn render(&self, frame: &mut Frame<'_>) {
+        let area = frame.area();
+        self.render_in(frame, area);
+    }
+
+    fn render_in(&self, frame: &mut Frame<'_>, area: Rect) {
+        self.hit_regions.clear();
+
+        let block = self.theme.border_style.apply(Block::default())
+            .title(self.title.as_str())
+            .border_style(self.style(Slot::Border, Variant::Normal))
+            .style(self.style(Slot::Base, Variant::Normal));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let mut y = inner.y;
+        let mut dropdown = None;
+        for (index, field) in self.fields.iter().enumerate() {
+            let height = field.height();
+            if y + height > inner.bottom() {
+                break;
+            }
+            let field_area = Rect::new(inner.x, y, inner.width, height);
+            self.hit_regions.register(FormRegion::Field(index), field_area);
+            if let Some(list) = self.render_field(frame, index, field, field_area) {
+                dropdown = Some(list);
+            }
+            y += height + 1;
+        }
+
+        if let Some(message) = &self.form_error {
+            if y < inner.bottom() {
+                let error = Paragraph::new(message.as_str()).style(self.style(Slot::Error, Variant::Normal));
+                frame.render_widget(error, Rect::new(inner.x, y, inner.width, 1));
+                y += 1;
+            }
+        }
+
+        if y < inner.bottom() {
+            let focused = self.focused == self.fields.len();
+            let label = format!("[ {} ]", self.submit_label);
+            let button_area = Rect::new(inner.x, y, label.chars().count() as u16, 1).intersection(inner);
+            frame.render_widget(Paragraph::new(label).style(self.focus_style(focused)), button_area);
+            self.hit_regions.register(FormRegion::Submit, button_area);
+        }
+
+        // Open drop-downs are drawn last so they cover the fields below
+        if let Some((index, options, selected, anchor)) = dropdown {
+            let block = self
+                .theme
+                .border_style
+                .apply(Block::default())
+                .border_style(self.style(Slot::Border, Variant::Focus));
+            let chrome = if self.theme.border_style.border_set().is_some() { 2 } else { 0 };
+            let height = (options.len() as u16 + chrome).min(area.bottom().saturating_sub(anchor.bottom()));
+            let list_area = Rect::new(anchor.x, anchor.bottom(), anchor.width, height).intersection(area);
+            let list_inner = block.inner(list_area);
+            frame.render_widget(Clear, list_area);
+            frame.render_widget(block, list_area);
+            for (option_index, option) in options.iter().enumerate() {
+                let row = list_inner.y + option_index as u16;
+                if row >= list_inner.bottom() {
+                    break;
+                }
+                let row_area = Rect::new(list_inner.x, row, list_inner.width, 1);
+                let slot = if option_index == selected { Slot::ItemSelected } else { Slot::Item };
+                let style = self.style(slot, Variant::Normal);
+                frame.render_widget(Paragraph::new(option.as_str()).style(style), row_area);
+                self.hit_regions.register(FormRegion::Option(index, option_index), row_area);
+            }
+        }
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.theme = theme.clone();
+    }
+
+    fn handle_event(&mut self, event: &Event) -> Option<Self::Message> {
+        let field = self.fields.get(self.focused);
+        let select_open = matches!(field.map(|f| &f.kind), Some(FieldKind::Select { open: true, .. }));
+        let textual = field.is_some_and(Field::is_textual);
+        let is_text = matches!(field.map(|f| &f.kind), Some(FieldKind::Text { .. }));
+
+        match event {
+            Event::Key(KeyEvent {
+                code, modifiers, ..
+            }) => match code {
+                // An open drop-down captures navigation until closed
+                KeyCode::Up if select_open => Some(FormMessage::PrevOption),
+                KeyCode::Down if select_open => Some(FormMessage::NextOption),
+                KeyCode::Enter | KeyCode::Esc | KeyCode::Char(' ') if select_open => {
+                    Some(FormMessage::Toggle)
+                }
+                KeyCode::Tab | KeyCode::Down => Some(FormMessage::FocusNext),
+                KeyCode::BackTab | KeyCode::Up => Some(FormMessage::FocusPrev),
+                KeyCode::Esc => Some(FormMessage::Cancel),
+                KeyCode::Enter if field.is_some_and(|f| matches!(f.kind, FieldKind::Select { .. })) => {
+                    Some(FormMessage::Toggle)
+                }
+                KeyCode::Enter => Some(FormMessage::Submit),
+                KeyCode::Left if is_text => Some(FormMessage::CursorLeft),
+                KeyCode::Right if is_text => Some(FormMessage::CursorRight),
+                KeyCode::Left => Some(FormMessage::PrevOption),
+                KeyCode::Right => Some(FormMessage::NextOption),
+                KeyCode::Home if is_text => Some(FormMessage::Home),
+                KeyCode::End if is_text => Some(FormMessage::End),
+                KeyCode::Backspace if textual => Some(FormMessage::Backspace),
+                KeyCode::Delete if textual => Some(FormMessage::Delete),
+                KeyCode::Char(ch) if textual && !modifiers.contains(KeyModifiers::CONTROL) => {
+                    Some(FormMessage::Input(*ch))
+                }
+                KeyCode::Char(' ') => Some(FormMessage::Toggle),
+                _ => None,
+            },
+            Event::Mouse(mouse_event) => match mouse_event.kind {
+                MouseEventKind::Down(_) => {
+                    match self.hit_regions.hit(mouse_event.column, mouse_event.row)? {
+                        FormRegion::Option(index, option) => Some(FormMessage::SelectOption(index, option)),
+                        FormRegion::Submit => Some(FormMessage::Submit),
+                        FormRegion::Field(index) => match self.fields[index].kind {
+                            FieldKind::Checkbox { .. } | FieldKind::Select { .. }
+                                if self.focused == index =>
+                            {
+                                Some(FormMessage::Toggle)
+                            }
+                            _ => Some(FormMessage::Focus(index)),
+                        },
+                    }
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl<'de> IntoDeserializer<'de, de::value::Error> for FieldValue {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de> de::Deserializer<'de> for FieldValue {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            FieldValue::Text(text) => visitor.visit_string(text),
+            FieldValue::Bool(value) => visitor.visit_bool(value),
+            // Whole numbers deserialize into integer fields as well as floats
+            FieldValue::Number(number) if number.fract() == 0.0 && number.abs() < 9.0e15 => {
+                visitor.visit_i64(number as i64)
+            }
+            FieldValue::Number(number) => visitor.visit_f64(number),
+            FieldValue::Empty => visitor.visit_none(),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            FieldValue::Empty => visitor.visit_none(),
+            FieldValue::Text(ref text) if text.is_empty() => visitor.visit_none(),
+            value => visitor.visit_some(value),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self {
+            // Radio and select options map onto unit enum variants
+            FieldValue::Text(text) => visitor.visit_enum(text.into_deserializer()),
+            value => value.deserialize_any(visitor).map_err(|_| {
+                de::Error::custom(format!("expected one of {:?} for {}", variants, name))
+            }),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::Component;
+    use crossterm::event::{MouseButton, MouseEvent};
+    use ratatui::backend::TestBackend;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    enum Engine {
+        Postgres,
+        Sqlite,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Connection {
+        host: String,
+        port: u16,
+        tls: bool,
+        engine: Engine,
+        timeout: Option<f64>,
+    }
+
+    fn connection_form() -> Form {
+        Form::new()
+            .title("Connection")
+            .field(Field::text("host", "Host").required())
+            .field(Field::number("port", "Port").range(1.0, 65535.0).value("5432"))
+            .field(Field::checkbox("tls", "Use TLS"))
+            .field(Field::radio("engine", "Engine", ["Postgres", "Sqlite"]))
+            .field(Field::number("timeout", "Timeout"))
+    }
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::from(code))
+    }
+
+    fn send(form: &mut Form, event: Event) -> Option<Action> {
+        let msg = Component::handle_event(form, &event)?;
+        form.update(msg)
+    }
+
+    fn draw(form: &Form) -> ratatui::buffer::Buffer {
+        let mut terminal = ratatui::Terminal::new(TestBackend::new(50, 30)).unwrap();
+        terminal.draw(|frame| Component::render(form, frame)).unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    #[test]
+    fn test_submit_deserializes_typed_value() {
+        let mut form = connection_form();
+        for ch in "db.local".chars() {
+            send(&mut form, key(KeyCode::Char(ch)));
+        }
+        send(&mut form, key(KeyCode::Tab));
+        send(&mut form, key(KeyCode::Tab));
+        send(&mut form, key(KeyCode::Char(' ')));
+        send(&mut form, key(KeyCode::Tab));
+        send(&mut form, key(KeyCode::Right));
+
+        assert_eq!(send(&mut form, key(KeyCode::Enter)), Some(Action::Confirm));
+        let connection: Connection = form.submit().unwrap();
+        assert_eq!(
+            connection,
+            Connection {
+                host: String::from("db.local"),
+                port: 5432,
+                tls: true,
+                engine: Engine::Sqlite,
+                timeout: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_field_errors_block_submission_and_render_under_field() {
+        let mut form = connection_form();
+        assert_eq!(send(&mut form, key(KeyCode::Enter)), None);
+        assert_eq!(form.fields()[0].error(), Some("Host is required"));
+        assert!(matches!(form.submit::<Connection>(), Err(FormError::Invalid(_))));
+
+        let buffer = draw(&form);
+        let row: String = (1..20).map(|x| buffer[(x, 3)].symbol().to_string()).collect();
+        assert!(row.starts_with("Host is required"));
+    }
+
+    #[test]
+    fn test_placeholder_and_errors_use_style_slots() {
+        let mut form = connection_form()
+            .field(Field::text("note", "Note").placeholder("optional"))
+            .styles(StyleSheet::new().slot(Slot::Error, Style::default().fg(Color::Yellow)));
+        send(&mut form, key(KeyCode::Enter));
+
+        let buffer = draw(&form);
+        assert_eq!(buffer[(1, 3)].fg, Color::Yellow);
+        let (x, y) = (0..30)
+            .find_map(|y| {
+                let row: String = (0..50).map(|x| buffer[(x, y)].symbol().to_string()).collect();
+                row.find("optional").map(|at| (row[..at].chars().count() as u16, y))
+            })
+            .unwrap();
+        assert_eq!(buffer[(x, y)].fg, Theme::default().muted);
+    }
+
+    #[test]
+    fn test_number_range_and_custom_validators() {
+        let mut form = Form::new()
+            .field(Field::number("port", "Port").range(1.0, 10.0).value("42"))
+            .field(Field::text("name", "Name").validate(|value| match value.as_str() {
+                Some(name) if name.contains(' ') => Err(String::from("No spaces")),
+                _ => Ok(()),
+            }).value("a b"));
+        let errors = form.validate_all().unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(form.fields()[1].error(), Some("No spaces"));
+    }
+
+    #[test]
+    fn test_cross_field_validation() {
+        let mut form = Form::new()
+            .field(Field::text("password", "Password").password().value("secret"))
+            .field(Field::text("confirm", "Confirm").password().value("secrets"))
+            .validate(|values| {
+                if values["password"] == values["confirm"] {
+                    Ok(())
+                } else {
+                    Err(ValidationError::field("confirm", "Passwords do not match"))
+                }
+            });
+        assert!(form.validate_all().is_err());
+        assert_eq!(form.fields()[1].error(), Some("Passwords do not match"));
+    }
+
+    #[test]
+    fn test_focus_moves_between_fields_and_validates_on_blur() {
+        let mut form = connection_form();
+        send(&mut form, key(KeyCode::Char('x')));
+        send(&mut form, key(KeyCode::Backspace));
+        send(&mut form, key(KeyCode::Tab));
+        assert_eq!(form.focused(), 1);
+        assert_eq!(form.fields()[0].error(), Some("Host is required"));
+
+        send(&mut form, key(KeyCode::BackTab));
+        send(&mut form, key(KeyCode::BackTab));
+        assert_eq!(form.focused(), form.fields().len());
+    }
+
+    #[test]
+    fn test_select_opens_and_picks_with_mouse() {
+        let mut form = Form::new().field(Field::select("level", "Level", ["low", "mid", "high"]));
+        send(&mut form, key(KeyCode::Enter));
+        draw(&form);
+
+        let option = form.hit_regions.area(FormRegion::Option(0, 2)).unwrap();
+        let click = Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: option.x,
+            row: option.y,
+            modifiers: KeyModifiers::NONE,
+        });
+        send(&mut form, click);
+        assert_eq!(form.values()["level"], FieldValue::Text(String::from("high")));
+    }
+}
@@ -0,0 +1,106 @@
+//! Hit-test regions for mouse interaction
+//!
+//! Components register the `Rect` of each interactive element while rendering
+//! and consult the registry when an `Event::Mouse` arrives, so hit-testing
+//! always matches what is actually on screen regardless of terminal size.
+
+use ratatui::layout::{Position, Rect};
+use std::cell::RefCell;
+
+/// Registry of interactive regions recorded during render
+///
+/// `Component::render` takes `&self`, so regions are stored behind a
+/// `RefCell` and can be refreshed on every frame.
+#[derive(Debug, Clone)]
+pub struct HitRegions<K> {
+    regions: RefCell<Vec<(K, Rect)>>,
+}
+
+impl<K: Copy + PartialEq> HitRegions<K> {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self {
+            regions: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Remove all regions (call at the start of each render)
+    pub fn clear(&self) {
+        self.regions.borrow_mut().clear();
+    }
+
+    /// Register an interactive region, replacing any previous area for `key`
+    pub fn register(&self, key: K, area: Rect) {
+        let mut regions = self.regions.borrow_mut();
+        regions.retain(|(k, _)| *k != key);
+        if !area.is_empty() {
+            regions.push((key, area));
+        }
+    }
+
+    /// Find the region under the given cell, preferring the last registered
+    pub fn hit(&self, column: u16, row: u16) -> Option<K> {
+        let position = Position::new(column, row);
+        self.regions
+            .borrow()
+            .iter()
+            .rev()
+            .find(|(_, area)| area.contains(position))
+            .map(|(key, _)| *key)
+    }
+
+    /// Get the area registered for `key`
+    pub fn area(&self, key: K) -> Option<Rect> {
+        self.regions
+            .borrow()
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, area)| *area)
+    }
+
+    /// Check whether any regions are registered
+    pub fn is_empty(&self) -> bool {
+        self.regions.borrow().is_empty()
+    }
+}
+
+impl<K: Copy + PartialEq> Default for HitRegions<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hit_returns_registered_key() {
+        let regions = HitRegions::new();
+        regions.register(1, Rect::new(2, 3, 4, 1));
+        assert_eq!(regions.hit(2, 3), Some(1));
+        assert_eq!(regions.hit(5, 3), Some(1));
+        assert_eq!(regions.hit(6, 3), None);
+        assert_eq!(regions.hit(2, 4), None);
+    }
+
+    #[test]
+    fn test_later_regions_win_on_overlap() {
+        let regions = HitRegions::new();
+        regions.register('a', Rect::new(0, 0, 10, 10));
+        regions.register('b', Rect::new(2, 2, 2, 2));
+        assert_eq!(regions.hit(3, 3), Some('b'));
+        assert_eq!(regions.hit(0, 0), Some('a'));
+    }
+
+    #[test]
+    fn test_register_replaces_and_clear_empties() {
+        let regions = HitRegions::new();
+        regions.register(1, Rect::new(0, 0, 1, 1));
+        regions.register(1, Rect::new(5, 5, 1, 1));
+        assert_eq!(regions.area(1), Some(Rect::new(5, 5, 1, 1)));
+        assert_eq!(regions.hit(0, 0), None);
+        regions.clear();
+        assert!(regions.is_empty());
+    }
+}
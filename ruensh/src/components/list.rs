@@ -1,11 +1,13 @@
 //! List component for displaying selectable items
 
+use super::element::{column, Element};
 use crate::events::Event;
 use crate::state::Action;
-use crate::style::Theme;
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::style::{markup, Slot, StyleSheet, Theme, Variant};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEventKind};
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Borders, List as RatatuiList, ListItem, Paragraph};
+use ratatui::widgets::{Block, List as RatatuiList, ListItem, ListState};
+use std::cell::Cell;
 
 /// Message types for list
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -14,6 +16,8 @@ pub enum ListMessage {
     Up,
     Down,
     Confirm,
+    /// The pointer moved onto an item, or off the items
+    Hover(Option<usize>),
 }
 
 /// List component for displaying and selecting from items
@@ -22,6 +26,16 @@ pub struct List {
     selected: usize,
     title: String,
     theme: Theme,
+    styles: StyleSheet,
+    focused: bool,
+    disabled: bool,
+    /// Parse items as rich text markup
+    markup: bool,
+    hovered: Option<usize>,
+    /// First visible item at the last render
+    offset: Cell<usize>,
+    /// Area inside the border at the last render
+    inner: Cell<Rect>,
 }
 
 impl List {
@@ -32,6 +46,13 @@ impl List {
             selected: 0,
             title: String::from("List"),
             theme: Theme::default(),
+            styles: StyleSheet::new(),
+            focused: false,
+            disabled: false,
+            markup: false,
+            hovered: None,
+            offset: Cell::new(0),
+            inner: Cell::new(Rect::default()),
         }
     }
 
@@ -47,6 +68,46 @@ impl List {
         self
     }
 
+    /// Override slot styles (`border`, `title`, `item`, `item.selected`)
+    pub fn styles(mut self, styles: StyleSheet) -> Self {
+        self.styles = styles;
+        self
+    }
+
+    /// Draw the list as focused
+    pub fn focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+
+    /// Disable the list; it is drawn muted and ignores input
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Parse items as rich text markup, e.g. `[bold]main.rs[/] [muted]modified[/]`
+    pub fn markup(mut self, markup: bool) -> Self {
+        self.markup = markup;
+        self
+    }
+
+    /// Change whether the list is drawn as focused
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    /// Change whether the list is disabled
+    pub fn set_disabled(&mut self, disabled: bool) {
+        self.disabled = disabled;
+        self.hovered = None;
+    }
+
+    /// Get the item under the pointer
+    pub fn hovered(&self) -> Option<usize> {
+        self.hovered
+    }
+
     /// Get currently selected item index
     pub fn selected(&self) -> usize {
         self.selected
@@ -57,6 +118,72 @@ impl List {
         self.items.get(self.selected).map(|s| s.as_str())
     }
 
+    /// Variant for the list as a whole
+    fn variant(&self) -> Variant {
+        if self.disabled {
+            Variant::Disabled
+        } else if self.focused {
+            Variant::Focus
+        } else {
+            Variant::Normal
+        }
+    }
+
+    /// Item at a screen position, using the last rendered layout
+    fn item_at(&self, column: u16, row: u16) -> Option<usize> {
+        let inner = self.inner.get();
+        if !inner.contains(Position::new(column, row)) {
+            return None;
+        }
+        // Items span one row per line of text
+        let mut top = inner.y;
+        for (index, item) in self.items.iter().enumerate().skip(self.offset.get()) {
+            top = top.saturating_add(self.item_text(item).height() as u16);
+            if row < top {
+                return Some(index);
+            }
+        }
+        None
+    }
+
+    /// Text of an item, parsed as markup if enabled
+    fn item_text<'a>(&'a self, item: &'a str) -> Text<'a> {
+        if self.markup {
+            markup(item, &self.theme)
+        } else {
+            Text::raw(item)
+        }
+    }
+
+    /// Draw the items inside the border
+    fn render_items(&self, frame: &mut Frame<'_>, area: Rect) {
+        let variant = self.variant();
+        let style = |slot, variant| self.styles.resolve(&self.theme, slot, variant);
+        let items: Vec<ListItem> = self
+            .items
+            .iter()
+            .enumerate()
+            .map(|(idx, item)| {
+                let item_variant = match variant {
+                    Variant::Disabled => Variant::Disabled,
+                    _ if self.hovered == Some(idx) => Variant::Hover,
+                    _ => Variant::Normal,
+                };
+                ListItem::new(self.item_text(item)).style(style(Slot::Item, item_variant))
+            })
+            .collect();
+
+        let list = RatatuiList::new(items)
+            .highlight_style(style(Slot::ItemSelected, variant))
+            .highlight_symbol("▸ ");
+
+        let mut state = ListState::default()
+            .with_offset(self.offset.get())
+            .with_selected(Some(self.selected));
+        frame.render_stateful_widget(list, area, &mut state);
+        self.offset.set(state.offset());
+    }
+
     /// Move selection up
     fn move_up(&mut self) {
         if self.selected > 0 {
@@ -92,48 +219,45 @@ impl super::Component for List {
                 None
             }
             ListMessage::Confirm => Some(Action::Confirm),
+            ListMessage::Hover(index) => {
+                self.hovered = index;
+                None
+            }
         }
     }
 
     fn render(&self, frame: &mut Frame<'_>) {
         let area = frame.area();
+        self.render_in(frame, area);
+    }
 
-        let items: Vec<ListItem> = self
-            .items
-            .iter()
-            .enumerate()
-            .map(|(idx, item)| {
-                let _content = if idx == self.selected {
-                    Paragraph::new(format!("▸ {}", item))
-                        .style(Style::default().fg(self.theme.primary).add_modifier(Modifier::BOLD))
-                } else {
-                    Paragraph::new(format!("  {}", item))
-                        .style(Style::default().fg(Color::White))
-                };
-                ListItem::new("")
-            })
-            .collect();
+    fn render_in(&self, frame: &mut Frame<'_>, area: Rect) {
+        let view = self.view();
+        let items = view.layout(area).into_iter().find(|(key, _)| key == "items");
+        self.inner.set(items.map_or(Rect::default(), |(_, inner)| inner));
+        view.render(frame, area);
+    }
 
-        let list = RatatuiList::new(items)
-            .block(
-                Block::default()
-                    .title(self.title.as_str())
-                    .borders(Borders::ALL)
-                    .border_type(ratatui::widgets::BorderType::Rounded)
-                    .border_style(Style::default().fg(self.theme.secondary))
-                    .style(Style::default().bg(Color::Black).fg(Color::White)),
-            )
-            .highlight_style(
-                Style::default()
-                    .fg(self.theme.primary)
-                    .add_modifier(Modifier::BOLD),
-            )
-            .highlight_symbol("▸ ");
+    /// The themed block around a slot keyed `items`
+    fn view(&self) -> Element<'_> {
+        let variant = self.variant();
+        let style = |slot, variant| self.styles.resolve(&self.theme, slot, variant);
+        let block = self.theme.border_style.apply(Block::default())
+            .title(self.title.as_str())
+            .title_style(style(Slot::Title, variant))
+            .border_style(style(Slot::Border, variant))
+            .style(style(Slot::Base, variant));
+        column([Element::slot(|frame, area| self.render_items(frame, area)).grow(1.0).key("items")]).block(block)
+    }
 
-        frame.render_widget(list, area);
+    fn set_theme(&mut self, theme: &Theme) {
+        self.theme = theme.clone();
     }
 
     fn handle_event(&mut self, event: &Event) -> Option<Self::Message> {
+        if self.disabled {
+            return None;
+        }
         match event {
             Event::Key(KeyEvent { code, .. }) => match code {
                 KeyCode::Up | KeyCode::Char('k') => Some(ListMessage::Up),
@@ -141,7 +265,113 @@ impl super::Component for List {
                 KeyCode::Enter => Some(ListMessage::Confirm),
                 _ => None,
             },
+            Event::Mouse(mouse_event) => {
+                let index = self.item_at(mouse_event.column, mouse_event.row);
+                match mouse_event.kind {
+                    MouseEventKind::Down(MouseButton::Left) => index.map(ListMessage::Select),
+                    MouseEventKind::Moved if index != self.hovered => Some(ListMessage::Hover(index)),
+                    _ => None,
+                }
+            }
             _ => None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::Component;
+    use crate::style::BorderStyle;
+    use crossterm::event::{KeyModifiers, MouseEvent};
+    use ratatui::backend::TestBackend;
+
+    fn list() -> List {
+        List::new(vec!["alpha".into(), "beta".into(), "gamma".into()])
+    }
+
+    fn draw(list: &List) -> Buffer {
+        let mut terminal = ratatui::Terminal::new(TestBackend::new(12, 5)).unwrap();
+        terminal.draw(|frame| list.render(frame)).unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    fn mouse(kind: MouseEventKind, row: u16) -> Event {
+        Event::Mouse(MouseEvent {
+            kind,
+            column: 3,
+            row,
+            modifiers: KeyModifiers::NONE,
+        })
+    }
+
+    #[test]
+    fn test_renders_items_with_slot_styles() {
+        let theme = Theme::light();
+        let list = list()
+            .theme(theme.clone())
+            .styles(StyleSheet::new().slot(Slot::Item, Style::default().fg(Color::Green)));
+        let buffer = draw(&list);
+
+        assert_eq!(buffer[(3, 1)].symbol(), "a");
+        assert_eq!(buffer[(3, 1)].fg, theme.primary);
+        assert_eq!(buffer[(3, 2)].symbol(), "b");
+        assert_eq!(buffer[(3, 2)].fg, Color::Green);
+        assert_eq!(buffer[(1, 2)].bg, theme.background);
+    }
+
+    #[test]
+    fn test_mouse_hover_and_select() {
+        let mut list = list();
+        draw(&list);
+
+        let msg = list.handle_event(&mouse(MouseEventKind::Moved, 3)).unwrap();
+        assert_eq!(msg, ListMessage::Hover(Some(2)));
+        list.update(msg);
+        assert_eq!(draw(&list)[(3, 3)].fg, list.theme.accent);
+
+        let msg = list.handle_event(&mouse(MouseEventKind::Down(MouseButton::Left), 2)).unwrap();
+        list.update(msg);
+        assert_eq!(list.selected(), 1);
+    }
+
+    #[test]
+    fn test_disabled_list_is_muted_and_ignores_input() {
+        let mut list = list().disabled(true);
+        assert_eq!(list.handle_event(&Event::Key(KeyEvent::from(KeyCode::Down))), None);
+        assert_eq!(draw(&list)[(3, 2)].fg, list.theme.muted);
+    }
+
+    #[test]
+    fn test_uses_theme_border_style() {
+        let ascii = list().theme(Theme::dark().set_border_style(BorderStyle::Ascii));
+        assert_eq!(draw(&ascii)[(0, 0)].symbol(), "+");
+
+        let borderless = list().theme(Theme::dark().set_border_style(BorderStyle::None));
+        // The title keeps its row
+        assert_eq!(draw(&borderless)[(2, 1)].symbol(), "a");
+    }
+
+    #[test]
+    fn test_markup_items() {
+        let list = List::new(vec!["first".into(), "[error]x[/]y".into()]).markup(true);
+        let buffer = draw(&list);
+        assert_eq!(buffer[(3, 2)].symbol(), "x");
+        assert_eq!(buffer[(3, 2)].fg, list.theme.error);
+        assert_eq!(buffer[(4, 2)].symbol(), "y");
+        assert_eq!(buffer[(4, 2)].fg, list.theme.text);
+    }
+
+    #[test]
+    fn test_mouse_maps_rows_to_multi_line_items() {
+        let mut list = List::new(vec!["one\ntwo".into(), "[bold]three[/]".into()]).markup(true);
+        let buffer = draw(&list);
+        assert_eq!(buffer[(3, 2)].symbol(), "t");
+
+        assert_eq!(list.item_at(3, 2), Some(0));
+        assert_eq!(list.item_at(3, 3), Some(1));
+        assert_eq!(list.item_at(3, 4), None);
+        let msg = list.handle_event(&mouse(MouseEventKind::Down(MouseButton::Left), 3)).unwrap();
+        assert_eq!(msg, ListMessage::Select(1));
+    }
+}
@@ -0,0 +1,466 @@
+//! Markdown block and inline parsing
+//!
+//! Covers the subset used in help screens and docs: ATX headings,
+//! paragraphs, bullet and ordered lists, block quotes, pipe tables, fenced
+//! code, thematic breaks, and inline emphasis, code spans and links.
+
+use ratatui::layout::Alignment;
+
+/// A block-level element
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Block {
+    Heading(u8, String),
+    Paragraph(String),
+    Quote(Vec<Block>),
+    /// Items with their number for ordered lists
+    List(Vec<(Option<u64>, Vec<Block>)>),
+    Code { lang: String, lines: Vec<String> },
+    Table {
+        header: Vec<String>,
+        align: Vec<Alignment>,
+        rows: Vec<Vec<String>>,
+    },
+    Rule,
+}
+
+/// Parse markdown source into blocks
+pub(super) fn parse(source: &str) -> Vec<Block> {
+    let lines: Vec<&str> = source.lines().collect();
+    parse_lines(&lines)
+}
+
+fn parse_lines(lines: &[&str]) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() {
+            i += 1;
+        } else if let Some(fence) = fence(trimmed) {
+            let lang = trimmed[fence.len()..].trim().to_string();
+            let mut code = Vec::new();
+            i += 1;
+            while i < lines.len() && !lines[i].trim_start().starts_with(fence) {
+                code.push(lines[i].to_string());
+                i += 1;
+            }
+            i += 1;
+            blocks.push(Block::Code { lang, lines: code });
+        } else if let Some((level, text)) = heading(trimmed) {
+            blocks.push(Block::Heading(level, text.to_string()));
+            i += 1;
+        } else if is_rule(trimmed) {
+            blocks.push(Block::Rule);
+            i += 1;
+        } else if trimmed.starts_with('>') {
+            let mut quoted = Vec::new();
+            while i < lines.len() && lines[i].trim_start().starts_with('>') {
+                let rest = &lines[i].trim_start()[1..];
+                quoted.push(rest.strip_prefix(' ').unwrap_or(rest));
+                i += 1;
+            }
+            blocks.push(Block::Quote(parse_lines(&quoted)));
+        } else if list_marker(line).is_some() {
+            let (list, next) = parse_list(lines, i);
+            blocks.push(list);
+            i = next;
+        } else if i + 1 < lines.len() && line.contains('|') && is_table_separator(lines[i + 1]) {
+            let header = table_cells(line);
+            let align = table_cells(lines[i + 1])
+                .iter()
+                .map(|cell| match (cell.starts_with(':'), cell.ends_with(':')) {
+                    (true, true) => Alignment::Center,
+                    (false, true) => Alignment::Right,
+                    _ => Alignment::Left,
+                })
+                .collect();
+            i += 2;
+            let mut rows = Vec::new();
+            while i < lines.len() && lines[i].contains('|') && !lines[i].trim().is_empty() {
+                rows.push(table_cells(lines[i]));
+                i += 1;
+            }
+            blocks.push(Block::Table { header, align, rows });
+        } else {
+            let mut text = trimmed.trim_end().to_string();
+            i += 1;
+            while i < lines.len() && !starts_block(lines[i]) {
+                text.push(' ');
+                text.push_str(lines[i].trim());
+                i += 1;
+            }
+            blocks.push(Block::Paragraph(text));
+        }
+    }
+    blocks
+}
+
+/// Check whether a line ends a paragraph
+fn starts_block(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.is_empty()
+        || fence(trimmed).is_some()
+        || heading(trimmed).is_some()
+        || is_rule(trimmed)
+        || trimmed.starts_with('>')
+        || list_marker(line).is_some()
+}
+
+fn fence(trimmed: &str) -> Option<&'static str> {
+    ["```", "~~~"].into_iter().find(|fence| trimmed.starts_with(fence))
+}
+
+fn heading(trimmed: &str) -> Option<(u8, &str)> {
+    let level = trimmed.chars().take_while(|c| *c == '#').count();
+    let rest = &trimmed[level..];
+    if (1..=6).contains(&level) && (rest.is_empty() || rest.starts_with(' ')) {
+        Some((level as u8, rest.trim().trim_end_matches('#').trim_end()))
+    } else {
+        None
+    }
+}
+
+fn is_rule(trimmed: &str) -> bool {
+    let chars: String = trimmed.chars().filter(|c| !c.is_whitespace()).collect();
+    chars.len() >= 3
+        && ['-', '*', '_']
+            .iter()
+            .any(|marker| chars.chars().all(|c| c == *marker))
+}
+
+/// Leading spaces and tabs, in bytes
+///
+/// Other whitespace such as U+3000 is content, and counting it would put
+/// column offsets inside multi-byte characters.
+fn indentation(line: &str) -> usize {
+    line.bytes().take_while(|b| *b == b' ' || *b == b'\t').count()
+}
+
+/// List marker on a line: (indent, content column, number for ordered items)
+fn list_marker(line: &str) -> Option<(usize, usize, Option<u64>)> {
+    let indent = indentation(line);
+    let rest = &line[indent..];
+    let (marker_len, number) = if rest.starts_with(['-', '*', '+']) {
+        (1, None)
+    } else {
+        let digits = rest.chars().take_while(char::is_ascii_digit).count();
+        if digits == 0 || digits > 9 || !rest[digits..].starts_with(['.', ')']) {
+            return None;
+        }
+        (digits + 1, rest[..digits].parse().ok())
+    };
+    let after = &rest[marker_len..];
+    if after.is_empty() {
+        return Some((indent, indent + marker_len + 1, number));
+    }
+    if !after.starts_with(' ') {
+        return None;
+    }
+    let spaces = indentation(after);
+    Some((indent, indent + marker_len + spaces.min(4), number))
+}
+
+/// Parse a list starting at `start`, returning it and the next line index
+fn parse_list(lines: &[&str], start: usize) -> (Block, usize) {
+    let (indent, _, first_number) = list_marker(lines[start]).expect("caller checked marker");
+    let ordered = first_number.is_some();
+    let mut items = Vec::new();
+    let mut i = start;
+
+    while i < lines.len() {
+        let Some((item_indent, content, number)) = list_marker(lines[i]) else {
+            break;
+        };
+        if item_indent != indent || number.is_some() != ordered {
+            break;
+        }
+        let mut body = vec![lines[i].get(content..).unwrap_or("")];
+        i += 1;
+        while i < lines.len() {
+            let line = lines[i];
+            let line_indent = indentation(line);
+            if line.trim().is_empty() {
+                // A blank line continues the item only if indented content follows
+                let next = lines[i + 1..].iter().find(|line| !line.trim().is_empty());
+                match next {
+                    Some(next) if indentation(next) >= content => {
+                        body.push("");
+                        i += 1;
+                    }
+                    _ => break,
+                }
+            } else if line_indent >= content {
+                body.push(line.get(content..).unwrap_or(""));
+                i += 1;
+            } else if line_indent <= indent && list_marker(line).is_some() || starts_block(line) {
+                break;
+            } else {
+                // Lazy continuation of the item's paragraph
+                body.push(line.trim_start());
+                i += 1;
+            }
+        }
+        items.push((number, parse_lines(&body)));
+
+        // Skip blank lines between items of the same list
+        let mut next = i;
+        while next < lines.len() && lines[next].trim().is_empty() {
+            next += 1;
+        }
+        match lines.get(next).and_then(|line| list_marker(line)) {
+            Some((next_indent, _, next_number))
+                if next_indent == indent && next_number.is_some() == ordered =>
+            {
+                i = next
+            }
+            _ => break,
+        }
+    }
+    (Block::List(items), i)
+}
+
+fn is_table_separator(line: &str) -> bool {
+    let cells = table_cells(line);
+    line.contains('-')
+        && !cells.is_empty()
+        && cells.iter().all(|cell| {
+            let inner = cell.trim_start_matches(':').trim_end_matches(':');
+            !inner.is_empty() && inner.chars().all(|c| c == '-')
+        })
+}
+
+fn table_cells(line: &str) -> Vec<String> {
+    let line = line.trim();
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = line.strip_suffix('|').unwrap_or(line);
+    line.split('|').map(|cell| cell.trim().to_string()).collect()
+}
+
+/// Inline formatting state of a run of text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(super) struct Emphasis {
+    pub bold: bool,
+    pub italic: bool,
+    pub strike: bool,
+    pub code: bool,
+}
+
+/// A run of inline text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Inline {
+    pub text: String,
+    pub emphasis: Emphasis,
+    /// Index into the link targets
+    pub link: Option<usize>,
+}
+
+/// Parse inline markdown, appending link targets to `urls`
+pub(super) fn inline(text: &str, urls: &mut Vec<String>) -> Vec<Inline> {
+    let mut out = Vec::new();
+    inline_into(text, Emphasis::default(), None, urls, &mut out);
+    out
+}
+
+fn inline_into(
+    text: &str,
+    mut emphasis: Emphasis,
+    link: Option<usize>,
+    urls: &mut Vec<String>,
+    out: &mut Vec<Inline>,
+) {
+    let mut buffer = String::new();
+    let flush = |buffer: &mut String, emphasis: Emphasis, out: &mut Vec<Inline>| {
+        if !buffer.is_empty() {
+            out.push(Inline {
+                text: std::mem::take(buffer),
+                emphasis,
+                link,
+            });
+        }
+    };
+
+    let mut i = 0;
+    while i < text.len() {
+        let rest = &text[i..];
+        let c = rest.chars().next().expect("index is on a char boundary");
+
+        if c == '\\' && rest[1..].starts_with(|c: char| c.is_ascii_punctuation()) {
+            let escaped = rest[1..].chars().next().expect("checked above");
+            buffer.push(escaped);
+            i += 1 + escaped.len_utf8();
+            continue;
+        }
+
+        if c == '`' {
+            let ticks = rest.chars().take_while(|c| *c == '`').count();
+            if let Some(end) = rest[ticks..].find(&rest[..ticks]) {
+                flush(&mut buffer, emphasis, out);
+                let code = rest[ticks..ticks + end].trim();
+                out.push(Inline {
+                    text: code.to_string(),
+                    emphasis: Emphasis { code: true, ..emphasis },
+                    link,
+                });
+                i += ticks + end + ticks;
+                continue;
+            }
+        }
+
+        if c == '[' && link.is_none() {
+            if let Some((label, url, length)) = link_at(rest) {
+                flush(&mut buffer, emphasis, out);
+                urls.push(url.to_string());
+                let index = urls.len() - 1;
+                inline_into(label, emphasis, Some(index), urls, out);
+                i += length;
+                continue;
+            }
+        }
+
+        if c == '<' && link.is_none() {
+            if let Some(end) = rest.find('>') {
+                let url = &rest[1..end];
+                if url.contains("://") && !url.contains(' ') {
+                    flush(&mut buffer, emphasis, out);
+                    urls.push(url.to_string());
+                    out.push(Inline {
+                        text: url.to_string(),
+                        emphasis,
+                        link: Some(urls.len() - 1),
+                    });
+                    i += end + 1;
+                    continue;
+                }
+            }
+        }
+
+        let delimiter = ["**", "__", "~~", "*", "_"]
+            .into_iter()
+            .find(|delimiter| rest.starts_with(delimiter));
+        if let Some(delimiter) = delimiter {
+            let flag = match delimiter {
+                "**" | "__" => emphasis.bold,
+                "~~" => emphasis.strike,
+                _ => emphasis.italic,
+            };
+            let before = text[..i].chars().next_back();
+            let after = rest[delimiter.len()..].chars().next();
+            // Underscores inside words are literal (snake_case)
+            let intraword = delimiter.starts_with('_')
+                && before.is_some_and(char::is_alphanumeric)
+                && after.is_some_and(char::is_alphanumeric);
+            let opens = !flag
+                && after.is_some_and(|c| !c.is_whitespace())
+                && rest[delimiter.len()..].contains(delimiter);
+            if !intraword && (flag || opens) {
+                flush(&mut buffer, emphasis, out);
+                match delimiter {
+                    "**" | "__" => emphasis.bold = !flag,
+                    "~~" => emphasis.strike = !flag,
+                    _ => emphasis.italic = !flag,
+                }
+                i += delimiter.len();
+                continue;
+            }
+        }
+
+        buffer.push(c);
+        i += c.len_utf8();
+    }
+    flush(&mut buffer, emphasis, out);
+}
+
+/// Match `[label](url)` at the start of `text`: (label, url, byte length)
+fn link_at(text: &str) -> Option<(&str, &str, usize)> {
+    let mut depth = 0;
+    let close = text.char_indices().find_map(|(index, c)| {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index);
+                }
+            }
+            _ => {}
+        }
+        None
+    })?;
+    let rest = text[close + 1..].strip_prefix('(')?;
+    let end = rest.find(')')?;
+    let url = rest[..end].split_whitespace().next().unwrap_or("");
+    Some((&text[1..close], url, close + 2 + end + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_structure() {
+        let source = "# Title\n\nSome *text*\ncontinues.\n\n- one\n- two\n  - nested\n3. three\n\n> quoted\n> more\n\n```rust\nfn main() {}\n```\n\n| a | b |\n|---|--:|\n| 1 | 2 |\n\n---";
+        let blocks = parse(source);
+        assert_eq!(blocks[0], Block::Heading(1, "Title".into()));
+        assert_eq!(blocks[1], Block::Paragraph("Some *text* continues.".into()));
+        let Block::List(items) = &blocks[2] else { panic!("expected list") };
+        assert_eq!(items.len(), 2);
+        assert!(matches!(&items[1].1[1], Block::List(nested) if nested.len() == 1));
+        let Block::List(ordered) = &blocks[3] else { panic!("expected ordered list") };
+        assert_eq!(ordered[0].0, Some(3));
+        assert_eq!(blocks[4], Block::Quote(vec![Block::Paragraph("quoted more".into())]));
+        assert_eq!(
+            blocks[5],
+            Block::Code {
+                lang: "rust".into(),
+                lines: vec!["fn main() {}".into()]
+            }
+        );
+        let Block::Table { align, rows, .. } = &blocks[6] else { panic!("expected table") };
+        assert_eq!(align, &[Alignment::Left, Alignment::Right]);
+        assert_eq!(rows, &[vec!["1".to_string(), "2".to_string()]]);
+        assert_eq!(blocks[7], Block::Rule);
+    }
+
+    #[test]
+    fn test_unicode_whitespace_is_not_indentation() {
+        let blocks = parse("- a\n\u{3000}b");
+        let Block::List(items) = &blocks[0] else { panic!("expected list") };
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].1, vec![Block::Paragraph("a b".into())]);
+        assert_eq!(list_marker("\u{3000}- c"), None);
+    }
+
+    #[test]
+    fn test_inline_emphasis_code_and_links() {
+        let mut urls = Vec::new();
+        let runs = inline("a **b _c_** `x*y` [see **docs**](http://d) snake_case \\*", &mut urls);
+        let texts: Vec<(&str, bool, bool, bool, Option<usize>)> = runs
+            .iter()
+            .map(|run| {
+                (
+                    run.text.as_str(),
+                    run.emphasis.bold,
+                    run.emphasis.italic,
+                    run.emphasis.code,
+                    run.link,
+                )
+            })
+            .collect();
+        assert_eq!(
+            texts,
+            [
+                ("a ", false, false, false, None),
+                ("b ", true, false, false, None),
+                ("c", true, true, false, None),
+                (" ", false, false, false, None),
+                ("x*y", false, false, true, None),
+                (" ", false, false, false, None),
+                ("see ", false, false, false, Some(0)),
+                ("docs", true, false, false, Some(0)),
+                (" snake_case *", false, false, false, None),
+            ]
+        );
+        assert_eq!(urls, ["http://d"]);
+    }
+}
@@ -0,0 +1,144 @@
+//! Line-based syntax highlighting for fenced code blocks
+//!
+//! A small tokenizer rather than a grammar: keywords, strings, numbers and
+//! line comments are coloured with theme roles. Unknown languages still get
+//! strings, numbers and comments.
+
+use crate::style::Theme;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Span;
+
+const RUST: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "false",
+    "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "type", "unsafe",
+    "use", "where", "while",
+];
+
+const PYTHON: &[&str] = &[
+    "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del", "elif",
+    "else", "except", "False", "finally", "for", "from", "if", "import", "in", "is", "lambda",
+    "None", "not", "or", "pass", "raise", "return", "True", "try", "while", "with", "yield",
+];
+
+const SHELL: &[&str] = &[
+    "case", "do", "done", "echo", "elif", "else", "esac", "export", "fi", "for", "function", "if",
+    "in", "local", "return", "then", "while",
+];
+
+const C_LIKE: &[&str] = &[
+    "break", "case", "class", "const", "continue", "default", "else", "enum", "export", "false",
+    "for", "func", "function", "go", "if", "import", "interface", "let", "new", "null", "package",
+    "private", "public", "return", "static", "struct", "switch", "this", "true", "type", "var",
+    "void", "while",
+];
+
+/// Keywords and line comment marker for a fence language
+fn language(lang: &str) -> (&'static [&'static str], Option<&'static str>) {
+    match lang.to_ascii_lowercase().as_str() {
+        "rust" | "rs" => (RUST, Some("//")),
+        "python" | "py" => (PYTHON, Some("#")),
+        "sh" | "bash" | "shell" | "zsh" | "console" => (SHELL, Some("#")),
+        "toml" | "yaml" | "yml" | "ini" => (&[], Some("#")),
+        "c" | "cpp" | "c++" | "go" | "java" | "js" | "javascript" | "ts" | "typescript" | "json" => {
+            (C_LIKE, Some("//"))
+        }
+        "sql" | "lua" => (&[], Some("--")),
+        _ => (&[], None),
+    }
+}
+
+/// Highlight one line of code on top of `base`
+pub(super) fn highlight(line: &str, lang: &str, theme: &Theme, base: Style) -> Vec<Span<'static>> {
+    let (keywords, comment) = language(lang);
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let push = |spans: &mut Vec<Span<'static>>, plain: &mut String, token: &str, style: Style| {
+        if !plain.is_empty() {
+            spans.push(Span::styled(std::mem::take(plain), base));
+        }
+        spans.push(Span::styled(token.to_string(), base.patch(style)));
+    };
+
+    let mut i = 0;
+    while i < line.len() {
+        let rest = &line[i..];
+        let c = rest.chars().next().expect("index is on a char boundary");
+
+        if comment.is_some_and(|marker| rest.starts_with(marker)) {
+            let style = Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC);
+            push(&mut spans, &mut plain, rest, style);
+            return spans;
+        }
+
+        // Rust uses `'` for lifetimes, so only double quotes start strings there
+        let quote = c == '"' || c == '`' || (c == '\'' && keywords != RUST);
+        if quote {
+            let mut end = rest.len();
+            let mut escaped = false;
+            for (index, next) in rest.char_indices().skip(1) {
+                if escaped {
+                    escaped = false;
+                } else if next == '\\' {
+                    escaped = true;
+                } else if next == c {
+                    end = index + 1;
+                    break;
+                }
+            }
+            push(&mut spans, &mut plain, &rest[..end], Style::default().fg(theme.success));
+            i += end;
+            continue;
+        }
+
+        if c.is_alphanumeric() || c == '_' {
+            let end = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+                .unwrap_or(rest.len());
+            let word = &rest[..end];
+            if c.is_ascii_digit() {
+                push(&mut spans, &mut plain, word, Style::default().fg(theme.warning));
+                i += end;
+                continue;
+            }
+            // Dots only belong to numbers
+            let word = &word[..word.find('.').unwrap_or(word.len())];
+            if keywords.contains(&word) {
+                let style = Style::default().fg(theme.primary).add_modifier(Modifier::BOLD);
+                push(&mut spans, &mut plain, word, style);
+            } else {
+                plain.push_str(word);
+            }
+            i += word.len();
+            continue;
+        }
+
+        plain.push(c);
+        i += c.len_utf8();
+    }
+    if !plain.is_empty() {
+        spans.push(Span::styled(plain, base));
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens_get_theme_roles() {
+        let theme = Theme::dark();
+        let spans = highlight("let x = \"a\\\"b\" + 42; // done", "rust", &theme, Style::default());
+        let find = |text: &str| spans.iter().find(|span| span.content == text).expect(text).style;
+        assert_eq!(find("let").fg, Some(theme.primary));
+        assert_eq!(find("\"a\\\"b\"").fg, Some(theme.success));
+        assert_eq!(find("42").fg, Some(theme.warning));
+        assert_eq!(find("// done").fg, Some(theme.muted));
+        assert_eq!(find(" x = ").fg, None);
+
+        let spans = highlight("fn main", "text", &theme, Style::default());
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].content, "fn main");
+    }
+}
@@ -0,0 +1,870 @@
+//! Markdown viewer for help screens and documentation
+//!
+//! The source is parsed once into blocks and laid out into styled lines for
+//! the current width; the layout is cached until the width or theme changes.
+//! Links are focusable with Tab and followed with Enter or a click. Links to
+//! `#anchor` scroll to the matching heading, anything else is returned to the
+//! application as `Action::Custom(url)`.
+
+mod blocks;
+mod highlight;
+
+use super::Component;
+use crate::events::Event;
+use crate::state::Action;
+use crate::style::Theme;
+use blocks::{Block, Emphasis, Inline};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEventKind};
+use ratatui::prelude::*;
+use ratatui::widgets::{Scrollbar, ScrollbarOrientation, ScrollbarState};
+use std::cell::{Cell, RefCell};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Lines moved per mouse wheel step
+const WHEEL_STEP: i32 = 3;
+
+/// Message types for the markdown viewer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MarkdownMessage {
+    /// Scroll by a number of lines
+    ScrollBy(i32),
+    PageUp,
+    PageDown,
+    Top,
+    Bottom,
+    /// Focus the next link, scrolling it into view
+    NextLink,
+    /// Focus the previous link, scrolling it into view
+    PrevLink,
+    /// Follow the link with this index
+    Follow(usize),
+}
+
+/// Where a link was drawn; wrapped links have one spot per line
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LinkSpot {
+    line: usize,
+    column: u16,
+    width: u16,
+    link: usize,
+}
+
+/// Styled lines for one width
+#[derive(Debug, Default)]
+struct Rendered {
+    width: u16,
+    lines: Vec<Line<'static>>,
+    spots: Vec<LinkSpot>,
+    /// Link targets in document order
+    urls: Vec<String>,
+    /// Heading slugs and their line
+    anchors: Vec<(String, usize)>,
+}
+
+/// Scrollable view of a markdown document
+///
+/// Keys: Up/Down (or `k`/`j`), PageUp/PageDown, Home/End, Tab/BackTab to
+/// move between links and Enter to follow the focused one. The mouse wheel
+/// scrolls and clicking a link follows it.
+pub struct Markdown {
+    blocks: Vec<Block>,
+    offset: usize,
+    focused_link: Option<usize>,
+    /// Text area at the last render
+    viewport: Cell<Rect>,
+    layout: RefCell<Option<Rendered>>,
+    theme: Theme,
+}
+
+impl Markdown {
+    /// Create a viewer for markdown source
+    pub fn new(source: &str) -> Self {
+        Markdown {
+            blocks: blocks::parse(source),
+            offset: 0,
+            focused_link: None,
+            viewport: Cell::new(Rect::default()),
+            layout: RefCell::new(None),
+            theme: Theme::default(),
+        }
+    }
+
+    /// Set theme
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Replace the document, scrolling back to the top
+    pub fn set_source(&mut self, source: &str) {
+        self.blocks = blocks::parse(source);
+        self.offset = 0;
+        self.focused_link = None;
+        self.layout.replace(None);
+    }
+
+    /// First visible line
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Target of the focused link
+    pub fn focused_link(&self) -> Option<String> {
+        let index = self.focused_link?;
+        self.with_layout(self.viewport.get().width, |rendered| rendered.urls.get(index).cloned())
+    }
+
+    /// Run `f` on the layout for `width`, laying the document out if needed
+    fn with_layout<R>(&self, width: u16, f: impl FnOnce(&Rendered) -> R) -> R {
+        let mut cache = self.layout.borrow_mut();
+        if cache.as_ref().is_none_or(|rendered| rendered.width != width) {
+            *cache = Some(Writer::layout(&self.blocks, width, &self.theme));
+        }
+        f(cache.as_ref().expect("layout was just filled"))
+    }
+
+    fn max_offset(&self) -> usize {
+        let viewport = self.viewport.get();
+        self.with_layout(viewport.width, |rendered| rendered.lines.len())
+            .saturating_sub(viewport.height as usize)
+    }
+
+    fn scroll_by(&mut self, delta: i32) {
+        let offset = (self.offset as i64 + delta as i64).max(0) as usize;
+        self.offset = offset.min(self.max_offset());
+    }
+
+    /// Move link focus by one in either direction, wrapping around
+    fn cycle_link(&mut self, forward: bool) {
+        let viewport = self.viewport.get();
+        let count = self.with_layout(viewport.width, |rendered| rendered.urls.len());
+        if count == 0 {
+            return;
+        }
+        let next = match (self.focused_link, forward) {
+            (None, true) => 0,
+            (None, false) => count - 1,
+            (Some(index), true) => (index + 1) % count,
+            (Some(index), false) => (index + count - 1) % count,
+        };
+        self.focused_link = Some(next);
+
+        let line = self.with_layout(viewport.width, |rendered| {
+            rendered.spots.iter().find(|spot| spot.link == next).map(|spot| spot.line)
+        });
+        if let Some(line) = line {
+            let height = (viewport.height as usize).max(1);
+            if line < self.offset {
+                self.offset = line;
+            } else if line >= self.offset + height {
+                self.offset = line + 1 - height;
+            }
+        }
+    }
+
+    fn follow(&mut self, index: usize) -> Option<Action> {
+        let width = self.viewport.get().width;
+        let url = self.with_layout(width, |rendered| rendered.urls.get(index).cloned())?;
+        self.focused_link = Some(index);
+        match url.strip_prefix('#') {
+            Some(anchor) => {
+                let line = self.with_layout(width, |rendered| {
+                    rendered
+                        .anchors
+                        .iter()
+                        .find(|(slug, _)| slug == anchor)
+                        .map(|(_, line)| *line)
+                });
+                if let Some(line) = line {
+                    self.offset = line.min(self.max_offset());
+                }
+                None
+            }
+            None => Some(Action::Custom(url)),
+        }
+    }
+}
+
+impl Component for Markdown {
+    type Message = MarkdownMessage;
+
+    fn update(&mut self, msg: Self::Message) -> Option<Action> {
+        let page = self.viewport.get().height.saturating_sub(1).max(1) as i32;
+        match msg {
+            MarkdownMessage::ScrollBy(delta) => self.scroll_by(delta),
+            MarkdownMessage::PageUp => self.scroll_by(-page),
+            MarkdownMessage::PageDown => self.scroll_by(page),
+            MarkdownMessage::Top => self.offset = 0,
+            MarkdownMessage::Bottom => self.offset = self.max_offset(),
+            MarkdownMessage::NextLink => self.cycle_link(true),
+            MarkdownMessage::PrevLink => self.cycle_link(false),
+            MarkdownMessage::Follow(index) => return self.follow(index),
+        }
+        None
+    }
+
+    fn render(&self, frame: &mut Frame<'_>) {
+        let area = frame.area();
+        self.render_in(frame, area);
+    }
+
+    fn render_in(&self, frame: &mut Frame<'_>, area: Rect) {
+        let base = Style::default().fg(self.theme.text).bg(self.theme.background);
+        frame.buffer_mut().set_style(area, base);
+
+        // The last column is kept for the scrollbar so wrapping does not
+        // change when the document starts to overflow
+        let viewport = Rect {
+            width: area.width.saturating_sub(1),
+            ..area
+        };
+        self.viewport.set(viewport);
+        if viewport.is_empty() {
+            return;
+        }
+
+        self.with_layout(viewport.width, |rendered| {
+            let max_offset = rendered.lines.len().saturating_sub(viewport.height as usize);
+            let offset = self.offset.min(max_offset);
+            let buffer = frame.buffer_mut();
+            let visible = rendered.lines.iter().skip(offset).take(viewport.height as usize);
+            for (y, line) in (viewport.y..).zip(visible) {
+                buffer.set_line(viewport.x, y, line, viewport.width);
+            }
+
+            let focused = rendered.spots.iter().filter(|spot| Some(spot.link) == self.focused_link);
+            for spot in focused {
+                if spot.line < offset || spot.line >= offset + viewport.height as usize {
+                    continue;
+                }
+                let y = viewport.y + (spot.line - offset) as u16;
+                let link_area = Rect::new(viewport.x + spot.column, y, spot.width, 1);
+                buffer.set_style(
+                    link_area.intersection(viewport),
+                    Style::default().add_modifier(Modifier::REVERSED),
+                );
+            }
+
+            if max_offset > 0 {
+                let mut state = ScrollbarState::new(max_offset)
+                    .position(offset)
+                    .viewport_content_length(viewport.height as usize);
+                frame.render_stateful_widget(
+                    Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                        .begin_symbol(None)
+                        .end_symbol(None)
+                        .style(Style::default().fg(self.theme.secondary)),
+                    area,
+                    &mut state,
+                );
+            }
+        });
+    }
+
+    /// Draw only the lines inside `window`, one row at a time
+    ///
+    /// Lets a `ScrollView` show documents longer than a buffer can hold. The
+    /// scrollbar is left out: the whole document fits in `area` here.
+    fn render_window(&self, frame: &mut Frame<'_>, area: Rect, window: Rect) {
+        let base = Style::default().fg(self.theme.text).bg(self.theme.background);
+        let window = window.intersection(area).intersection(frame.buffer_mut().area);
+        frame.buffer_mut().set_style(window, base);
+
+        let viewport = Rect {
+            width: area.width.saturating_sub(1),
+            ..area
+        };
+        self.viewport.set(viewport);
+        if viewport.is_empty() || window.is_empty() {
+            return;
+        }
+
+        self.with_layout(viewport.width, |rendered| {
+            let max_offset = rendered.lines.len().saturating_sub(viewport.height as usize);
+            let offset = self.offset.min(max_offset);
+            let first = offset + (window.y - viewport.y) as usize;
+            let buffer = frame.buffer_mut();
+            let columns = window.left()..window.right().min(viewport.right());
+
+            // Lines are drawn at full width into a one-row buffer, then the
+            // window's columns are copied out
+            let row_area = Rect { height: 1, y: 0, ..viewport };
+            let mut row = Buffer::empty(row_area);
+            for (y, index) in (window.top()..window.bottom()).zip(first..) {
+                row.reset();
+                row.set_style(row_area, base);
+                if let Some(line) = rendered.lines.get(index) {
+                    row.set_line(viewport.x, 0, line, viewport.width);
+                }
+                let focused = rendered
+                    .spots
+                    .iter()
+                    .filter(|spot| spot.line == index && Some(spot.link) == self.focused_link);
+                for spot in focused {
+                    let link_area = Rect::new(viewport.x + spot.column, 0, spot.width, 1);
+                    row.set_style(
+                        link_area.intersection(row_area),
+                        Style::default().add_modifier(Modifier::REVERSED),
+                    );
+                }
+                for x in columns.clone() {
+                    buffer[(x, y)] = row[(x, 0)].clone();
+                }
+            }
+        });
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.theme = theme.clone();
+        self.layout.replace(None);
+    }
+
+    fn handle_event(&mut self, event: &Event) -> Option<Self::Message> {
+        match event {
+            Event::Key(KeyEvent { code, .. }) => match code {
+                KeyCode::Up | KeyCode::Char('k') => Some(MarkdownMessage::ScrollBy(-1)),
+                KeyCode::Down | KeyCode::Char('j') => Some(MarkdownMessage::ScrollBy(1)),
+                KeyCode::PageUp => Some(MarkdownMessage::PageUp),
+                KeyCode::PageDown | KeyCode::Char(' ') => Some(MarkdownMessage::PageDown),
+                KeyCode::Home => Some(MarkdownMessage::Top),
+                KeyCode::End => Some(MarkdownMessage::Bottom),
+                KeyCode::Tab => Some(MarkdownMessage::NextLink),
+                KeyCode::BackTab => Some(MarkdownMessage::PrevLink),
+                KeyCode::Enter => self.focused_link.map(MarkdownMessage::Follow),
+                _ => None,
+            },
+            Event::Mouse(mouse_event) => match mouse_event.kind {
+                MouseEventKind::ScrollUp => Some(MarkdownMessage::ScrollBy(-WHEEL_STEP)),
+                MouseEventKind::ScrollDown => Some(MarkdownMessage::ScrollBy(WHEEL_STEP)),
+                MouseEventKind::Down(MouseButton::Left) => {
+                    let viewport = self.viewport.get();
+                    if !viewport.contains(Position::new(mouse_event.column, mouse_event.row)) {
+                        return None;
+                    }
+                    let line = self.offset.min(self.max_offset())
+                        + (mouse_event.row - viewport.y) as usize;
+                    let column = mouse_event.column - viewport.x;
+                    self.with_layout(viewport.width, |rendered| {
+                        rendered
+                            .spots
+                            .iter()
+                            .find(|spot| {
+                                spot.line == line
+                                    && (spot.column..spot.column + spot.width).contains(&column)
+                            })
+                            .map(|spot| MarkdownMessage::Follow(spot.link))
+                    })
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// A run of text ready for wrapping
+#[derive(Debug, Clone)]
+struct Piece {
+    text: String,
+    style: Style,
+    link: Option<usize>,
+}
+
+/// Line prefix for quotes and list items
+struct Prefix {
+    first: Span<'static>,
+    rest: Span<'static>,
+    used: bool,
+}
+
+/// Lays blocks out into lines
+struct Writer<'t> {
+    theme: &'t Theme,
+    rendered: Rendered,
+    prefixes: Vec<Prefix>,
+    /// Style under inline formatting, changed inside quotes
+    base: Style,
+    list_depth: usize,
+}
+
+impl<'t> Writer<'t> {
+    fn layout(blocks: &[Block], width: u16, theme: &'t Theme) -> Rendered {
+        let mut writer = Writer {
+            theme,
+            rendered: Rendered {
+                width,
+                ..Rendered::default()
+            },
+            prefixes: Vec::new(),
+            base: Style::default().fg(theme.text),
+            list_depth: 0,
+        };
+        writer.blocks(blocks, true);
+        writer.rendered
+    }
+
+    /// Columns left after the prefixes
+    fn available(&self) -> usize {
+        let prefix: usize = self.prefixes.iter().map(|prefix| prefix.rest.width()).sum();
+        (self.rendered.width as usize).saturating_sub(prefix).max(1)
+    }
+
+    /// Append a line of pieces after the current prefixes
+    fn push_line(&mut self, pieces: Vec<Piece>) {
+        let mut spans = Vec::new();
+        for prefix in &mut self.prefixes {
+            spans.push(if prefix.used { prefix.rest.clone() } else { prefix.first.clone() });
+            prefix.used = true;
+        }
+        let mut column: usize = spans.iter().map(Span::width).sum();
+        let line = self.rendered.lines.len();
+        for piece in pieces {
+            let width = piece.text.width();
+            if let Some(link) = piece.link {
+                match self.rendered.spots.last_mut() {
+                    Some(spot) if spot.line == line && spot.link == link
+                        && (spot.column + spot.width) as usize == column =>
+                    {
+                        spot.width += width as u16
+                    }
+                    _ => self.rendered.spots.push(LinkSpot {
+                        line,
+                        column: column as u16,
+                        width: width as u16,
+                        link,
+                    }),
+                }
+            }
+            column += width;
+            spans.push(Span::styled(piece.text, piece.style));
+        }
+        self.rendered.lines.push(Line::from(spans));
+    }
+
+    fn blocks(&mut self, blocks: &[Block], spaced: bool) {
+        for (index, block) in blocks.iter().enumerate() {
+            if index > 0 && spaced {
+                self.push_line(Vec::new());
+            }
+            self.block(block);
+        }
+    }
+
+    fn block(&mut self, block: &Block) {
+        let theme = self.theme;
+        match block {
+            Block::Heading(level, text) => {
+                let style = match level {
+                    1 => Style::default()
+                        .fg(theme.primary)
+                        .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+                    2 => Style::default().fg(theme.primary).add_modifier(Modifier::BOLD),
+                    _ => Style::default().fg(theme.secondary).add_modifier(Modifier::BOLD),
+                };
+                self.rendered.anchors.push((slug(text), self.rendered.lines.len()));
+                let base = self.base;
+                self.base = base.patch(style);
+                self.paragraph(text);
+                self.base = base;
+            }
+            Block::Paragraph(text) => self.paragraph(text),
+            Block::Quote(blocks) => {
+                let bar = Span::styled("│ ", Style::default().fg(theme.muted));
+                let base = self.base;
+                self.base = base.fg(theme.muted).add_modifier(Modifier::ITALIC);
+                self.prefixes.push(Prefix {
+                    first: bar.clone(),
+                    rest: bar,
+                    used: false,
+                });
+                self.blocks(blocks, true);
+                self.prefixes.pop();
+                self.base = base;
+            }
+            Block::List(items) => {
+                let markers: Vec<String> = items
+                    .iter()
+                    .map(|(number, _)| match number {
+                        Some(number) => format!("{}.", number),
+                        None => ["•", "◦", "▪"][self.list_depth % 3].to_string(),
+                    })
+                    .collect();
+                let marker_width = markers.iter().map(|marker| marker.width()).max().unwrap_or(0);
+                self.list_depth += 1;
+                for ((_, blocks), marker) in items.iter().zip(markers) {
+                    let padding = " ".repeat(marker_width - marker.width() + 1);
+                    self.prefixes.push(Prefix {
+                        first: Span::styled(format!("{}{}", marker, padding), Style::default().fg(theme.primary)),
+                        rest: Span::raw(" ".repeat(marker_width + 1)),
+                        used: false,
+                    });
+                    if blocks.is_empty() {
+                        self.push_line(Vec::new());
+                    }
+                    self.blocks(blocks, false);
+                    self.prefixes.pop();
+                }
+                self.list_depth -= 1;
+            }
+            Block::Code { lang, lines } => {
+                let width = self.available();
+                let style = Style::default().fg(theme.text).bg(theme.surface);
+                for line in lines {
+                    let line = line.replace('\t', "    ");
+                    let mut pieces: Vec<Piece> = highlight::highlight(&line, lang, theme, style)
+                        .into_iter()
+                        .map(|span| Piece {
+                            text: span.content.into_owned(),
+                            style: span.style,
+                            link: None,
+                        })
+                        .collect();
+                    let padding = width.saturating_sub(line.width());
+                    if padding > 0 {
+                        pieces.push(Piece {
+                            text: " ".repeat(padding),
+                            style,
+                            link: None,
+                        });
+                    }
+                    self.push_line(pieces);
+                }
+            }
+            Block::Table { header, align, rows } => self.table(header, align, rows),
+            Block::Rule => {
+                let rule = "─".repeat(self.available());
+                self.push_line(vec![Piece {
+                    text: rule,
+                    style: Style::default().fg(theme.muted),
+                    link: None,
+                }]);
+            }
+        }
+    }
+
+    /// Styled pieces for inline markdown
+    fn pieces(&mut self, text: &str) -> Vec<Piece> {
+        let theme = self.theme;
+        let base = self.base;
+        let runs = blocks::inline(text, &mut self.rendered.urls);
+        runs.into_iter()
+            .map(|Inline { text, emphasis, link }| {
+                let Emphasis { bold, italic, strike, code } = emphasis;
+                let mut style = base;
+                if bold {
+                    style = style.add_modifier(Modifier::BOLD);
+                }
+                if italic {
+                    style = style.add_modifier(Modifier::ITALIC);
+                }
+                if strike {
+                    style = style.add_modifier(Modifier::CROSSED_OUT);
+                }
+                if code {
+                    style = style.fg(theme.accent).bg(theme.surface);
+                }
+                if link.is_some() {
+                    style = style.fg(theme.accent).add_modifier(Modifier::UNDERLINED);
+                }
+                Piece { text, style, link }
+            })
+            .collect()
+    }
+
+    /// Word-wrap inline text into lines
+    fn paragraph(&mut self, text: &str) {
+        let width = self.available();
+        let pieces = self.pieces(text);
+        for line in wrap(pieces, width) {
+            self.push_line(line);
+        }
+    }
+
+    fn table(&mut self, header: &[String], align: &[Alignment], rows: &[Vec<String>]) {
+        let theme = self.theme;
+        let columns = header.len();
+        let header_style = Style::default().add_modifier(Modifier::BOLD);
+        let mut cells: Vec<Vec<Vec<Piece>>> = Vec::new();
+        let base = self.base;
+        self.base = base.patch(header_style).fg(theme.primary);
+        cells.push(header.iter().map(|cell| self.pieces(cell)).collect());
+        self.base = base;
+        for row in rows {
+            let row = (0..columns).map(|column| self.pieces(row.get(column).map_or("", String::as_str)));
+            cells.push(row.collect());
+        }
+
+        let cell_width = |pieces: &Vec<Piece>| pieces.iter().map(|piece| piece.text.width()).sum::<usize>();
+        let mut widths: Vec<usize> = (0..columns)
+            .map(|column| cells.iter().map(|row| cell_width(&row[column])).max().unwrap_or(0))
+            .collect();
+        // Shrink the widest columns until the table fits
+        let separators = columns.saturating_sub(1) * 3;
+        let available = self.available().saturating_sub(separators).max(columns);
+        while widths.iter().sum::<usize>() > available {
+            let widest = (0..columns).max_by_key(|&column| widths[column]).unwrap_or(0);
+            widths[widest] -= 1;
+        }
+
+        let separator = Style::default().fg(theme.muted);
+        for (index, row) in cells.into_iter().enumerate() {
+            let mut line = Vec::new();
+            for (column, pieces) in row.into_iter().enumerate() {
+                if column > 0 {
+                    line.push(Piece {
+                        text: " │ ".into(),
+                        style: separator,
+                        link: None,
+                    });
+                }
+                let pieces = truncate(pieces, widths[column]);
+                let space = widths[column] - cell_width(&pieces);
+                let left = match align.get(column) {
+                    Some(Alignment::Right) => space,
+                    Some(Alignment::Center) => space / 2,
+                    _ => 0,
+                };
+                let pad = |width: usize| Piece {
+                    text: " ".repeat(width),
+                    style: Style::default(),
+                    link: None,
+                };
+                line.push(pad(left));
+                line.extend(pieces);
+                line.push(pad(space - left));
+            }
+            self.push_line(line);
+            if index == 0 {
+                let rule = widths.iter().map(|width| "─".repeat(*width)).collect::<Vec<_>>();
+                self.push_line(vec![Piece {
+                    text: rule.join("─┼─"),
+                    style: separator,
+                    link: None,
+                }]);
+            }
+        }
+    }
+}
+
+/// Cut pieces down to `width` columns
+fn truncate(pieces: Vec<Piece>, width: usize) -> Vec<Piece> {
+    let mut left = width;
+    let mut out = Vec::new();
+    for mut piece in pieces {
+        if left == 0 {
+            break;
+        }
+        if piece.text.width() > left {
+            piece.text = take_width(&piece.text, left).to_string();
+        }
+        left -= piece.text.width();
+        out.push(piece);
+    }
+    out
+}
+
+/// Longest prefix of `text` no wider than `width`
+fn take_width(text: &str, width: usize) -> &str {
+    let mut used = 0;
+    for (index, grapheme) in text.grapheme_indices(true) {
+        used += grapheme.width();
+        if used > width {
+            return &text[..index];
+        }
+    }
+    text
+}
+
+/// Greedy word wrap; words wider than a line are split
+fn wrap(pieces: Vec<Piece>, width: usize) -> Vec<Vec<Piece>> {
+    // Split pieces into words and the spaces between them, keeping styles
+    let mut words: Vec<(bool, Vec<Piece>)> = Vec::new();
+    for piece in pieces {
+        let mut start = 0;
+        let text = &piece.text;
+        while start < text.len() {
+            let space = text[start..].starts_with(char::is_whitespace);
+            let end = text[start..]
+                .find(|c: char| c.is_whitespace() != space)
+                .map_or(text.len(), |end| start + end);
+            let part = Piece {
+                text: if space { " ".into() } else { text[start..end].to_string() },
+                ..piece.clone()
+            };
+            match words.last_mut() {
+                Some((last_space, parts)) if *last_space == space => {
+                    if !space {
+                        parts.push(part)
+                    }
+                }
+                _ => words.push((space, vec![part])),
+            }
+            start = end;
+        }
+    }
+
+    let mut lines = Vec::new();
+    let mut line: Vec<Piece> = Vec::new();
+    let mut used = 0;
+    let mut pending: Option<Piece> = None;
+    for (space, parts) in words {
+        if space {
+            if !line.is_empty() {
+                pending = parts.into_iter().next();
+            }
+            continue;
+        }
+        let word_width: usize = parts.iter().map(|part| part.text.width()).sum();
+        if !line.is_empty() && used + 1 + word_width > width {
+            lines.push(std::mem::take(&mut line));
+            used = 0;
+            pending = None;
+        }
+        if let Some(space) = pending.take() {
+            line.push(space);
+            used += 1;
+        }
+        for mut part in parts {
+            // Break words that cannot fit on a line of their own
+            while used + part.text.width() > width {
+                let head = take_width(&part.text, width - used).to_string();
+                if head.is_empty() && used == 0 {
+                    // A single grapheme wider than the line
+                    break;
+                }
+                part.text = part.text[head.len()..].to_string();
+                if !head.is_empty() {
+                    line.push(Piece { text: head, ..part.clone() });
+                }
+                lines.push(std::mem::take(&mut line));
+                used = 0;
+            }
+            if !part.text.is_empty() {
+                used += part.text.width();
+                line.push(part);
+            }
+        }
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Anchor for a heading, as GitHub builds them
+fn slug(text: &str) -> String {
+    let mut urls = Vec::new();
+    let plain: String = blocks::inline(text, &mut urls).into_iter().map(|run| run.text).collect();
+    plain
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyModifiers, MouseEvent};
+    use ratatui::backend::TestBackend;
+
+    const DOC: &str = "# Help\n\nPress **q** to quit. See [keys](#keys) or [the site](https://ruensh.dev).\n\n- first\n- second item wraps\n\n```rust\nlet x = 1;\n```\n\n## Keys\n\n> tip";
+
+    fn draw(markdown: &Markdown, width: u16, height: u16) -> Buffer {
+        let mut terminal = ratatui::Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|frame| markdown.render(frame)).unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    fn row(buf: &Buffer, y: u16) -> String {
+        (0..buf.area.width).map(|x| buf[(x, y)].symbol()).collect()
+    }
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn test_renders_blocks_with_theme() {
+        let theme = Theme::dark();
+        let markdown = Markdown::new(DOC).theme(theme.clone());
+        let buf = draw(&markdown, 21, 30);
+        assert_eq!(row(&buf, 0).trim_end(), "Help");
+        assert!(buf[(0, 0)].modifier.contains(Modifier::UNDERLINED));
+        assert_eq!(buf[(0, 0)].fg, theme.primary);
+        assert_eq!(row(&buf, 2).trim_end(), "Press q to quit. See");
+        assert!(buf[(6, 2)].modifier.contains(Modifier::BOLD));
+        assert_eq!(row(&buf, 5).trim_end(), "• first");
+        assert_eq!(row(&buf, 6).trim_end(), "• second item wraps");
+        assert_eq!(buf[(0, 8)].fg, theme.primary);
+        assert_eq!(buf[(19, 8)].bg, theme.surface);
+        assert_eq!(row(&buf, 10).trim_end(), "Keys");
+        assert_eq!(row(&buf, 12).trim_end(), "│ tip");
+        assert_eq!(buf[(2, 12)].fg, theme.muted);
+    }
+
+    #[test]
+    fn test_wrap_splits_long_words_and_keeps_styles() {
+        let piece = |text: &str| Piece {
+            text: text.into(),
+            style: Style::default(),
+            link: None,
+        };
+        let lines = wrap(vec![piece("aa bbbbbbb"), piece("c d")], 4);
+        let texts: Vec<String> = lines
+            .iter()
+            .map(|line| line.iter().map(|piece| piece.text.as_str()).collect())
+            .collect();
+        assert_eq!(texts, ["aa", "bbbb", "bbbc", "d"]);
+    }
+
+    #[test]
+    fn test_links_focus_follow_and_anchors() {
+        let mut markdown = Markdown::new(DOC);
+        draw(&markdown, 21, 6);
+
+        assert_eq!(markdown.handle_event(&key(KeyCode::Enter)), None);
+        let msg = markdown.handle_event(&key(KeyCode::Tab)).unwrap();
+        markdown.update(msg);
+        assert_eq!(markdown.focused_link().as_deref(), Some("#keys"));
+
+        let msg = markdown.handle_event(&key(KeyCode::Enter)).unwrap();
+        assert_eq!(markdown.update(msg), None);
+        assert_eq!(markdown.offset(), 10.min(markdown.max_offset()));
+        assert!(markdown.offset() > 0);
+
+        markdown.update(MarkdownMessage::Top);
+        let buf = draw(&markdown, 21, 6);
+        let site = (0..6)
+            .flat_map(|y| (0..20).map(move |x| (x, y)))
+            .find(|&(x, y)| buf[(x, y)].symbol() == "t" && buf[(x + 1, y)].symbol() == "h")
+            .expect("link text is visible");
+        let click = Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: site.0,
+            row: site.1,
+            modifiers: KeyModifiers::NONE,
+        });
+        let msg = markdown.handle_event(&click).unwrap();
+        assert_eq!(markdown.update(msg), Some(Action::Custom("https://ruensh.dev".into())));
+    }
+
+    #[test]
+    fn test_scrolling_is_clamped() {
+        let mut markdown = Markdown::new(DOC);
+        draw(&markdown, 21, 5);
+        markdown.update(MarkdownMessage::ScrollBy(-3));
+        assert_eq!(markdown.offset(), 0);
+        markdown.update(MarkdownMessage::ScrollBy(100));
+        assert_eq!(markdown.offset(), markdown.max_offset());
+        let buf = draw(&markdown, 21, 5);
+        assert_eq!(row(&buf, 4), "│ tip               █");
+        markdown.update(MarkdownMessage::Top);
+        assert_eq!(markdown.offset(), 0);
+    }
+}
@@ -2,12 +2,22 @@
 
 pub mod modal;
 pub mod list;
+pub mod hit_test;
+pub mod overlay;
+pub mod toast;
+pub mod form;
+pub mod tabs;
+pub mod split_pane;
+pub mod scroll_view;
+pub mod element;
+pub mod compose;
+pub mod markdown;
+mod offscreen;
 
+use crate::style::Theme;
+use ratatui::layout::Rect;
 use ratatui::Frame;
 
-/// Type alias for rendered elements
-pub type Element = ratatui::widgets::Block<'static>;
-
 /// Base component trait
 pub trait Component {
     type Message;
@@ -18,9 +28,58 @@ pub trait Component {
     /// Render the component
     fn render(&self, frame: &mut Frame<'_>);
 
+    /// Render the component into a sub-area of the frame
+    ///
+    /// Containers call this to place children. The default ignores `area`
+    /// and falls back to `render`.
+    fn render_in(&self, frame: &mut Frame<'_>, area: Rect) {
+        let _ = area;
+        self.render(frame);
+    }
+
+    /// Render the part of the component inside `window`
+    ///
+    /// `area` is the component's full area and `window` the part of it the
+    /// frame's buffer covers. `ScrollView` calls this for content over the
+    /// 65,535 cells a ratatui buffer can hold. The default renders the
+    /// component into `window` as if it were its whole area; components
+    /// whose content should scroll past that limit override it.
+    fn render_window(&self, frame: &mut Frame<'_>, area: Rect, window: Rect) {
+        let _ = area;
+        self.render_in(frame, window);
+    }
+
+    /// Describe the component as an element tree
+    ///
+    /// The default is a slot drawn with `render_in`. Components built from
+    /// elements override this and render with `self.view().render(frame, area)`.
+    fn view(&self) -> Element<'_>
+    where
+        Self: Sized,
+    {
+        Element::component(self)
+    }
+
+    /// Apply a new theme at runtime
+    ///
+    /// Containers pass the theme on to their children. The default ignores it.
+    fn set_theme(&mut self, theme: &Theme) {
+        let _ = theme;
+    }
+
     /// Handle keyboard/mouse events
     fn handle_event(&mut self, event: &crate::events::Event) -> Option<Self::Message>;
 }
 
 pub use modal::Modal;
 pub use list::List;
+pub use hit_test::HitRegions;
+pub use overlay::{Layer, LayerResponse, Overlay};
+pub use toast::{Severity, Toast, ToastPosition, Toasts};
+pub use form::{Field, FieldValue, Form};
+pub use tabs::{Tab, Tabs};
+pub use split_pane::{Pane, SplitPane, SplitState};
+pub use scroll_view::ScrollView;
+pub use element::Element;
+pub use compose::{AnyComponent, Children, ComponentExt};
+pub use markdown::{Markdown, MarkdownMessage};
@@ -1,11 +1,18 @@
 //! Modal/Dialog component
 
+use super::element::{column, row, Element};
+use super::hit_test::HitRegions;
+use super::Component;
+use super::overlay::{Layer, LayerResponse};
 use crate::events::Event;
 use crate::state::Action;
-use crate::style::Theme;
+use crate::style::{markup, Slot, StyleSheet, Theme, Variant};
+use crate::svg::{Easing, Transition, TransitionPresets};
 use crossterm::event::{KeyCode, KeyEvent, MouseEventKind};
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use ratatui::widgets::{Block, Padding, Paragraph, Wrap};
+use std::time::{Duration, Instant};
+use unicode_width::UnicodeWidthStr;
 
 /// Message types for modal
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -16,6 +23,8 @@ pub enum ModalMessage {
     HoverPrimary,
     HoverSecondary,
     NoHover,
+    /// Appear or disappear animation finished in the given state
+    AnimationComplete(ModalState),
 }
 
 /// Button state tracking
@@ -35,6 +44,39 @@ pub enum ModalState {
     Disappearing,
 }
 
+/// Edge a sliding modal enters from and leaves towards
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlideEdge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+/// Appear/disappear animation presets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModalAnimation {
+    /// Show and hide instantly
+    None,
+    /// Fade the backdrop and content in and out
+    Fade,
+    /// Grow from half size to full size
+    Zoom,
+    /// Slide in from an edge of the area
+    Slide(SlideEdge),
+    /// Grow from nothing with a springy overshoot
+    Bounce,
+}
+
+/// Geometry and opacity of the modal at one point of its animation
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ModalTransform {
+    scale: f32,
+    /// Fraction of the slide distance still to travel (0.0 = in place)
+    offset: f32,
+    visibility: f32,
+}
+
 /// Modal dialog component with advanced event handling
 pub struct Modal {
     title: String,
@@ -42,11 +84,19 @@ pub struct Modal {
     primary_label: String,
     secondary_label: String,
     theme: Theme,
+    styles: StyleSheet,
+    /// Parse the content as rich text markup
+    markup: bool,
     focused_button: ButtonFocus,
+    /// Button that cannot be focused or pressed, if any
+    disabled_button: ButtonFocus,
     modal_state: ModalState,
-    animation_frame: u8,
-    last_mouse_x: u16,
-    last_mouse_y: u16,
+    animation: ModalAnimation,
+    animation_duration: Duration,
+    transition: Transition<f32>,
+    backdrop: bool,
+    /// Button areas recorded during the last render
+    hit_regions: HitRegions<ButtonFocus>,
 }
 
 impl Modal {
@@ -58,11 +108,16 @@ impl Modal {
             primary_label: String::from("Confirm"),
             secondary_label: String::from("Cancel"),
             theme: Theme::default(),
+            styles: StyleSheet::new(),
+            markup: false,
             focused_button: ButtonFocus::Primary,
+            disabled_button: ButtonFocus::None,
             modal_state: ModalState::Hidden,
-            animation_frame: 0,
-            last_mouse_x: 0,
-            last_mouse_y: 0,
+            animation: ModalAnimation::Fade,
+            animation_duration: Duration::from_millis(160),
+            transition: TransitionPresets::fade_in(Duration::ZERO).with_loop(Some(1)),
+            backdrop: true,
+            hit_regions: HitRegions::new(),
         }
     }
 
@@ -90,16 +145,105 @@ impl Modal {
         self
     }
 
+    /// Override slot styles (`border`, `title`, `text`, `button`, `button.secondary`)
+    pub fn styles(mut self, styles: StyleSheet) -> Self {
+        self.styles = styles;
+        self
+    }
+
+    /// Parse the content as rich text markup, e.g. `Delete [bold error]3 files[/]?`
+    pub fn markup(mut self, markup: bool) -> Self {
+        self.markup = markup;
+        self
+    }
+
+    /// Disable one of the buttons; it is drawn muted and cannot be pressed
+    pub fn disable_button(mut self, button: ButtonFocus) -> Self {
+        self.set_disabled_button(button);
+        self
+    }
+
+    /// Change which button is disabled (`ButtonFocus::None` enables both)
+    pub fn set_disabled_button(&mut self, button: ButtonFocus) {
+        self.disabled_button = button;
+        if self.focused_button == button {
+            self.focused_button = self.default_focus();
+        }
+    }
+
+    /// Check whether a button can be focused and pressed
+    fn is_enabled(&self, button: ButtonFocus) -> bool {
+        button != ButtonFocus::None && button != self.disabled_button
+    }
+
+    /// Button focused when nothing is hovered
+    fn default_focus(&self) -> ButtonFocus {
+        if self.is_enabled(ButtonFocus::Primary) {
+            ButtonFocus::Primary
+        } else {
+            ButtonFocus::Secondary
+        }
+    }
+
+    /// Move focus to the other button unless it is disabled
+    fn toggle_focus(&mut self) {
+        let other = match self.focused_button {
+            ButtonFocus::Primary => ButtonFocus::Secondary,
+            ButtonFocus::Secondary | ButtonFocus::None => ButtonFocus::Primary,
+        };
+        if self.is_enabled(other) {
+            self.focused_button = other;
+        }
+    }
+
+    /// Style for a slot in the given variant
+    fn style(&self, slot: Slot, variant: Variant) -> Style {
+        self.styles.resolve(&self.theme, slot, variant)
+    }
+
+    /// Enable or disable the full-area backdrop drawn behind the modal
+    pub fn backdrop(mut self, backdrop: bool) -> Self {
+        self.backdrop = backdrop;
+        self
+    }
+
+    /// Set the appear/disappear animation preset
+    pub fn animation(mut self, animation: ModalAnimation) -> Self {
+        self.animation = animation;
+        self
+    }
+
+    /// Set how long the appear/disappear animation takes
+    pub fn animation_duration(mut self, duration: Duration) -> Self {
+        self.animation_duration = duration;
+        self
+    }
+
     /// Show the modal with popup animation
     pub fn show(&mut self) {
+        self.show_at(Instant::now());
+    }
+
+    /// Show the modal, starting the animation at `now`
+    pub fn show_at(&mut self, now: Instant) {
         self.modal_state = ModalState::Appearing;
-        self.animation_frame = 0;
+        self.transition = self.appear_transition();
+        self.transition.start_at(now);
     }
 
     /// Hide the modal with disappear animation
     pub fn hide(&mut self) {
+        self.hide_at(Instant::now());
+    }
+
+    /// Hide the modal, starting the animation at `now`
+    pub fn hide_at(&mut self, now: Instant) {
+        if self.modal_state == ModalState::Hidden {
+            return;
+        }
         self.modal_state = ModalState::Disappearing;
-        self.animation_frame = 0;
+        self.transition = self.disappear_transition();
+        self.transition.start_at(now);
     }
 
     /// Get current modal state
@@ -107,61 +251,122 @@ impl Modal {
         self.modal_state == ModalState::Visible || self.modal_state == ModalState::Appearing
     }
 
+    /// Get the current animation state
+    pub fn state(&self) -> ModalState {
+        self.modal_state
+    }
+
     /// Get focused button
     pub fn focused_button(&self) -> ButtonFocus {
         self.focused_button
     }
 
-    /// Update animation state
-    pub fn update_animation(&mut self) {
-        match self.modal_state {
-            ModalState::Appearing => {
-                self.animation_frame = self.animation_frame.saturating_add(1);
-                if self.animation_frame >= 10 {
-                    self.modal_state = ModalState::Visible;
-                }
-            }
-            ModalState::Disappearing => {
-                self.animation_frame = self.animation_frame.saturating_add(1);
-                if self.animation_frame >= 10 {
-                    self.modal_state = ModalState::Hidden;
-                }
-            }
-            _ => {}
+    /// Update animation state from the wall clock
+    ///
+    /// Returns `ModalMessage::AnimationComplete` once the running animation
+    /// has finished.
+    pub fn update_animation(&mut self) -> Option<ModalMessage> {
+        self.update_animation_at(Instant::now())
+    }
+
+    /// Update animation state as of `now`
+    pub fn update_animation_at(&mut self, now: Instant) -> Option<ModalMessage> {
+        let finished = match self.modal_state {
+            ModalState::Appearing => ModalState::Visible,
+            ModalState::Disappearing => ModalState::Hidden,
+            ModalState::Visible | ModalState::Hidden => return None,
+        };
+
+        self.transition.update_at(now);
+        if self.transition.is_running() {
+            return None;
         }
+        self.modal_state = finished;
+        Some(ModalMessage::AnimationComplete(finished))
     }
 
-    /// Check if mouse is over primary button
-    #[allow(dead_code)]
-    fn is_mouse_over_primary(&self, button_area: Rect) -> bool {
-        self.last_mouse_x >= button_area.x
-            && self.last_mouse_x < button_area.x + button_area.width
-            && self.last_mouse_y == button_area.y
+    /// Build the transition played by `show`
+    fn appear_transition(&self) -> Transition<f32> {
+        let duration = self.animation_duration;
+        let transition = match self.animation {
+            ModalAnimation::None => Transition::from_to(Duration::ZERO, 1.0, 1.0, Easing::Linear),
+            ModalAnimation::Fade => TransitionPresets::fade_in(duration),
+            ModalAnimation::Zoom => TransitionPresets::zoom_in(duration),
+            ModalAnimation::Slide(_) => Transition::from_to(duration, 0.0, 1.0, Easing::EaseOut),
+            ModalAnimation::Bounce => TransitionPresets::bounce_scale(duration),
+        };
+        transition.with_loop(Some(1))
     }
 
-    /// Check if mouse is over secondary button
-    #[allow(dead_code)]
-    fn is_mouse_over_secondary(&self, button_area: Rect) -> bool {
-        let secondary_start = button_area.x + (self.primary_label.len() as u16) + 4;
-        self.last_mouse_x >= secondary_start
-            && self.last_mouse_x < secondary_start + (self.secondary_label.len() as u16) + 2
-            && self.last_mouse_y == button_area.y
+    /// Build the transition played by `hide`
+    fn disappear_transition(&self) -> Transition<f32> {
+        let duration = self.animation_duration;
+        let transition = match self.animation {
+            ModalAnimation::None => Transition::from_to(Duration::ZERO, 0.0, 0.0, Easing::Linear),
+            ModalAnimation::Fade => TransitionPresets::fade_out(duration),
+            ModalAnimation::Zoom => Transition::from_to(duration, 1.0, 0.5, Easing::EaseIn),
+            ModalAnimation::Slide(_) | ModalAnimation::Bounce => {
+                Transition::from_to(duration, 1.0, 0.0, Easing::EaseIn)
+            }
+        };
+        transition.with_loop(Some(1))
+    }
+
+    /// Map the current transition value onto scale, slide and opacity
+    fn transform(&self) -> ModalTransform {
+        let value = match self.modal_state {
+            ModalState::Visible => return ModalTransform { scale: 1.0, offset: 0.0, visibility: 1.0 },
+            ModalState::Hidden => 0.0,
+            ModalState::Appearing | ModalState::Disappearing => {
+                self.transition.current_value.unwrap_or(0.0)
+            }
+        };
+
+        match self.animation {
+            ModalAnimation::None | ModalAnimation::Fade => ModalTransform {
+                scale: 1.0,
+                offset: 0.0,
+                visibility: value,
+            },
+            ModalAnimation::Zoom => ModalTransform {
+                scale: value,
+                offset: 0.0,
+                visibility: ((value - 0.5) * 2.0).clamp(0.0, 1.0),
+            },
+            ModalAnimation::Slide(_) => ModalTransform {
+                scale: 1.0,
+                offset: 1.0 - value,
+                visibility: value,
+            },
+            ModalAnimation::Bounce => ModalTransform {
+                scale: value,
+                offset: 0.0,
+                visibility: value.min(1.0),
+            },
+        }
+    }
+
+    /// Get the button under the given cell, based on the last render
+    pub fn button_at(&self, column: u16, row: u16) -> ButtonFocus {
+        self.hit_regions.hit(column, row).unwrap_or(ButtonFocus::None)
     }
 
     /// Render the modal in the center of the given area
     pub fn render_centered(&self, frame: &mut Frame, area: Rect) {
-        // Calculate animation opacity/scale
-        let visibility = match self.modal_state {
-            ModalState::Hidden => return,
-            ModalState::Appearing => (self.animation_frame as f32 / 10.0).min(1.0),
-            ModalState::Visible => 1.0,
-            ModalState::Disappearing => ((10 - self.animation_frame) as f32 / 10.0).max(0.0),
-        };
+        self.hit_regions.clear();
+
+        if self.modal_state == ModalState::Hidden {
+            return;
+        }
+        let transform = self.transform();
 
-        // Create centered area for modal
+        // Create centered area for modal, scaled by the animation
         let modal_width = 60.min(area.width.saturating_sub(4));
         let modal_height = 14.min(area.height.saturating_sub(2));
 
+        let modal_width = ((modal_width as f32 * transform.scale).round() as u16).min(area.width);
+        let modal_height = ((modal_height as f32 * transform.scale).round() as u16).min(area.height);
+
         let x = (area.width.saturating_sub(modal_width)) / 2;
         let y = (area.height.saturating_sub(modal_height)) / 2;
 
@@ -173,121 +378,111 @@ impl Modal {
         };
 
         // Draw semi-transparent backdrop
-        let backdrop_color = if visibility > 0.5 {
-            Color::Black
-        } else {
-            Color::Reset
-        };
-
-        frame.render_widget(
-            Block::default().style(Style::default().bg(backdrop_color).fg(Color::DarkGray)),
-            area,
-        );
-
-        // Draw modal box with border
-        let block = Block::default()
-            .title(self.title.as_str())
-            .borders(Borders::ALL)
-            .border_type(ratatui::widgets::BorderType::Rounded)
-            .border_style(
-                Style::default()
-                    .fg(self.theme.primary)
-                    .add_modifier(Modifier::BOLD),
-            )
-            .style(Style::default().bg(Color::Black).fg(Color::White));
-
-        let inner_area = block.inner(modal_area);
-        frame.render_widget(block, modal_area);
-
-        // Calculate content and button areas
-        let mut inner_y = inner_area.y;
-
-        // Render message with word wrap
-        let message = Paragraph::new(self.content.as_str())
-            .wrap(Wrap { trim: true })
-            .style(Style::default().fg(Color::White));
+        if self.backdrop {
+            let backdrop_color = if transform.visibility > 0.5 {
+                self.theme.background
+            } else {
+                Color::Reset
+            };
+
+            frame.render_widget(
+                Block::default().style(Style::default().bg(backdrop_color).fg(self.theme.muted)),
+                area,
+            );
+        }
 
-        let message_area = Rect {
-            y: inner_y,
-            height: (inner_area.height / 2).min(6),
-            ..inner_area
-        };
-        frame.render_widget(message, message_area);
+        if modal_area.width < 2 || modal_area.height < 2 {
+            return;
+        }
 
-        inner_y += message_area.height + 1;
+        // Draw off-screen so a sliding modal can be clipped at the area edge
+        let mut scratch = Buffer::empty(modal_area);
+        let buttons = self.render_box(&mut scratch, modal_area);
+        if transform.visibility < 0.5 {
+            scratch.set_style(modal_area, Style::default().add_modifier(Modifier::DIM));
+        }
 
-        // Render buttons with hover state
-        let button_area = Rect {
-            y: inner_y,
-            height: 1,
-            ..inner_area
-        };
+        let (dx, dy) = self.slide_offset(area, modal_area, transform.offset);
+        let target = frame.buffer_mut();
+        let visible = area.intersection(target.area);
+        for position in modal_area.positions() {
+            let to = Position::new(
+                (position.x as i32 + dx) as u16,
+                (position.y as i32 + dy) as u16,
+            );
+            let on_screen = position.x as i32 + dx >= 0 && position.y as i32 + dy >= 0;
+            if on_screen && visible.contains(to) {
+                target[to] = scratch[position].clone();
+            }
+        }
 
-        self.render_buttons(frame, button_area);
+        // Clip before converting back to u16, as for the cells above
+        for (button, button_area) in buttons {
+            let left = (button_area.left() as i32 + dx).max(visible.left() as i32);
+            let top = (button_area.top() as i32 + dy).max(visible.top() as i32);
+            let right = (button_area.right() as i32 + dx).min(visible.right() as i32);
+            let bottom = (button_area.bottom() as i32 + dy).min(visible.bottom() as i32);
+            let moved = if left < right && top < bottom {
+                Rect::new(left as u16, top as u16, (right - left) as u16, (bottom - top) as u16)
+            } else {
+                Rect::default()
+            };
+            self.hit_regions.register(button, moved);
+        }
     }
 
-    /// Render buttons with hover and focus states
-    fn render_buttons(&self, frame: &mut Frame, area: Rect) {
-        let primary_focused = self.focused_button == ButtonFocus::Primary;
-        let secondary_focused = self.focused_button == ButtonFocus::Secondary;
-
-        // Primary button style
-        let primary_style = if primary_focused {
-            Style::default()
-                .fg(Color::Black)
-                .bg(self.theme.primary)
-                .add_modifier(Modifier::BOLD)
-        } else {
-            Style::default()
-                .fg(self.theme.primary)
-                .add_modifier(Modifier::BOLD)
-        };
-
-        let primary_text = if primary_focused {
-            format!(" {} ", self.primary_label)
-        } else {
-            format!("[ {} ]", self.primary_label)
+    /// Cells to shift the modal by for a slide of `fraction` of the way out
+    fn slide_offset(&self, area: Rect, modal_area: Rect, fraction: f32) -> (i32, i32) {
+        let ModalAnimation::Slide(edge) = self.animation else {
+            return (0, 0);
         };
-
-        let primary_widget = Paragraph::new(primary_text).style(primary_style);
-
-        let primary_button_area = Rect {
-            x: area.x + 2,
-            y: area.y,
-            width: (self.primary_label.len() as u16) + 4,
-            height: 1,
+        let distance = match edge {
+            SlideEdge::Top => modal_area.bottom() as i32 - area.y as i32,
+            SlideEdge::Bottom => area.bottom() as i32 - modal_area.y as i32,
+            SlideEdge::Left => modal_area.right() as i32 - area.x as i32,
+            SlideEdge::Right => area.right() as i32 - modal_area.x as i32,
         };
+        let shift = (distance as f32 * fraction).round() as i32;
+        match edge {
+            SlideEdge::Top => (0, -shift),
+            SlideEdge::Bottom => (0, shift),
+            SlideEdge::Left => (-shift, 0),
+            SlideEdge::Right => (shift, 0),
+        }
+    }
 
-        frame.render_widget(primary_widget, primary_button_area);
+    /// Draw the modal box into `buf`, returning the button areas
+    fn render_box(&self, buf: &mut Buffer, modal_area: Rect) -> Vec<(ButtonFocus, Rect)> {
+        let view = self.view();
+        view.render_buffer(buf, modal_area);
+        view.layout(modal_area)
+            .into_iter()
+            .filter_map(|(key, area)| match key.as_str() {
+                "primary" => Some((ButtonFocus::Primary, area)),
+                "secondary" => Some((ButtonFocus::Secondary, area)),
+                _ => None,
+            })
+            .collect()
+    }
 
-        // Secondary button style
-        let secondary_style = if secondary_focused {
-            Style::default()
-                .fg(Color::Black)
-                .bg(self.theme.secondary)
-                .add_modifier(Modifier::BOLD)
+    /// A button element, bracketed unless it has focus
+    fn button(&self, button: ButtonFocus, label: &str, slot: Slot) -> Element<'_> {
+        let variant = if !self.is_enabled(button) {
+            Variant::Disabled
+        } else if self.focused_button == button {
+            Variant::Focus
         } else {
-            Style::default()
-                .fg(self.theme.secondary)
-                .add_modifier(Modifier::BOLD)
+            Variant::Normal
         };
-
-        let secondary_text = if secondary_focused {
-            format!(" {} ", self.secondary_label)
+        let text = if self.focused_button == button {
+            format!(" {} ", label)
         } else {
-            format!("[ {} ]", self.secondary_label)
+            format!("[ {} ]", label)
         };
-
-        let secondary_widget = Paragraph::new(secondary_text).style(secondary_style);
-
-        let secondary_button_area = Rect {
-            x: primary_button_area.x + primary_button_area.width + 3,
-            y: area.y,
-            width: (self.secondary_label.len() as u16) + 4,
-            height: 1,
-        };
-
-        frame.render_widget(secondary_widget, secondary_button_area);
+        Element::text(text)
+            .style(self.style(slot, variant))
+            .basis(label.width() as u16 + 4)
+            .shrink(0.0)
     }
 }
 
@@ -309,17 +504,22 @@ impl super::Component for Modal {
                 Some(Action::Cancel)
             }
             ModalMessage::HoverPrimary => {
-                self.focused_button = ButtonFocus::Primary;
+                if self.is_enabled(ButtonFocus::Primary) {
+                    self.focused_button = ButtonFocus::Primary;
+                }
                 None
             }
             ModalMessage::HoverSecondary => {
-                self.focused_button = ButtonFocus::Secondary;
+                if self.is_enabled(ButtonFocus::Secondary) {
+                    self.focused_button = ButtonFocus::Secondary;
+                }
                 None
             }
             ModalMessage::NoHover => {
-                self.focused_button = ButtonFocus::Primary;
+                self.focused_button = self.default_focus();
                 None
             }
+            ModalMessage::AnimationComplete(_) => None,
         }
     }
 
@@ -328,6 +528,50 @@ impl super::Component for Modal {
         self.render_centered(frame, area);
     }
 
+    fn render_in(&self, frame: &mut Frame<'_>, area: Rect) {
+        self.render_centered(frame, area);
+    }
+
+    /// The modal box: message, then the buttons keyed `primary` and `secondary`
+    ///
+    /// `render_centered` places, animates and clips it.
+    fn view(&self) -> Element<'_> {
+        // The modal holds input focus while open
+        let block = self.theme.border_style.apply(Block::default())
+            .title(self.title.as_str())
+            .title_style(self.style(Slot::Title, Variant::Focus))
+            .border_style(self.style(Slot::Border, Variant::Focus))
+            .style(self.style(Slot::Base, Variant::Normal));
+        let content = if self.markup {
+            markup(&self.content, &self.theme)
+        } else {
+            Text::raw(self.content.as_str())
+        };
+        let message = Paragraph::new(content)
+            .wrap(Wrap { trim: true })
+            .style(self.style(Slot::Text, Variant::Normal));
+
+        let buttons = row([
+            self.button(ButtonFocus::Primary, &self.primary_label, Slot::Button).key("primary"),
+            self.button(ButtonFocus::Secondary, &self.secondary_label, Slot::ButtonSecondary).key("secondary"),
+        ])
+        .gap(3)
+        .padding(Padding::left(2));
+
+        // Up to six rows of message, a blank row, the buttons, then free space
+        column([
+            Element::widget(message).basis(6).key("message"),
+            Element::empty().basis(1),
+            buttons.basis(1).shrink(0.0),
+            Element::empty().grow(1.0),
+        ])
+        .block(block)
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.theme = theme.clone();
+    }
+
     fn handle_event(&mut self, event: &Event) -> Option<Self::Message> {
         match event {
             // Keyboard event handling with robust navigation
@@ -342,87 +586,39 @@ impl super::Component for Modal {
                 }
                 // Dismiss on Escape
                 KeyCode::Esc => return Some(ModalMessage::Dismiss),
-                // Navigation between buttons
-                KeyCode::Tab | KeyCode::Right => {
-                    self.focused_button = match self.focused_button {
-                        ButtonFocus::Primary => ButtonFocus::Secondary,
-                        ButtonFocus::Secondary => ButtonFocus::Primary,
-                        ButtonFocus::None => ButtonFocus::Primary,
-                    };
-                    None?;
-                }
-                KeyCode::BackTab | KeyCode::Left => {
-                    self.focused_button = match self.focused_button {
-                        ButtonFocus::Primary => ButtonFocus::Secondary,
-                        ButtonFocus::Secondary => ButtonFocus::Primary,
-                        ButtonFocus::None => ButtonFocus::Secondary,
-                    };
+                // Navigation between buttons; with two buttons both directions toggle
+                KeyCode::Tab | KeyCode::Right | KeyCode::BackTab | KeyCode::Left => {
+                    self.toggle_focus();
                     None?;
                 }
                 // Quick keys
-                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                KeyCode::Char('y') | KeyCode::Char('Y') if self.is_enabled(ButtonFocus::Primary) => {
                     return Some(ModalMessage::PrimaryButton)
                 }
-                KeyCode::Char('n') | KeyCode::Char('N') => {
+                KeyCode::Char('n') | KeyCode::Char('N') if self.is_enabled(ButtonFocus::Secondary) => {
                     return Some(ModalMessage::SecondaryButton)
                 }
                 _ => {}
             },
-            // Mouse event handling
+            // Advance time-based animations
+            Event::Tick => return self.update_animation(),
+            // Mouse event handling against the areas recorded during render
             Event::Mouse(mouse_event) => {
-                self.last_mouse_x = mouse_event.column;
-                self.last_mouse_y = mouse_event.row;
-
-                // Calculate button positions (approximate based on modal rendering)
-                let area = Rect {
-                    x: 0,
-                    y: 0,
-                    width: 80,
-                    height: 24,
-                };
-
-                let modal_width = 60.min(area.width.saturating_sub(4));
-                let modal_height = 14.min(area.height.saturating_sub(2));
-                let modal_x = (area.width.saturating_sub(modal_width)) / 2;
-                let modal_y = (area.height.saturating_sub(modal_height)) / 2;
-
-                let button_y = modal_y + modal_height - 4;
-
-                // Primary button area
-                let primary_start_x = modal_x + 4;
-                let primary_end_x = primary_start_x + (self.primary_label.len() as u16) + 4;
-
-                // Secondary button area
-                let secondary_start_x = primary_end_x + 4;
-                let secondary_end_x = secondary_start_x + (self.secondary_label.len() as u16) + 4;
+                let hovered = self.button_at(mouse_event.column, mouse_event.row);
+                let hovered = if self.is_enabled(hovered) { hovered } else { ButtonFocus::None };
 
                 match mouse_event.kind {
-                    MouseEventKind::Down(_) | MouseEventKind::Up(_) => {
-                        // Check if click is on primary button
-                        if mouse_event.column >= primary_start_x
-                            && mouse_event.column < primary_end_x
-                            && mouse_event.row == button_y
-                        {
-                            return Some(ModalMessage::PrimaryButton);
-                        }
-                        // Check if click is on secondary button
-                        if mouse_event.column >= secondary_start_x
-                            && mouse_event.column < secondary_end_x
-                            && mouse_event.row == button_y
-                        {
-                            return Some(ModalMessage::SecondaryButton);
-                        }
-                    }
+                    MouseEventKind::Down(_) => match hovered {
+                        ButtonFocus::Primary => return Some(ModalMessage::PrimaryButton),
+                        ButtonFocus::Secondary => return Some(ModalMessage::SecondaryButton),
+                        ButtonFocus::None => {}
+                    },
                     MouseEventKind::Moved => {
-                        // Update hover state
-                        if mouse_event.column >= primary_start_x && mouse_event.column < primary_end_x && mouse_event.row == button_y {
-                            return Some(ModalMessage::HoverPrimary);
-                        } else if mouse_event.column >= secondary_start_x && mouse_event.column < secondary_end_x && mouse_event.row == button_y
-                        {
-                            return Some(ModalMessage::HoverSecondary);
-                        } else {
-                            return Some(ModalMessage::NoHover);
-                        }
+                        return Some(match hovered {
+                            ButtonFocus::Primary => ModalMessage::HoverPrimary,
+                            ButtonFocus::Secondary => ModalMessage::HoverSecondary,
+                            ButtonFocus::None => ModalMessage::NoHover,
+                        });
                     }
                     _ => {}
                 }
@@ -432,3 +628,219 @@ impl super::Component for Modal {
         None
     }
 }
+
+impl Layer for Modal {
+    fn render_layer(&self, frame: &mut Frame<'_>, area: Rect) {
+        self.render_centered(frame, area);
+    }
+
+    fn layer_event(&mut self, event: &Event) -> LayerResponse {
+        let Some(msg) = self.handle_event(event) else {
+            return LayerResponse::Ignored;
+        };
+        match self.update(msg) {
+            Some(action) => LayerResponse::Close(action),
+            None => LayerResponse::Consumed,
+        }
+    }
+
+    fn on_push(&mut self) {
+        // The overlay dims the layers beneath, so skip the opaque backdrop
+        self.backdrop = false;
+        self.show();
+    }
+
+    fn on_theme_change(&mut self, theme: &Theme) {
+        self.set_theme(theme);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::Component;
+    use crossterm::event::{KeyModifiers, MouseButton, MouseEvent};
+    use ratatui::backend::TestBackend;
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> Event {
+        Event::Mouse(MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        })
+    }
+
+    fn draw(modal: &Modal, width: u16, height: u16) {
+        let mut terminal = ratatui::Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|frame| modal.render(frame)).unwrap();
+    }
+
+    #[test]
+    fn test_click_hits_rendered_buttons_at_any_size() {
+        for (width, height) in [(80, 24), (200, 60), (50, 16)] {
+            let mut modal = Modal::new("Quit?").primary_button("Yes").secondary_button("No");
+            modal.show();
+            draw(&modal, width, height);
+
+            let primary = modal.hit_regions.area(ButtonFocus::Primary).unwrap();
+            let secondary = modal.hit_regions.area(ButtonFocus::Secondary).unwrap();
+
+            let click = mouse(MouseEventKind::Down(MouseButton::Left), primary.x, primary.y);
+            assert_eq!(modal.handle_event(&click), Some(ModalMessage::PrimaryButton));
+
+            let click = mouse(
+                MouseEventKind::Down(MouseButton::Left),
+                secondary.right() - 1,
+                secondary.y,
+            );
+            assert_eq!(modal.handle_event(&click), Some(ModalMessage::SecondaryButton));
+        }
+    }
+
+    #[test]
+    fn test_hover_updates_focus() {
+        let mut modal = Modal::new("Quit?");
+        modal.show();
+        draw(&modal, 120, 40);
+
+        let secondary = modal.hit_regions.area(ButtonFocus::Secondary).unwrap();
+        let msg = modal.handle_event(&mouse(MouseEventKind::Moved, secondary.x, secondary.y));
+        assert_eq!(msg, Some(ModalMessage::HoverSecondary));
+        modal.update(msg.unwrap());
+        assert_eq!(modal.focused_button(), ButtonFocus::Secondary);
+
+        let msg = modal.handle_event(&mouse(MouseEventKind::Moved, 0, 0));
+        assert_eq!(msg, Some(ModalMessage::NoHover));
+    }
+
+    #[test]
+    fn test_disabled_button_is_skipped_and_muted() {
+        let theme = Theme::light();
+        let mut modal = Modal::new("Save?")
+            .animation(ModalAnimation::None)
+            .theme(theme.clone())
+            .disable_button(ButtonFocus::Primary);
+        modal.show();
+        assert_eq!(modal.focused_button(), ButtonFocus::Secondary);
+
+        let tab = Event::Key(KeyEvent::from(KeyCode::Tab));
+        assert_eq!(modal.handle_event(&tab), None);
+        assert_eq!(modal.focused_button(), ButtonFocus::Secondary);
+        let yes = Event::Key(KeyEvent::from(KeyCode::Char('y')));
+        assert_eq!(modal.handle_event(&yes), None);
+
+        let mut terminal = ratatui::Terminal::new(TestBackend::new(80, 24)).unwrap();
+        terminal.draw(|frame| modal.render(frame)).unwrap();
+        let primary = modal.hit_regions.area(ButtonFocus::Primary).unwrap();
+        let secondary = modal.hit_regions.area(ButtonFocus::Secondary).unwrap();
+        let buffer = terminal.backend().buffer();
+        assert_eq!(buffer[(primary.x + 2, primary.y)].fg, theme.muted);
+        assert_eq!(buffer[(secondary.x + 1, secondary.y)].bg, theme.secondary);
+
+        let click = mouse(MouseEventKind::Down(MouseButton::Left), primary.x, primary.y);
+        assert_eq!(modal.handle_event(&click), None);
+    }
+
+    #[test]
+    fn test_hidden_modal_has_no_hit_regions() {
+        let mut modal = Modal::new("Quit?");
+        draw(&modal, 80, 24);
+        let click = mouse(MouseEventKind::Down(MouseButton::Left), 40, 12);
+        assert_eq!(modal.handle_event(&click), None);
+    }
+
+    #[test]
+    fn test_modal_layer_closes_with_action() {
+        let mut overlay = crate::components::Overlay::new();
+        overlay.push(Modal::new("Delete?"));
+        let enter = Event::Key(KeyEvent::from(KeyCode::Enter));
+        assert_eq!(overlay.handle_event(&enter), Some(Action::Confirm));
+        assert!(overlay.is_empty());
+    }
+
+    #[test]
+    fn test_animation_is_time_based_and_reports_completion() {
+        let start = Instant::now();
+        let mut modal = Modal::new("Saved").animation_duration(Duration::from_millis(200));
+        modal.show_at(start);
+
+        // Polling often does not speed the animation up
+        for _ in 0..50 {
+            assert_eq!(modal.update_animation_at(start + Duration::from_millis(100)), None);
+        }
+        assert_eq!(modal.state(), ModalState::Appearing);
+
+        assert_eq!(
+            modal.update_animation_at(start + Duration::from_millis(250)),
+            Some(ModalMessage::AnimationComplete(ModalState::Visible))
+        );
+        assert_eq!(modal.update_animation_at(start + Duration::from_millis(300)), None);
+
+        modal.hide_at(start + Duration::from_millis(300));
+        assert_eq!(
+            modal.update_animation_at(start + Duration::from_millis(600)),
+            Some(ModalMessage::AnimationComplete(ModalState::Hidden))
+        );
+    }
+
+    #[test]
+    fn test_zoom_scales_rendered_rect() {
+        let start = Instant::now();
+        let mut modal = Modal::new("Saved")
+            .animation(ModalAnimation::Zoom)
+            .animation_duration(Duration::from_millis(200));
+        modal.show_at(start);
+        draw(&modal, 100, 40);
+        let small = modal.hit_regions.area(ButtonFocus::Primary).unwrap();
+
+        modal.update_animation_at(start + Duration::from_millis(250));
+        draw(&modal, 100, 40);
+        let full = modal.hit_regions.area(ButtonFocus::Primary).unwrap();
+
+        // The box grows around the centre, so the buttons move outward
+        assert!(small.x > full.x);
+    }
+
+    #[test]
+    fn test_slide_offsets_rendered_rect() {
+        let start = Instant::now();
+        let mut modal = Modal::new("Saved")
+            .animation(ModalAnimation::Slide(SlideEdge::Bottom))
+            .animation_duration(Duration::from_millis(200));
+        modal.show_at(start);
+        modal.update_animation_at(start + Duration::from_millis(100));
+        draw(&modal, 100, 40);
+        let sliding = modal.hit_regions.area(ButtonFocus::Primary).unwrap();
+
+        modal.update_animation_at(start + Duration::from_millis(250));
+        draw(&modal, 100, 40);
+        let settled = modal.hit_regions.area(ButtonFocus::Primary).unwrap();
+
+        assert_eq!(sliding.x, settled.x);
+        assert!(sliding.y > settled.y);
+    }
+
+    #[test]
+    fn test_sliding_button_regions_are_clipped_at_the_edge() {
+        let start = Instant::now();
+        let mut modal = Modal::new("Saved")
+            .animation(ModalAnimation::Slide(SlideEdge::Left))
+            .animation_duration(Duration::from_millis(200));
+        modal.show_at(start);
+        let click = |column| mouse(MouseEventKind::Down(MouseButton::Left), column, 21);
+
+        // The button is still entirely off the left edge
+        modal.update_animation_at(start + Duration::from_millis(70));
+        draw(&modal, 100, 40);
+        assert_eq!(modal.hit_regions.area(ButtonFocus::Primary), None);
+        assert_ne!(modal.handle_event(&click(0)), Some(ModalMessage::PrimaryButton));
+
+        // Only the part of the button on screen is clickable
+        modal.update_animation_at(start + Duration::from_millis(80));
+        draw(&modal, 100, 40);
+        assert_eq!(modal.hit_regions.area(ButtonFocus::Primary), Some(Rect::new(0, 21, 5, 1)));
+        assert_eq!(modal.handle_event(&click(0)), Some(ModalMessage::PrimaryButton));
+        assert_ne!(modal.handle_event(&click(5)), Some(ModalMessage::PrimaryButton));
+    }
+}
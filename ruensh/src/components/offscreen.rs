@@ -0,0 +1,71 @@
+//! Terminal without a screen, for rendering into scratch buffers
+//!
+//! Components draw through a `Frame`, which only a `Terminal` hands out.
+//! An off-screen terminal lets a component render somewhere other than the
+//! frame it was given, for scrolling or compositing.
+
+use ratatui::backend::{Backend, ClearType, WindowSize};
+use ratatui::buffer::Cell;
+use ratatui::prelude::*;
+use ratatui::{TerminalOptions, Viewport};
+use std::io;
+
+/// Backend that discards output; frames only come from a `Terminal`, and
+/// the off-screen one is never flushed
+pub(crate) struct Offscreen;
+
+impl Backend for Offscreen {
+    fn draw<'a, I>(&mut self, _content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        Ok(())
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn get_cursor_position(&mut self) -> io::Result<Position> {
+        Ok(Position::ORIGIN)
+    }
+
+    fn set_cursor_position<P: Into<Position>>(&mut self, _position: P) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn clear_region(&mut self, _clear_type: ClearType) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn size(&self) -> io::Result<Size> {
+        Ok(Size::default())
+    }
+
+    fn window_size(&mut self) -> io::Result<WindowSize> {
+        Ok(WindowSize {
+            columns_rows: Size::default(),
+            pixels: Size::default(),
+        })
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Terminal whose frames cover exactly `area`
+pub(crate) fn terminal(area: Rect) -> Terminal<Offscreen> {
+    let options = TerminalOptions {
+        viewport: Viewport::Fixed(area),
+    };
+    Terminal::with_options(Offscreen, options).expect("offscreen backend never fails")
+}
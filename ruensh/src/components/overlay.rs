@@ -0,0 +1,329 @@
+//! Overlay layer manager for stacking modals and popups
+//!
+//! An `Overlay` keeps a stack of layers ordered by z-index. Layers are
+//! rendered bottom-to-top, input events go to the topmost layer, and when a
+//! layer closes its result is handed to the layer beneath it.
+
+use super::offscreen;
+use crate::events::Event;
+use crate::state::Action;
+use crate::style::Theme;
+use ratatui::buffer::Cell;
+use ratatui::prelude::*;
+
+/// Response from a layer after handling an event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayerResponse {
+    /// The event was not used by the layer
+    Ignored,
+    /// The event was used and the layer stays open
+    Consumed,
+    /// The layer is done and should be removed with the given result
+    Close(Action),
+}
+
+/// A single entry in the overlay stack
+pub trait Layer {
+    /// Render the layer into the given area
+    fn render_layer(&self, frame: &mut Frame<'_>, area: Rect);
+
+    /// Handle an event routed to this layer
+    fn layer_event(&mut self, event: &Event) -> LayerResponse;
+
+    /// Whether the layer is modal (dims and blocks the layers beneath it)
+    fn is_modal(&self) -> bool {
+        true
+    }
+
+    /// Called when the layer is pushed onto the stack
+    fn on_push(&mut self) {}
+
+    /// Receive the result of the layer that was closed above this one
+    fn on_result(&mut self, _result: Action) {}
+
+    /// Apply a new theme at runtime
+    fn on_theme_change(&mut self, _theme: &Theme) {}
+}
+
+/// Stack of layers rendered bottom-to-top
+#[derive(Default)]
+pub struct Overlay {
+    layers: Vec<Box<dyn Layer>>,
+}
+
+impl Overlay {
+    /// Create an empty overlay stack
+    pub fn new() -> Self {
+        Self { layers: Vec::new() }
+    }
+
+    /// Push a layer on top of the stack
+    pub fn push(&mut self, layer: impl Layer + 'static) {
+        self.push_boxed(Box::new(layer));
+    }
+
+    /// Push an already boxed layer on top of the stack
+    pub fn push_boxed(&mut self, mut layer: Box<dyn Layer>) {
+        layer.on_push();
+        self.layers.push(layer);
+    }
+
+    /// Remove the topmost layer without delivering a result
+    pub fn pop(&mut self) -> Option<Box<dyn Layer>> {
+        self.layers.pop()
+    }
+
+    /// Number of layers in the stack
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Check whether the stack is empty
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Apply a new theme to every layer
+    pub fn set_theme(&mut self, theme: &Theme) {
+        for layer in &mut self.layers {
+            layer.on_theme_change(theme);
+        }
+    }
+
+    /// Index of the topmost modal layer, if any
+    fn top_modal(&self) -> Option<usize> {
+        self.layers.iter().rposition(|layer| layer.is_modal())
+    }
+
+    /// Render all layers, dimming everything beneath the topmost modal
+    pub fn render(&self, frame: &mut Frame<'_>, area: Rect) {
+        let top_modal = self.top_modal();
+
+        for (index, layer) in self.layers.iter().enumerate() {
+            if Some(index) == top_modal && index > 0 {
+                dim_area(frame.buffer_mut(), area);
+                render_undimmed(layer.as_ref(), frame, area);
+            } else {
+                layer.render_layer(frame, area);
+            }
+        }
+    }
+
+    /// Route an event through the stack
+    ///
+    /// Key and mouse events go to the topmost layer, falling through to the
+    /// layer beneath while a non-modal layer ignores them. `Tick` and
+    /// `Resize` reach every layer so animations beneath keep running.
+    /// Returns the result of a closed layer when nothing is left beneath it.
+    pub fn handle_event(&mut self, event: &Event) -> Option<Action> {
+        if matches!(event, Event::Tick | Event::Resize(..)) {
+            let mut result = None;
+            let mut index = 0;
+            while index < self.layers.len() {
+                match self.layers[index].layer_event(event) {
+                    LayerResponse::Close(action) => result = result.or(self.close_at(index, action)),
+                    LayerResponse::Ignored | LayerResponse::Consumed => index += 1,
+                }
+            }
+            return result;
+        }
+
+        for index in (0..self.layers.len()).rev() {
+            match self.layers[index].layer_event(event) {
+                LayerResponse::Close(result) => return self.close_at(index, result),
+                LayerResponse::Consumed => return None,
+                LayerResponse::Ignored if self.layers[index].is_modal() => return None,
+                LayerResponse::Ignored => {}
+            }
+        }
+        None
+    }
+
+    /// Close the topmost layer and deliver its result to the one beneath
+    pub fn close_top(&mut self, result: Action) -> Option<Action> {
+        let top = self.layers.len().checked_sub(1)?;
+        self.close_at(top, result)
+    }
+
+    /// Remove the layer at `index` and deliver its result to the one beneath
+    fn close_at(&mut self, index: usize, result: Action) -> Option<Action> {
+        self.layers.remove(index);
+        match index.checked_sub(1) {
+            Some(beneath) => {
+                self.layers[beneath].on_result(result);
+                None
+            }
+            None => Some(result),
+        }
+    }
+}
+
+/// Dim every cell in the area to form a backdrop
+fn dim_area(buffer: &mut Buffer, area: Rect) {
+    let area = area.intersection(buffer.area);
+    let style = Style::default()
+        .fg(Color::DarkGray)
+        .add_modifier(Modifier::DIM);
+    buffer.set_style(area, style);
+}
+
+/// Render a layer on clean cells and copy the cells it drew over the frame
+///
+/// Drawing straight onto the dimmed backdrop would leave `DIM` on every
+/// cell the layer does not fully restyle.
+fn render_undimmed(layer: &dyn Layer, frame: &mut Frame<'_>, area: Rect) {
+    let buffer = frame.buffer_mut();
+    let area = area.intersection(buffer.area);
+    let mut terminal = offscreen::terminal(area);
+    layer.render_layer(&mut terminal.get_frame(), area);
+    let scratch = terminal.current_buffer_mut();
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let cell = &scratch[(x, y)];
+            if *cell != Cell::default() {
+                buffer[(x, y)] = cell.clone();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyEvent};
+    use ratatui::backend::TestBackend;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    struct TestLayer {
+        name: &'static str,
+        modal: bool,
+        log: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl TestLayer {
+        fn new(name: &'static str, modal: bool, log: &Rc<RefCell<Vec<String>>>) -> Self {
+            Self {
+                name,
+                modal,
+                log: Rc::clone(log),
+            }
+        }
+    }
+
+    impl Layer for TestLayer {
+        fn render_layer(&self, frame: &mut Frame<'_>, area: Rect) {
+            frame.render_widget(ratatui::widgets::Paragraph::new(self.name), area);
+        }
+
+        fn layer_event(&mut self, event: &Event) -> LayerResponse {
+            self.log.borrow_mut().push(format!("{}:{}", self.name, event_name(event)));
+            match event {
+                Event::Key(key) if key.code == KeyCode::Enter => {
+                    LayerResponse::Close(Action::Custom(self.name.to_string()))
+                }
+                _ => LayerResponse::Consumed,
+            }
+        }
+
+        fn is_modal(&self) -> bool {
+            self.modal
+        }
+
+        fn on_result(&mut self, result: Action) {
+            self.log.borrow_mut().push(format!("{} got {:?}", self.name, result));
+        }
+    }
+
+    fn event_name(event: &Event) -> &'static str {
+        match event {
+            Event::Key(_) => "key",
+            Event::Mouse(_) => "mouse",
+            Event::Resize(..) => "resize",
+            Event::Tick => "tick",
+        }
+    }
+
+    fn enter() -> Event {
+        Event::Key(KeyEvent::from(KeyCode::Enter))
+    }
+
+    #[test]
+    fn test_events_go_to_topmost_layer() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut overlay = Overlay::new();
+        overlay.push(TestLayer::new("main", false, &log));
+        overlay.push(TestLayer::new("picker", true, &log));
+
+        overlay.handle_event(&Event::Key(KeyEvent::from(KeyCode::Down)));
+        assert_eq!(*log.borrow(), vec!["picker:key"]);
+    }
+
+    #[test]
+    fn test_closed_layer_result_goes_to_layer_beneath() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut overlay = Overlay::new();
+        overlay.push(TestLayer::new("main", false, &log));
+        overlay.push(TestLayer::new("picker", true, &log));
+        overlay.push(TestLayer::new("confirm", true, &log));
+
+        assert_eq!(overlay.handle_event(&enter()), None);
+        assert_eq!(overlay.len(), 2);
+        assert!(log
+            .borrow()
+            .contains(&"picker got Custom(\"confirm\")".to_string()));
+    }
+
+    #[test]
+    fn test_closing_last_layer_returns_result() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut overlay = Overlay::new();
+        overlay.push(TestLayer::new("main", false, &log));
+        assert_eq!(
+            overlay.handle_event(&enter()),
+            Some(Action::Custom("main".to_string()))
+        );
+        assert!(overlay.is_empty());
+    }
+
+    #[test]
+    fn test_tick_reaches_every_layer() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut overlay = Overlay::new();
+        overlay.push(TestLayer::new("main", false, &log));
+        overlay.push(TestLayer::new("picker", true, &log));
+        overlay.handle_event(&Event::Tick);
+        assert_eq!(log.borrow().len(), 2);
+    }
+
+    #[test]
+    fn test_only_layers_below_top_modal_are_dimmed() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut overlay = Overlay::new();
+        overlay.push(TestLayer::new("main", false, &log));
+        overlay.push(TestLayer::new("picker", true, &log));
+
+        let mut terminal = ratatui::Terminal::new(TestBackend::new(20, 2)).unwrap();
+        terminal
+            .draw(|frame| overlay.render(frame, frame.area()))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+
+        // "picker" is drawn undimmed over the dimmed "main"
+        assert_eq!(buffer[(0, 0)].symbol(), "p");
+        assert!(!buffer[(0, 0)].modifier.contains(Modifier::DIM));
+        assert_eq!(buffer[(0, 0)].fg, Color::Reset);
+        assert!(buffer[(10, 1)].modifier.contains(Modifier::DIM));
+    }
+
+    #[test]
+    fn test_ignored_input_falls_through_non_modal_layer() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut overlay = Overlay::new();
+        overlay.push(TestLayer::new("main", false, &log));
+        overlay.push(crate::components::Toasts::new());
+
+        overlay.handle_event(&Event::Key(KeyEvent::from(KeyCode::Down)));
+        assert_eq!(*log.borrow(), vec!["main:key"]);
+    }
+}
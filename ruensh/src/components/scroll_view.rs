@@ -0,0 +1,542 @@
+//! Scrollable viewport for content larger than its area
+//!
+//! The child renders into an off-screen buffer of its natural size and the
+//! visible window is copied into the frame, so children need no scrolling
+//! logic of their own. Mouse events are translated into content coordinates
+//! before they reach the child.
+//!
+//! ratatui buffers hold at most 65,535 cells. Content larger than that is
+//! drawn with `Component::render_window` into a buffer covering only the
+//! visible window, positioned at the scroll offset in content coordinates.
+//! `Markdown` implements it; other children need to for long content.
+
+use super::offscreen::{self, Offscreen};
+use super::Component;
+use crate::events::Event;
+use crate::state::Action;
+use crate::style::Theme;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
+use ratatui::prelude::*;
+use ratatui::widgets::{Scrollbar, ScrollbarOrientation, ScrollbarState};
+use std::cell::{Cell, RefCell};
+
+/// Lines moved per mouse wheel step
+const WHEEL_STEP: i32 = 3;
+
+/// Most cells a ratatui buffer can index
+const MAX_CELLS: u32 = u16::MAX as u32;
+
+/// Message types for the scroll view, wrapping the child message type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScrollViewMessage<M> {
+    /// Scroll by a number of columns and rows
+    ScrollBy { dx: i32, dy: i32 },
+    /// Scroll to an absolute offset
+    ScrollTo { x: u16, y: u16 },
+    PageUp,
+    PageDown,
+    Top,
+    Bottom,
+    /// Scroll the minimum amount to show a rect given in content coordinates
+    ScrollIntoView(Rect),
+    /// Message for the child component
+    Child(M),
+}
+
+/// Viewport showing a window into a larger child component
+///
+/// Keys the child ignores scroll the view: arrows, PageUp/PageDown,
+/// Home/End. The mouse wheel scrolls vertically, or horizontally with Shift.
+pub struct ScrollView<C: Component> {
+    content: C,
+    /// Content width; `None` follows the viewport width
+    content_width: Option<u16>,
+    content_height: u16,
+    offset: (u16, u16),
+    /// Visible content area at the last render
+    viewport: Cell<Rect>,
+    /// Off-screen terminal reused while the rendered region stays the same
+    offscreen: RefCell<Option<(Rect, Terminal<Offscreen>)>>,
+    show_scrollbars: bool,
+    theme: Theme,
+}
+
+impl<C: Component> ScrollView<C> {
+    /// Wrap a child whose natural height is `content_height` rows
+    pub fn new(content: C, content_height: u16) -> Self {
+        ScrollView {
+            content,
+            content_width: None,
+            content_height,
+            offset: (0, 0),
+            viewport: Cell::new(Rect::default()),
+            offscreen: RefCell::new(None),
+            show_scrollbars: true,
+            theme: Theme::default(),
+        }
+    }
+
+    /// Give the content a fixed width, enabling horizontal scrolling
+    pub fn content_width(mut self, width: u16) -> Self {
+        self.content_width = Some(width);
+        self
+    }
+
+    /// Show or hide the scrollbars
+    pub fn scrollbars(mut self, show: bool) -> Self {
+        self.show_scrollbars = show;
+        self
+    }
+
+    /// Set theme
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Change the content's natural size
+    pub fn set_content_size(&mut self, width: Option<u16>, height: u16) {
+        self.content_width = width;
+        self.content_height = height;
+        self.clamp_offset();
+    }
+
+    /// Get the child component
+    pub fn content(&self) -> &C {
+        &self.content
+    }
+
+    /// Get the child component mutably
+    pub fn content_mut(&mut self) -> &mut C {
+        &mut self.content
+    }
+
+    /// Get the scroll offset as (column, row)
+    pub fn offset(&self) -> (u16, u16) {
+        self.offset
+    }
+
+    /// Scroll the minimum amount to show `target` (in content coordinates)
+    pub fn scroll_into_view(&mut self, target: Rect) {
+        let viewport = self.viewport.get();
+        self.offset.0 = into_view(self.offset.0, viewport.width, target.x, target.width);
+        self.offset.1 = into_view(self.offset.1, viewport.height, target.y, target.height);
+        self.clamp_offset();
+    }
+
+    /// Content size for a viewport of the given width
+    fn content_size(&self, viewport_width: u16) -> (u16, u16) {
+        (self.content_width.unwrap_or(viewport_width), self.content_height)
+    }
+
+    /// Largest scroll offset for the last viewport
+    fn max_offset(&self) -> (u16, u16) {
+        let viewport = self.viewport.get();
+        let (width, height) = self.content_size(viewport.width);
+        (
+            width.saturating_sub(viewport.width),
+            height.saturating_sub(viewport.height),
+        )
+    }
+
+    fn clamp_offset(&mut self) {
+        let (max_x, max_y) = self.max_offset();
+        self.offset = (self.offset.0.min(max_x), self.offset.1.min(max_y));
+    }
+
+    fn scroll_by(&mut self, dx: i32, dy: i32) {
+        let x = (self.offset.0 as i32 + dx).clamp(0, u16::MAX as i32) as u16;
+        let y = (self.offset.1 as i32 + dy).clamp(0, u16::MAX as i32) as u16;
+        self.offset = (x, y);
+        self.clamp_offset();
+    }
+
+    /// Split `area` into the visible content area and the scrollbars
+    fn viewport_for(&self, area: Rect) -> (Rect, Option<Rect>, Option<Rect>) {
+        if !self.show_scrollbars {
+            return (area, None, None);
+        }
+        let needs_vertical = |width: u16| self.content_size(width).1 > area.height;
+        let mut vertical = needs_vertical(area.width);
+        let content_width = area.width - u16::from(vertical && area.width > 0);
+        let horizontal = self.content_size(content_width).0 > content_width;
+        if horizontal && !vertical {
+            vertical = self.content_height > area.height.saturating_sub(1);
+        }
+
+        let viewport = Rect {
+            width: area.width.saturating_sub(u16::from(vertical)),
+            height: area.height.saturating_sub(u16::from(horizontal)),
+            ..area
+        };
+        let vertical_bar = vertical.then(|| Rect {
+            x: viewport.right(),
+            width: 1,
+            ..viewport
+        });
+        let horizontal_bar = horizontal.then(|| Rect {
+            y: viewport.bottom(),
+            height: 1,
+            ..viewport
+        });
+        (viewport, vertical_bar, horizontal_bar)
+    }
+
+    /// Translate a mouse event into content coordinates for the child
+    fn to_content(&self, mouse_event: &MouseEvent) -> Option<Event> {
+        let viewport = self.viewport.get();
+        if !viewport.contains(Position::new(mouse_event.column, mouse_event.row)) {
+            return None;
+        }
+        Some(Event::Mouse(MouseEvent {
+            column: mouse_event.column - viewport.x + self.offset.0,
+            row: mouse_event.row - viewport.y + self.offset.1,
+            ..*mouse_event
+        }))
+    }
+}
+
+/// Offset that shows `start..start + len` inside a window of `window` cells
+fn into_view(offset: u16, window: u16, start: u16, len: u16) -> u16 {
+    let end = start.saturating_add(len);
+    if start < offset || len > window {
+        start
+    } else if end > offset.saturating_add(window) {
+        end - window
+    } else {
+        offset
+    }
+}
+
+impl<C: Component> Component for ScrollView<C> {
+    type Message = ScrollViewMessage<C::Message>;
+
+    fn update(&mut self, msg: Self::Message) -> Option<Action> {
+        let page = self.viewport.get().height.saturating_sub(1).max(1) as i32;
+        match msg {
+            ScrollViewMessage::ScrollBy { dx, dy } => self.scroll_by(dx, dy),
+            ScrollViewMessage::ScrollTo { x, y } => {
+                self.offset = (x, y);
+                self.clamp_offset();
+            }
+            ScrollViewMessage::PageUp => self.scroll_by(0, -page),
+            ScrollViewMessage::PageDown => self.scroll_by(0, page),
+            ScrollViewMessage::Top => self.offset.1 = 0,
+            ScrollViewMessage::Bottom => self.offset.1 = self.max_offset().1,
+            ScrollViewMessage::ScrollIntoView(target) => self.scroll_into_view(target),
+            ScrollViewMessage::Child(msg) => return self.content.update(msg),
+        }
+        None
+    }
+
+    fn render(&self, frame: &mut Frame<'_>) {
+        let area = frame.area();
+        self.render_in(frame, area);
+    }
+
+    fn render_in(&self, frame: &mut Frame<'_>, area: Rect) {
+        let (viewport, vertical_bar, horizontal_bar) = self.viewport_for(area);
+        self.viewport.set(viewport);
+        if viewport.is_empty() {
+            return;
+        }
+
+        let (width, height) = self.content_size(viewport.width);
+        let (max_x, max_y) = self.max_offset();
+        let (offset_x, offset_y) = (self.offset.0.min(max_x), self.offset.1.min(max_y));
+
+        if width > 0 && height > 0 {
+            let buffer = frame.buffer_mut();
+            let visible = viewport.intersection(buffer.area);
+            let window = Rect {
+                x: offset_x,
+                y: offset_y,
+                width: visible.width.min(width - offset_x),
+                height: visible.height.min(height - offset_y),
+            };
+            let content_area = Rect {
+                x: 0,
+                y: 0,
+                width,
+                height,
+            };
+            let region = if width as u32 * height as u32 <= MAX_CELLS {
+                content_area
+            } else {
+                window
+            };
+
+            let mut offscreen = self.offscreen.borrow_mut();
+            if offscreen.as_ref().is_none_or(|(area, _)| *area != region) {
+                *offscreen = Some((region, offscreen::terminal(region)));
+            }
+            let (_, terminal) = offscreen.as_mut().expect("offscreen terminal was just created");
+            terminal.current_buffer_mut().reset();
+            if region == content_area {
+                self.content.render_in(&mut terminal.get_frame(), content_area);
+            } else {
+                self.content.render_window(&mut terminal.get_frame(), content_area, window);
+            }
+
+            // Both buffers use content coordinates, whichever region was rendered
+            let content = terminal.current_buffer_mut();
+            for y in 0..window.height {
+                for x in 0..window.width {
+                    let cell = &content[(offset_x + x, offset_y + y)];
+                    buffer[(visible.x + x, visible.y + y)] = cell.clone();
+                }
+            }
+        }
+
+        let bar_style = Style::default().fg(self.theme.secondary);
+        if let Some(bar) = vertical_bar {
+            let mut state = ScrollbarState::new(max_y as usize)
+                .position(offset_y as usize)
+                .viewport_content_length(viewport.height as usize);
+            frame.render_stateful_widget(
+                Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                    .begin_symbol(None)
+                    .end_symbol(None)
+                    .style(bar_style),
+                bar,
+                &mut state,
+            );
+        }
+        if let Some(bar) = horizontal_bar {
+            let mut state = ScrollbarState::new(max_x as usize)
+                .position(offset_x as usize)
+                .viewport_content_length(viewport.width as usize);
+            frame.render_stateful_widget(
+                Scrollbar::new(ScrollbarOrientation::HorizontalBottom)
+                    .begin_symbol(None)
+                    .end_symbol(None)
+                    .style(bar_style),
+                bar,
+                &mut state,
+            );
+        }
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.theme = theme.clone();
+        self.content.set_theme(theme);
+    }
+
+    fn handle_event(&mut self, event: &Event) -> Option<Self::Message> {
+        match event {
+            Event::Mouse(mouse_event) => {
+                let shift = mouse_event.modifiers.contains(KeyModifiers::SHIFT);
+                match mouse_event.kind {
+                    MouseEventKind::ScrollUp if shift => {
+                        Some(ScrollViewMessage::ScrollBy { dx: -WHEEL_STEP, dy: 0 })
+                    }
+                    MouseEventKind::ScrollDown if shift => {
+                        Some(ScrollViewMessage::ScrollBy { dx: WHEEL_STEP, dy: 0 })
+                    }
+                    MouseEventKind::ScrollUp => Some(ScrollViewMessage::ScrollBy { dx: 0, dy: -WHEEL_STEP }),
+                    MouseEventKind::ScrollDown => Some(ScrollViewMessage::ScrollBy { dx: 0, dy: WHEEL_STEP }),
+                    MouseEventKind::ScrollLeft => Some(ScrollViewMessage::ScrollBy { dx: -WHEEL_STEP, dy: 0 }),
+                    MouseEventKind::ScrollRight => Some(ScrollViewMessage::ScrollBy { dx: WHEEL_STEP, dy: 0 }),
+                    _ => {
+                        let event = self.to_content(mouse_event)?;
+                        self.content.handle_event(&event).map(ScrollViewMessage::Child)
+                    }
+                }
+            }
+            Event::Key(KeyEvent { code, .. }) => {
+                if let Some(msg) = self.content.handle_event(event) {
+                    return Some(ScrollViewMessage::Child(msg));
+                }
+                match code {
+                    KeyCode::Up => Some(ScrollViewMessage::ScrollBy { dx: 0, dy: -1 }),
+                    KeyCode::Down => Some(ScrollViewMessage::ScrollBy { dx: 0, dy: 1 }),
+                    KeyCode::Left => Some(ScrollViewMessage::ScrollBy { dx: -1, dy: 0 }),
+                    KeyCode::Right => Some(ScrollViewMessage::ScrollBy { dx: 1, dy: 0 }),
+                    KeyCode::PageUp => Some(ScrollViewMessage::PageUp),
+                    KeyCode::PageDown => Some(ScrollViewMessage::PageDown),
+                    KeyCode::Home => Some(ScrollViewMessage::Top),
+                    KeyCode::End => Some(ScrollViewMessage::Bottom),
+                    _ => None,
+                }
+            }
+            _ => self.content.handle_event(event).map(ScrollViewMessage::Child),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+
+    /// Child drawing its row number at the start of each line, clipped to the buffer
+    /// so it can render any window of itself
+    struct Lines {
+        clicked: Option<(u16, u16)>,
+    }
+
+    impl Component for Lines {
+        type Message = (u16, u16);
+
+        fn update(&mut self, msg: (u16, u16)) -> Option<Action> {
+            self.clicked = Some(msg);
+            None
+        }
+
+        fn render(&self, frame: &mut Frame<'_>) {
+            let area = frame.area();
+            self.render_in(frame, area);
+        }
+
+        fn render_in(&self, frame: &mut Frame<'_>, area: Rect) {
+            let visible = area.intersection(frame.buffer_mut().area);
+            for y in visible.top()..visible.bottom() {
+                let line = format!("{:<3}{}", y - area.y, "-".repeat(area.width as usize));
+                let line: String = line.chars().skip((visible.x - area.x) as usize).collect();
+                frame.buffer_mut().set_string(visible.x, y, line, Style::default());
+            }
+        }
+
+        fn render_window(&self, frame: &mut Frame<'_>, area: Rect, _window: Rect) {
+            self.render_in(frame, area);
+        }
+
+        fn handle_event(&mut self, event: &Event) -> Option<(u16, u16)> {
+            match event {
+                Event::Mouse(mouse_event) if matches!(mouse_event.kind, MouseEventKind::Down(_)) => {
+                    Some((mouse_event.column, mouse_event.row))
+                }
+                _ => None,
+            }
+        }
+    }
+
+    fn view() -> ScrollView<Lines> {
+        ScrollView::new(Lines { clicked: None }, 50).content_width(30)
+    }
+
+    fn draw(view: &ScrollView<Lines>) -> Buffer {
+        let mut terminal = ratatui::Terminal::new(TestBackend::new(20, 10)).unwrap();
+        terminal.draw(|frame| view.render(frame)).unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    fn send<C: Component>(view: &mut ScrollView<C>, event: Event) {
+        if let Some(msg) = view.handle_event(&event) {
+            view.update(msg);
+        }
+    }
+
+    fn row_label(buffer: &Buffer, row: u16) -> String {
+        (0..2).map(|x| buffer[(x, row)].symbol()).collect::<String>().trim().to_string()
+    }
+
+    #[test]
+    fn test_keys_scroll_and_clamp() {
+        let mut view = view();
+        draw(&view);
+        assert_eq!(view.viewport.get(), Rect::new(0, 0, 19, 9));
+
+        send(&mut view, Event::Key(KeyEvent::from(KeyCode::Down)));
+        let buffer = draw(&view);
+        assert_eq!(row_label(&buffer, 0), "1");
+
+        send(&mut view, Event::Key(KeyEvent::from(KeyCode::End)));
+        assert_eq!(view.offset(), (0, 41));
+        send(&mut view, Event::Key(KeyEvent::from(KeyCode::PageDown)));
+        assert_eq!(view.offset(), (0, 41));
+        let buffer = draw(&view);
+        assert_eq!(row_label(&buffer, 8), "49");
+    }
+
+    #[test]
+    fn test_wheel_and_scrollbars() {
+        let mut view = view();
+        let buffer = draw(&view);
+        // Vertical bar on the right, horizontal bar at the bottom
+        assert_ne!(buffer[(19, 0)].symbol(), "-");
+        assert_ne!(buffer[(0, 9)].symbol(), "0");
+
+        let wheel = |kind, modifiers| {
+            Event::Mouse(MouseEvent {
+                kind,
+                column: 5,
+                row: 5,
+                modifiers,
+            })
+        };
+        send(&mut view, wheel(MouseEventKind::ScrollDown, KeyModifiers::NONE));
+        send(&mut view, wheel(MouseEventKind::ScrollDown, KeyModifiers::SHIFT));
+        assert_eq!(view.offset(), (3, 3));
+
+        send(&mut view, wheel(MouseEventKind::ScrollRight, KeyModifiers::NONE));
+        send(&mut view, wheel(MouseEventKind::ScrollRight, KeyModifiers::NONE));
+        send(&mut view, wheel(MouseEventKind::ScrollRight, KeyModifiers::NONE));
+        assert_eq!(view.offset(), (11, 3));
+    }
+
+    #[test]
+    fn test_mouse_events_use_content_coordinates() {
+        let mut view = view();
+        draw(&view);
+        view.update(ScrollViewMessage::ScrollTo { x: 4, y: 10 });
+        draw(&view);
+
+        let click = Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(crossterm::event::MouseButton::Left),
+            column: 2,
+            row: 3,
+            modifiers: KeyModifiers::NONE,
+        });
+        send(&mut view, click);
+        assert_eq!(view.content().clicked, Some((6, 13)));
+    }
+
+    #[test]
+    fn test_scroll_into_view_moves_minimally() {
+        let mut view = view();
+        draw(&view);
+        view.scroll_into_view(Rect::new(0, 20, 10, 2));
+        assert_eq!(view.offset(), (0, 13));
+        view.scroll_into_view(Rect::new(0, 15, 10, 1));
+        assert_eq!(view.offset(), (0, 13));
+        view.scroll_into_view(Rect::new(25, 2, 3, 1));
+        assert_eq!(view.offset(), (9, 2));
+    }
+
+    #[test]
+    fn test_content_larger_than_a_buffer() {
+        // 79 × 1000 cells is more than a ratatui buffer can hold
+        let mut view = ScrollView::new(Lines { clicked: None }, 1000);
+        let mut terminal = ratatui::Terminal::new(TestBackend::new(80, 24)).unwrap();
+        terminal.draw(|frame| view.render(frame)).unwrap();
+        assert_eq!(row_label(terminal.backend().buffer(), 0), "0");
+
+        send(&mut view, Event::Key(KeyEvent::from(KeyCode::End)));
+        assert_eq!(view.offset(), (0, 976));
+        terminal.draw(|frame| view.render(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let label: String = (0..3).map(|x| buffer[(x, 23)].symbol()).collect();
+        assert_eq!(label, "999");
+        assert_eq!(buffer[(78, 23)].symbol(), "-");
+    }
+
+    #[test]
+    fn test_markdown_larger_than_a_buffer() {
+        let source: String = (0..1000).map(|line| format!("line {}\n\n", line)).collect();
+        let mut view = ScrollView::new(crate::components::markdown::Markdown::new(&source), 1999).content_width(80);
+        let mut terminal = ratatui::Terminal::new(TestBackend::new(80, 24)).unwrap();
+        terminal.draw(|frame| view.render(frame)).unwrap();
+        let text = |buffer: &Buffer, row: u16| -> String {
+            (0..9).map(|x| buffer[(x, row)].symbol()).collect::<String>().trim_end().to_string()
+        };
+        assert_eq!(text(terminal.backend().buffer(), 0), "line 0");
+
+        // Markdown takes the End key itself, so scroll the view directly
+        view.update(ScrollViewMessage::Bottom);
+        assert_eq!(view.offset(), (0, 1976));
+        terminal.draw(|frame| view.render(frame)).unwrap();
+        assert_eq!(text(terminal.backend().buffer(), 22), "line 999");
+        assert_eq!(text(terminal.backend().buffer(), 20), "line 998");
+    }
+}
@@ -0,0 +1,568 @@
+//! Resizable split panes with draggable dividers
+
+use super::hit_test::HitRegions;
+use super::Component;
+use crate::events::Event;
+use crate::layout::{Constraint, Layout};
+use crate::state::Action;
+use crate::style::{Slot, StyleSheet, Theme, Variant};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEventKind};
+use ratatui::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+
+/// Message types for split panes, wrapping the child message type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SplitPaneMessage<M> {
+    Focus(usize),
+    FocusNext,
+    FocusPrev,
+    /// Move a divider by a number of cells
+    MoveDivider { divider: usize, delta: i16 },
+    /// Start dragging a divider
+    DragStart(usize),
+    /// Move the dragged divider to a column (horizontal) or row (vertical)
+    DragTo(u16),
+    DragEnd,
+    ToggleCollapse(usize),
+    /// Message for the child in the given pane, which also gains focus
+    Child(usize, M),
+}
+
+/// Interactive areas of the split pane
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SplitRegion {
+    Pane(usize),
+    Divider(usize),
+}
+
+/// Pane sizes that can be saved and restored between sessions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SplitState {
+    /// Relative size of each pane
+    pub ratios: Vec<f32>,
+    pub collapsed: Vec<bool>,
+}
+
+/// A single pane and its content
+pub struct Pane<C> {
+    content: C,
+    ratio: f32,
+    min_size: u16,
+    collapsed: bool,
+}
+
+impl<C> Pane<C> {
+    /// Create a pane with ratio 1
+    pub fn new(content: C) -> Self {
+        Pane {
+            content,
+            ratio: 1.0,
+            min_size: 1,
+            collapsed: false,
+        }
+    }
+
+    /// Set the pane's size relative to the others
+    pub fn ratio(mut self, ratio: f32) -> Self {
+        self.ratio = ratio.max(0.0);
+        self
+    }
+
+    /// Set the smallest size the pane can be dragged to
+    pub fn min_size(mut self, min_size: u16) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    /// Get the pane's component
+    pub fn content(&self) -> &C {
+        &self.content
+    }
+
+    /// Get the pane's component mutably
+    pub fn content_mut(&mut self) -> &mut C {
+        &mut self.content
+    }
+
+    /// Check whether the pane is collapsed
+    pub fn is_collapsed(&self) -> bool {
+        self.collapsed
+    }
+}
+
+/// Container showing child components side by side with movable dividers
+///
+/// Keys: F6/Shift+F6 cycle focus, Ctrl+arrows move the divider after the
+/// focused pane, Ctrl+O collapses or expands it. Dividers can be dragged.
+pub struct SplitPane<C: Component> {
+    direction: Direction,
+    panes: Vec<Pane<C>>,
+    focused: usize,
+    dragging: Option<usize>,
+    /// Space shared by the panes at the last render
+    available: Cell<u16>,
+    theme: Theme,
+    styles: StyleSheet,
+    hit_regions: HitRegions<SplitRegion>,
+}
+
+impl<C: Component> SplitPane<C> {
+    fn new(direction: Direction) -> Self {
+        SplitPane {
+            direction,
+            panes: Vec::new(),
+            focused: 0,
+            dragging: None,
+            available: Cell::new(0),
+            theme: Theme::default(),
+            styles: StyleSheet::new(),
+            hit_regions: HitRegions::new(),
+        }
+    }
+
+    /// Create panes laid out left to right
+    pub fn horizontal() -> Self {
+        Self::new(Direction::Horizontal)
+    }
+
+    /// Create panes laid out top to bottom
+    pub fn vertical() -> Self {
+        Self::new(Direction::Vertical)
+    }
+
+    /// Add a pane
+    pub fn pane(mut self, pane: Pane<C>) -> Self {
+        self.panes.push(pane);
+        self
+    }
+
+    /// Set theme
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Override slot styles (`border` for dividers, `Focus` while dragged)
+    pub fn styles(mut self, styles: StyleSheet) -> Self {
+        self.styles = styles;
+        self
+    }
+
+    /// Get all panes
+    pub fn panes(&self) -> &[Pane<C>] {
+        &self.panes
+    }
+
+    /// Get a pane mutably
+    pub fn pane_mut(&mut self, index: usize) -> Option<&mut Pane<C>> {
+        self.panes.get_mut(index)
+    }
+
+    /// Get the focused pane index
+    pub fn focused(&self) -> usize {
+        self.focused
+    }
+
+    /// Save pane ratios and collapsed flags
+    pub fn state(&self) -> SplitState {
+        SplitState {
+            ratios: self.panes.iter().map(|pane| pane.ratio).collect(),
+            collapsed: self.panes.iter().map(|pane| pane.collapsed).collect(),
+        }
+    }
+
+    /// Restore a saved state; returns false if it was saved for a different pane count
+    pub fn restore(&mut self, state: &SplitState) -> bool {
+        if state.ratios.len() != self.panes.len() || state.collapsed.len() != self.panes.len() {
+            return false;
+        }
+        for ((pane, ratio), collapsed) in self.panes.iter_mut().zip(&state.ratios).zip(&state.collapsed) {
+            pane.ratio = ratio.max(0.0);
+            pane.collapsed = *collapsed;
+        }
+        true
+    }
+
+    /// Collapse or expand a pane
+    pub fn toggle_collapse(&mut self, index: usize) {
+        if let Some(pane) = self.panes.get_mut(index) {
+            pane.collapsed = !pane.collapsed;
+        }
+    }
+
+    /// Size of each pane when `available` cells are shared between them
+    ///
+    /// Space is split by ratio; panes that would fall below their minimum
+    /// get the minimum and the rest is shared among the others.
+    fn sizes(&self, available: u16) -> Vec<u16> {
+        let mut sizes = vec![0u16; self.panes.len()];
+        let mut fixed: Vec<bool> = self.panes.iter().map(|pane| pane.collapsed).collect();
+
+        loop {
+            let taken: u16 = sizes.iter().zip(&fixed).filter(|(_, f)| **f).map(|(s, _)| *s).sum();
+            let remaining = available.saturating_sub(taken);
+            let open: Vec<usize> = (0..self.panes.len()).filter(|&i| !fixed[i]).collect();
+            let total: f32 = open.iter().map(|&i| self.panes[i].ratio).sum();
+            if open.is_empty() {
+                break;
+            }
+
+            let mut given = 0;
+            for &i in &open {
+                let share = if total > 0.0 {
+                    self.panes[i].ratio / total
+                } else {
+                    1.0 / open.len() as f32
+                };
+                sizes[i] = (remaining as f32 * share).floor() as u16;
+                given += sizes[i];
+            }
+            if let Some(&last) = open.last() {
+                sizes[last] += remaining - given;
+            }
+
+            let too_small: Vec<usize> = open
+                .into_iter()
+                .filter(|&i| sizes[i] < self.panes[i].min_size)
+                .collect();
+            if too_small.is_empty() {
+                break;
+            }
+            for i in too_small {
+                sizes[i] = self.panes[i].min_size.min(remaining);
+                fixed[i] = true;
+            }
+        }
+        sizes
+    }
+
+    /// Set the size of the pane before `divider`, taking space from the pane after it
+    fn resize_divider(&mut self, divider: usize, size: i32) {
+        let (before, after) = (divider, divider + 1);
+        if after >= self.panes.len() || self.panes[before].collapsed || self.panes[after].collapsed {
+            return;
+        }
+        let sizes = self.sizes(self.available.get());
+        let pair = sizes[before] as i32 + sizes[after] as i32;
+        let min_before = self.panes[before].min_size as i32;
+        let min_after = self.panes[after].min_size as i32;
+        if pair <= 0 || pair < min_before + min_after {
+            return;
+        }
+
+        let size = size.clamp(min_before, pair - min_after);
+        let ratio = self.panes[before].ratio + self.panes[after].ratio;
+        self.panes[before].ratio = ratio * size as f32 / pair as f32;
+        self.panes[after].ratio = ratio - self.panes[before].ratio;
+    }
+
+    /// Main-axis start of a pane at the last render
+    fn pane_start(&self, index: usize) -> Option<u16> {
+        let area = self.hit_regions.area(SplitRegion::Pane(index))?;
+        Some(match self.direction {
+            Direction::Horizontal => area.x,
+            Direction::Vertical => area.y,
+        })
+    }
+
+    /// Divider that keyboard resizing moves for the focused pane
+    fn focused_divider(&self) -> Option<usize> {
+        match self.panes.len() {
+            0 | 1 => None,
+            len if self.focused + 1 < len => Some(self.focused),
+            _ => Some(self.focused - 1),
+        }
+    }
+}
+
+impl<C: Component> Component for SplitPane<C> {
+    type Message = SplitPaneMessage<C::Message>;
+
+    fn update(&mut self, msg: Self::Message) -> Option<Action> {
+        let len = self.panes.len();
+        match msg {
+            SplitPaneMessage::Focus(index) if index < len => self.focused = index,
+            SplitPaneMessage::FocusNext if len > 0 => self.focused = (self.focused + 1) % len,
+            SplitPaneMessage::FocusPrev if len > 0 => self.focused = (self.focused + len - 1) % len,
+            SplitPaneMessage::Focus(_) | SplitPaneMessage::FocusNext | SplitPaneMessage::FocusPrev => {}
+            SplitPaneMessage::MoveDivider { divider, delta } => {
+                let sizes = self.sizes(self.available.get());
+                if let Some(size) = sizes.get(divider) {
+                    self.resize_divider(divider, *size as i32 + delta as i32);
+                }
+            }
+            SplitPaneMessage::DragStart(divider) => self.dragging = Some(divider),
+            SplitPaneMessage::DragTo(position) => {
+                if let Some(divider) = self.dragging {
+                    if let Some(start) = self.pane_start(divider) {
+                        self.resize_divider(divider, position as i32 - start as i32);
+                    }
+                }
+            }
+            SplitPaneMessage::DragEnd => self.dragging = None,
+            SplitPaneMessage::ToggleCollapse(index) => self.toggle_collapse(index),
+            SplitPaneMessage::Child(index, msg) => {
+                let pane = self.panes.get_mut(index)?;
+                self.focused = index;
+                return pane.content.update(msg);
+            }
+        }
+        None
+    }
+
+    fn render(&self, frame: &mut Frame<'_>) {
+        let area = frame.area();
+        self.render_in(frame, area);
+    }
+
+    fn render_in(&self, frame: &mut Frame<'_>, area: Rect) {
+        self.hit_regions.clear();
+        if self.panes.is_empty() {
+            return;
+        }
+
+        let horizontal = self.direction == Direction::Horizontal;
+        let main = if horizontal { area.width } else { area.height };
+        let dividers = self.panes.len() as u16 - 1;
+        let available = main.saturating_sub(dividers);
+        self.available.set(available);
+
+        let mut constraints = Vec::new();
+        for (index, size) in self.sizes(available).into_iter().enumerate() {
+            if index > 0 {
+                constraints.push(Constraint::Length(1));
+            }
+            constraints.push(Constraint::Length(size));
+        }
+        let areas = if horizontal {
+            Layout::horizontal(constraints).split(area)
+        } else {
+            Layout::vertical(constraints).split(area)
+        };
+
+        for (index, pane) in self.panes.iter().enumerate() {
+            let pane_area = areas[index * 2];
+            if !pane.collapsed && !pane_area.is_empty() {
+                pane.content.render_in(frame, pane_area);
+                self.hit_regions.register(SplitRegion::Pane(index), pane_area);
+            }
+
+            if index == 0 {
+                continue;
+            }
+            let divider = index - 1;
+            let divider_area = areas[index * 2 - 1];
+            let variant = if self.dragging == Some(divider) { Variant::Focus } else { Variant::Normal };
+            let style = self.styles.resolve(&self.theme, Slot::Border, variant);
+            let symbol = if horizontal { "│" } else { "─" };
+            let buffer = frame.buffer_mut();
+            for position in divider_area.positions() {
+                if buffer.area.contains(position) {
+                    buffer[position].set_symbol(symbol).set_style(style);
+                }
+            }
+            self.hit_regions.register(SplitRegion::Divider(divider), divider_area);
+        }
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.theme = theme.clone();
+        for pane in &mut self.panes {
+            pane.content.set_theme(theme);
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> Option<Self::Message> {
+        let horizontal = self.direction == Direction::Horizontal;
+        match event {
+            Event::Key(KeyEvent { code, modifiers, .. }) => {
+                let ctrl = modifiers.contains(KeyModifiers::CONTROL);
+                let delta = match code {
+                    KeyCode::Left if ctrl && horizontal => Some(-1),
+                    KeyCode::Right if ctrl && horizontal => Some(1),
+                    KeyCode::Up if ctrl && !horizontal => Some(-1),
+                    KeyCode::Down if ctrl && !horizontal => Some(1),
+                    _ => None,
+                };
+                if let (Some(delta), Some(divider)) = (delta, self.focused_divider()) {
+                    return Some(SplitPaneMessage::MoveDivider { divider, delta });
+                }
+                match code {
+                    KeyCode::F(6) if modifiers.contains(KeyModifiers::SHIFT) => {
+                        Some(SplitPaneMessage::FocusPrev)
+                    }
+                    KeyCode::F(6) => Some(SplitPaneMessage::FocusNext),
+                    KeyCode::Char('o') if ctrl => Some(SplitPaneMessage::ToggleCollapse(self.focused)),
+                    _ => self.forward(self.focused, event),
+                }
+            }
+            Event::Mouse(mouse_event) => {
+                let position = if horizontal { mouse_event.column } else { mouse_event.row };
+                if self.dragging.is_some() {
+                    return match mouse_event.kind {
+                        MouseEventKind::Drag(_) => Some(SplitPaneMessage::DragTo(position)),
+                        MouseEventKind::Up(_) => Some(SplitPaneMessage::DragEnd),
+                        _ => None,
+                    };
+                }
+                match self.hit_regions.hit(mouse_event.column, mouse_event.row)? {
+                    SplitRegion::Divider(divider) => match mouse_event.kind {
+                        MouseEventKind::Down(MouseButton::Left) => Some(SplitPaneMessage::DragStart(divider)),
+                        _ => None,
+                    },
+                    SplitRegion::Pane(index) => match mouse_event.kind {
+                        MouseEventKind::Down(_) if index != self.focused => self
+                            .forward(index, event)
+                            .or(Some(SplitPaneMessage::Focus(index))),
+                        _ => self.forward(index, event),
+                    },
+                }
+            }
+            _ => self.forward(self.focused, event),
+        }
+    }
+}
+
+impl<C: Component> SplitPane<C> {
+    /// Pass an event to the component in a pane
+    fn forward(&mut self, index: usize, event: &Event) -> Option<SplitPaneMessage<C::Message>> {
+        let pane = self.panes.get_mut(index)?;
+        if pane.collapsed {
+            return None;
+        }
+        pane.content
+            .handle_event(event)
+            .map(|msg| SplitPaneMessage::Child(index, msg))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::MouseEvent;
+    use ratatui::backend::TestBackend;
+
+    /// Child that fills its area with a letter
+    struct Fill(&'static str);
+
+    impl Component for Fill {
+        type Message = ();
+
+        fn update(&mut self, _msg: ()) -> Option<Action> {
+            None
+        }
+
+        fn render(&self, frame: &mut Frame<'_>) {
+            let area = frame.area();
+            self.render_in(frame, area);
+        }
+
+        fn render_in(&self, frame: &mut Frame<'_>, area: Rect) {
+            frame.buffer_mut().set_style(area, Style::default());
+            for position in area.positions() {
+                frame.buffer_mut()[position].set_symbol(self.0);
+            }
+        }
+
+        fn handle_event(&mut self, _event: &Event) -> Option<()> {
+            None
+        }
+    }
+
+    fn split() -> SplitPane<Fill> {
+        SplitPane::horizontal()
+            .pane(Pane::new(Fill("a")).min_size(10))
+            .pane(Pane::new(Fill("b")).ratio(2.0))
+            .pane(Pane::new(Fill("c")).min_size(5))
+    }
+
+    fn draw(split: &SplitPane<Fill>) -> Buffer {
+        let mut terminal = ratatui::Terminal::new(TestBackend::new(42, 3)).unwrap();
+        terminal.draw(|frame| split.render(frame)).unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    fn widths(split: &SplitPane<Fill>) -> Vec<u16> {
+        (0..split.panes().len())
+            .map(|i| split.hit_regions.area(SplitRegion::Pane(i)).map_or(0, |area| area.width))
+            .collect()
+    }
+
+    fn mouse(kind: MouseEventKind, column: u16) -> Event {
+        Event::Mouse(MouseEvent {
+            kind,
+            column,
+            row: 1,
+            modifiers: KeyModifiers::NONE,
+        })
+    }
+
+    fn send(split: &mut SplitPane<Fill>, event: Event) {
+        if let Some(msg) = split.handle_event(&event) {
+            split.update(msg);
+        }
+    }
+
+    #[test]
+    fn test_ratios_and_dividers() {
+        let split = split();
+        let buffer = draw(&split);
+        assert_eq!(widths(&split), [10, 20, 10]);
+        assert_eq!(buffer[(10, 0)].symbol(), "│");
+        assert_eq!(buffer[(11, 0)].symbol(), "b");
+        assert_eq!(buffer[(31, 0)].symbol(), "│");
+    }
+
+    #[test]
+    fn test_drag_divider_respects_min_size() {
+        let mut split = split();
+        draw(&split);
+
+        send(&mut split, mouse(MouseEventKind::Down(MouseButton::Left), 10));
+        send(&mut split, mouse(MouseEventKind::Drag(MouseButton::Left), 15));
+        draw(&split);
+        assert_eq!(widths(&split), [15, 15, 10]);
+
+        send(&mut split, mouse(MouseEventKind::Drag(MouseButton::Left), 2));
+        send(&mut split, mouse(MouseEventKind::Up(MouseButton::Left), 2));
+        draw(&split);
+        assert_eq!(widths(&split)[0], 10);
+    }
+
+    #[test]
+    fn test_keyboard_resize_and_collapse() {
+        let mut split = split();
+        draw(&split);
+        send(&mut split, Event::Key(KeyEvent::from(KeyCode::F(6))));
+        assert_eq!(split.focused(), 1);
+
+        send(&mut split, Event::Key(KeyEvent::new(KeyCode::Right, KeyModifiers::CONTROL)));
+        draw(&split);
+        assert_eq!(widths(&split), [10, 21, 9]);
+
+        send(&mut split, Event::Key(KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL)));
+        draw(&split);
+        assert_eq!(widths(&split)[1], 0);
+        assert_eq!(widths(&split).iter().sum::<u16>(), 40);
+    }
+
+    #[test]
+    fn test_state_round_trip() {
+        let mut split = split();
+        split.toggle_collapse(2);
+        let state = split.state();
+
+        let mut restored = SplitPane::horizontal()
+            .pane(Pane::new(Fill("a")))
+            .pane(Pane::new(Fill("b")))
+            .pane(Pane::new(Fill("c")));
+        assert!(restored.restore(&state));
+        assert_eq!(restored.state(), state);
+        assert!(restored.panes()[2].is_collapsed());
+        assert!(!restored.restore(&SplitState {
+            ratios: vec![1.0],
+            collapsed: vec![false],
+        }));
+    }
+}
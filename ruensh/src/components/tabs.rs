@@ -0,0 +1,714 @@
+//! Tabs component hosting one child component per tab
+
+use super::hit_test::HitRegions;
+use super::Component;
+use crate::events::Event;
+use crate::state::Action;
+use crate::style::{Slot, StyleSheet, Theme, Variant};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEventKind};
+use ratatui::prelude::*;
+use ratatui::widgets::Paragraph;
+use std::cell::Cell;
+use unicode_width::UnicodeWidthStr;
+
+/// Message types for tabs, wrapping the child message type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TabsMessage<M> {
+    Select(usize),
+    Next,
+    Prev,
+    /// Request closing a tab; may wait for confirmation
+    Close(usize),
+    /// Close the tab waiting for confirmation
+    ConfirmClose,
+    /// Keep the tab waiting for confirmation open
+    CancelClose,
+    /// Move a tab to a new position
+    Move {
+        from: usize,
+        to: usize,
+    },
+    ScrollLeft,
+    ScrollRight,
+    /// Select a tab and start dragging it
+    DragStart(usize),
+    DragEnd,
+    /// Message for the active tab's component
+    Child(M),
+}
+
+/// Interactive areas of the tab bar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TabRegion {
+    Tab(usize),
+    Close(usize),
+    ScrollLeft,
+    ScrollRight,
+}
+
+/// Hook deciding whether closing a tab needs confirmation
+type CloseGuard<C> = Box<dyn Fn(&Tab<C>) -> bool>;
+
+/// A single tab and its content
+pub struct Tab<C> {
+    title: String,
+    content: C,
+    dirty: bool,
+    closable: bool,
+}
+
+impl<C> Tab<C> {
+    /// Create a closable tab
+    pub fn new(title: impl Into<String>, content: C) -> Self {
+        Tab {
+            title: title.into(),
+            content,
+            dirty: false,
+            closable: true,
+        }
+    }
+
+    /// Set whether the tab shows a close button
+    pub fn closable(mut self, closable: bool) -> Self {
+        self.closable = closable;
+        self
+    }
+
+    /// Get tab title
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Set tab title
+    pub fn set_title(&mut self, title: impl Into<String>) {
+        self.title = title.into();
+    }
+
+    /// Check whether the tab has unsaved changes
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Mark the tab as having unsaved changes
+    pub fn set_dirty(&mut self, dirty: bool) {
+        self.dirty = dirty;
+    }
+
+    /// Get the tab's component
+    pub fn content(&self) -> &C {
+        &self.content
+    }
+
+    /// Get the tab's component mutably
+    pub fn content_mut(&mut self) -> &mut C {
+        &mut self.content
+    }
+
+    /// Text before the close button
+    fn label(&self) -> String {
+        if self.dirty {
+            format!(" {} ●", self.title)
+        } else {
+            format!(" {}", self.title)
+        }
+    }
+
+    /// Cells used in the tab bar, including padding and close button
+    fn width(&self) -> u16 {
+        let close = if self.closable { 2 } else { 0 };
+        self.label().width() as u16 + close + 1
+    }
+}
+
+/// Tab bar with one child component per tab
+///
+/// Keys: Alt+←/→ switch tabs, Alt+Shift+←/→ reorder, Alt+W closes,
+/// Alt+1..9 jump to a tab. Everything else goes to the active tab.
+pub struct Tabs<C: Component> {
+    tabs: Vec<Tab<C>>,
+    active: usize,
+    /// First tab shown when the bar overflows
+    scroll: Cell<usize>,
+    /// Bring the active tab into view on next render
+    follow_active: Cell<bool>,
+    dragging: Option<usize>,
+    pending_close: Option<usize>,
+    confirm_close: CloseGuard<C>,
+    theme: Theme,
+    styles: StyleSheet,
+    hit_regions: HitRegions<TabRegion>,
+}
+
+impl<C: Component> Tabs<C> {
+    /// Create an empty tab set; dirty tabs ask for confirmation on close
+    pub fn new() -> Self {
+        Tabs {
+            tabs: Vec::new(),
+            active: 0,
+            scroll: Cell::new(0),
+            follow_active: Cell::new(true),
+            dragging: None,
+            pending_close: None,
+            confirm_close: Box::new(|tab| tab.is_dirty()),
+            theme: Theme::default(),
+            styles: StyleSheet::new(),
+            hit_regions: HitRegions::new(),
+        }
+    }
+
+    /// Add a tab
+    pub fn tab(mut self, tab: Tab<C>) -> Self {
+        self.tabs.push(tab);
+        self
+    }
+
+    /// Decide which tabs need confirmation before closing
+    pub fn confirm_close_when(mut self, hook: impl Fn(&Tab<C>) -> bool + 'static) -> Self {
+        self.confirm_close = Box::new(hook);
+        self
+    }
+
+    /// Set theme
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Override slot styles (`tab`, `tab.active`, `separator`)
+    pub fn styles(mut self, styles: StyleSheet) -> Self {
+        self.styles = styles;
+        self
+    }
+
+    /// Append a tab and make it active
+    pub fn push(&mut self, tab: Tab<C>) {
+        self.tabs.push(tab);
+        self.select(self.tabs.len() - 1);
+    }
+
+    /// Activate the tab at `index`
+    pub fn select(&mut self, index: usize) {
+        if index < self.tabs.len() {
+            self.active = index;
+            self.follow_active.set(true);
+        }
+    }
+
+    /// Get the active tab index
+    pub fn active(&self) -> usize {
+        self.active
+    }
+
+    /// Get the active tab
+    pub fn active_tab(&self) -> Option<&Tab<C>> {
+        self.tabs.get(self.active)
+    }
+
+    /// Get the active tab mutably
+    pub fn active_tab_mut(&mut self) -> Option<&mut Tab<C>> {
+        self.tabs.get_mut(self.active)
+    }
+
+    /// Get all tabs in display order
+    pub fn tabs(&self) -> &[Tab<C>] {
+        &self.tabs
+    }
+
+    /// Get a tab mutably
+    pub fn tab_mut(&mut self, index: usize) -> Option<&mut Tab<C>> {
+        self.tabs.get_mut(index)
+    }
+
+    /// Number of tabs
+    pub fn len(&self) -> usize {
+        self.tabs.len()
+    }
+
+    /// Check whether there are no tabs
+    pub fn is_empty(&self) -> bool {
+        self.tabs.is_empty()
+    }
+
+    /// Tab waiting for the app to confirm or cancel closing
+    ///
+    /// Check this after `update`; answer with `TabsMessage::ConfirmClose`
+    /// or `TabsMessage::CancelClose` (e.g. from a `Modal`).
+    pub fn pending_close(&self) -> Option<usize> {
+        self.pending_close
+    }
+
+    /// Close a tab, asking for confirmation if the hook requires it
+    fn request_close(&mut self, index: usize) {
+        let Some(tab) = self.tabs.get(index) else {
+            return;
+        };
+        if !tab.closable {
+            return;
+        }
+        if (self.confirm_close)(tab) {
+            self.pending_close = Some(index);
+        } else {
+            self.remove(index);
+        }
+    }
+
+    /// Remove a tab, keeping a sensible tab active
+    pub fn remove(&mut self, index: usize) -> Option<Tab<C>> {
+        if index >= self.tabs.len() {
+            return None;
+        }
+        let tab = self.tabs.remove(index);
+        if index < self.active || self.active >= self.tabs.len() {
+            self.active = self.active.saturating_sub(1);
+        }
+        self.pending_close = None;
+        self.follow_active.set(true);
+        Some(tab)
+    }
+
+    /// Move a tab, keeping the same tab active
+    fn move_tab(&mut self, from: usize, to: usize) {
+        if from >= self.tabs.len() || to >= self.tabs.len() || from == to {
+            return;
+        }
+        let active = self.active;
+        let tab = self.tabs.remove(from);
+        self.tabs.insert(to, tab);
+        self.active = if active == from {
+            to
+        } else if from < active && to >= active {
+            active - 1
+        } else if from > active && to <= active {
+            active + 1
+        } else {
+            active
+        };
+        self.follow_active.set(true);
+    }
+
+    /// Pick the first visible tab so the bar fits and, if needed, shows the active tab
+    fn first_visible(&self, widths: &[u16], bar_width: u16) -> usize {
+        let mut first = self.scroll.get().min(widths.len().saturating_sub(1));
+        if self.follow_active.get() {
+            first = first.min(self.active);
+            while first < self.active
+                && widths[first..=self.active].iter().sum::<u16>() + (self.active - first) as u16
+                    > bar_width
+            {
+                first += 1;
+            }
+        }
+        first
+    }
+
+    /// Style for a slot in the given variant
+    fn style(&self, slot: Slot, variant: Variant) -> Style {
+        self.styles.resolve(&self.theme, slot, variant)
+    }
+
+    fn tab_style(&self, active: bool) -> Style {
+        self.style(if active { Slot::TabActive } else { Slot::Tab }, Variant::Normal)
+    }
+}
+
+impl<C: Component> Default for Tabs<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Component> Component for Tabs<C> {
+    type Message = TabsMessage<C::Message>;
+
+    fn update(&mut self, msg: Self::Message) -> Option<Action> {
+        match msg {
+            TabsMessage::Select(index) => self.select(index),
+            TabsMessage::Next if !self.tabs.is_empty() => {
+                self.select((self.active + 1) % self.tabs.len())
+            }
+            TabsMessage::Prev if !self.tabs.is_empty() => {
+                self.select((self.active + self.tabs.len() - 1) % self.tabs.len())
+            }
+            TabsMessage::Next | TabsMessage::Prev => {}
+            TabsMessage::Close(index) => self.request_close(index),
+            TabsMessage::ConfirmClose => {
+                if let Some(index) = self.pending_close.take() {
+                    self.remove(index);
+                }
+            }
+            TabsMessage::CancelClose => self.pending_close = None,
+            TabsMessage::Move { from, to } => {
+                self.move_tab(from, to);
+                if self.dragging == Some(from) {
+                    self.dragging = Some(to);
+                }
+            }
+            TabsMessage::ScrollLeft => {
+                self.follow_active.set(false);
+                self.scroll.set(self.scroll.get().saturating_sub(1));
+            }
+            TabsMessage::ScrollRight => {
+                self.follow_active.set(false);
+                let last = self.tabs.len().saturating_sub(1);
+                self.scroll.set((self.scroll.get() + 1).min(last));
+            }
+            TabsMessage::DragStart(index) => {
+                self.select(index);
+                self.dragging = Some(index);
+            }
+            TabsMessage::DragEnd => self.dragging = None,
+            TabsMessage::Child(msg) => {
+                return self.tabs.get_mut(self.active)?.content.update(msg);
+            }
+        }
+        None
+    }
+
+    fn render(&self, frame: &mut Frame<'_>) {
+        let area = frame.area();
+        self.render_in(frame, area);
+    }
+
+    fn render_in(&self, frame: &mut Frame<'_>, area: Rect) {
+        self.hit_regions.clear();
+        if area.is_empty() {
+            return;
+        }
+
+        let bar = Rect { height: 1, ..area };
+        let content = Rect {
+            y: area.y + 1,
+            height: area.height - 1,
+            ..area
+        };
+
+        let widths: Vec<u16> = self.tabs.iter().map(Tab::width).collect();
+        let total = widths.iter().sum::<u16>() + widths.len().saturating_sub(1) as u16;
+        let overflow = total > bar.width && bar.width > 4;
+
+        let strip = if overflow {
+            let left = Rect { width: 2, ..bar };
+            let right = Rect {
+                x: bar.right() - 2,
+                width: 2,
+                ..bar
+            };
+            frame.render_widget(
+                Paragraph::new("◀ ").style(Style::default().fg(self.theme.accent)),
+                left,
+            );
+            frame.render_widget(
+                Paragraph::new(" ▶").style(Style::default().fg(self.theme.accent)),
+                right,
+            );
+            self.hit_regions.register(TabRegion::ScrollLeft, left);
+            self.hit_regions.register(TabRegion::ScrollRight, right);
+            Rect {
+                x: bar.x + 2,
+                width: bar.width - 4,
+                ..bar
+            }
+        } else {
+            bar
+        };
+
+        let first = if overflow {
+            self.first_visible(&widths, strip.width)
+        } else {
+            0
+        };
+        self.scroll.set(first);
+        self.follow_active.set(false);
+
+        let mut x = strip.x;
+        for (index, tab) in self.tabs.iter().enumerate().skip(first) {
+            let width = widths[index];
+            if x + width > strip.right() {
+                break;
+            }
+            let active = index == self.active;
+            let style = self.tab_style(active);
+            let tab_area = Rect::new(x, strip.y, width, 1);
+
+            let mut spans = vec![Span::styled(tab.label(), style)];
+            if tab.closable {
+                spans.push(Span::styled(" ×", style.add_modifier(Modifier::DIM)));
+            }
+            spans.push(Span::styled(" ", style));
+            frame.render_widget(Paragraph::new(Line::from(spans)), tab_area);
+
+            self.hit_regions.register(TabRegion::Tab(index), tab_area);
+            if tab.closable {
+                let close = Rect::new(tab_area.right() - 2, strip.y, 1, 1);
+                self.hit_regions.register(TabRegion::Close(index), close);
+            }
+
+            x += width;
+            if x < strip.right() {
+                frame.render_widget(
+                    Paragraph::new("│").style(self.style(Slot::Separator, Variant::Normal)),
+                    Rect::new(x, strip.y, 1, 1),
+                );
+                x += 1;
+            }
+        }
+
+        if let Some(tab) = self.tabs.get(self.active) {
+            tab.content.render_in(frame, content);
+        }
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.theme = theme.clone();
+        for tab in &mut self.tabs {
+            tab.content.set_theme(theme);
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> Option<Self::Message> {
+        match event {
+            Event::Key(KeyEvent {
+                code, modifiers, ..
+            }) if modifiers.contains(KeyModifiers::ALT) => {
+                let shift = modifiers.contains(KeyModifiers::SHIFT);
+                let last = self.tabs.len().saturating_sub(1);
+                match code {
+                    KeyCode::Left if shift && self.active > 0 => Some(TabsMessage::Move {
+                        from: self.active,
+                        to: self.active - 1,
+                    }),
+                    KeyCode::Right if shift && self.active < last => Some(TabsMessage::Move {
+                        from: self.active,
+                        to: self.active + 1,
+                    }),
+                    KeyCode::Left if !shift => Some(TabsMessage::Prev),
+                    KeyCode::Right if !shift => Some(TabsMessage::Next),
+                    KeyCode::Char('w') | KeyCode::Char('W') => {
+                        Some(TabsMessage::Close(self.active))
+                    }
+                    KeyCode::Char(digit @ '1'..='9') => {
+                        Some(TabsMessage::Select(*digit as usize - '1' as usize))
+                    }
+                    _ => self.forward(event),
+                }
+            }
+            Event::Mouse(mouse_event) => {
+                let hit = self.hit_regions.hit(mouse_event.column, mouse_event.row);
+                match (mouse_event.kind, hit) {
+                    (MouseEventKind::Down(MouseButton::Left), Some(TabRegion::Close(index))) => {
+                        Some(TabsMessage::Close(index))
+                    }
+                    (MouseEventKind::Down(MouseButton::Left), Some(TabRegion::Tab(index))) => {
+                        Some(TabsMessage::DragStart(index))
+                    }
+                    (MouseEventKind::Down(MouseButton::Left), Some(TabRegion::ScrollLeft)) => {
+                        Some(TabsMessage::ScrollLeft)
+                    }
+                    (MouseEventKind::Down(MouseButton::Left), Some(TabRegion::ScrollRight)) => {
+                        Some(TabsMessage::ScrollRight)
+                    }
+                    (MouseEventKind::Drag(MouseButton::Left), Some(TabRegion::Tab(to)))
+                    | (MouseEventKind::Drag(MouseButton::Left), Some(TabRegion::Close(to))) => {
+                        match self.dragging {
+                            Some(from) if from != to => Some(TabsMessage::Move { from, to }),
+                            Some(_) => None,
+                            None => self.forward(event),
+                        }
+                    }
+                    (MouseEventKind::Up(_), _) if self.dragging.is_some() => {
+                        Some(TabsMessage::DragEnd)
+                    }
+                    (MouseEventKind::ScrollUp, Some(_)) => Some(TabsMessage::Prev),
+                    (MouseEventKind::ScrollDown, Some(_)) => Some(TabsMessage::Next),
+                    (_, Some(_)) => None,
+                    (_, None) => self.forward(event),
+                }
+            }
+            _ => self.forward(event),
+        }
+    }
+}
+
+impl<C: Component> Tabs<C> {
+    /// Pass an event to the active tab's component
+    fn forward(&mut self, event: &Event) -> Option<TabsMessage<C::Message>> {
+        self.tabs
+            .get_mut(self.active)?
+            .content
+            .handle_event(event)
+            .map(TabsMessage::Child)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::MouseEvent;
+    use ratatui::backend::TestBackend;
+
+    /// Minimal child that counts key presses
+    struct Counter {
+        count: usize,
+    }
+
+    impl Component for Counter {
+        type Message = ();
+
+        fn update(&mut self, _msg: ()) -> Option<Action> {
+            self.count += 1;
+            None
+        }
+
+        fn render(&self, frame: &mut Frame<'_>) {
+            let area = frame.area();
+            self.render_in(frame, area);
+        }
+
+        fn render_in(&self, frame: &mut Frame<'_>, area: Rect) {
+            frame.render_widget(Paragraph::new(self.count.to_string()), area);
+        }
+
+        fn handle_event(&mut self, event: &Event) -> Option<()> {
+            matches!(event, Event::Key(_)).then_some(())
+        }
+    }
+
+    fn tabs(titles: &[&str]) -> Tabs<Counter> {
+        titles.iter().fold(Tabs::new(), |tabs, title| {
+            tabs.tab(Tab::new(*title, Counter { count: 0 }))
+        })
+    }
+
+    fn send(tabs: &mut Tabs<Counter>, event: Event) {
+        if let Some(msg) = tabs.handle_event(&event) {
+            tabs.update(msg);
+        }
+    }
+
+    fn alt(code: KeyCode, modifiers: KeyModifiers) -> Event {
+        Event::Key(KeyEvent::new(code, modifiers | KeyModifiers::ALT))
+    }
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> Event {
+        Event::Mouse(MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        })
+    }
+
+    fn draw(tabs: &Tabs<Counter>, width: u16) -> ratatui::buffer::Buffer {
+        let mut terminal = ratatui::Terminal::new(TestBackend::new(width, 5)).unwrap();
+        terminal.draw(|frame| tabs.render(frame)).unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    fn titles(tabs: &Tabs<Counter>) -> Vec<&str> {
+        tabs.tabs().iter().map(Tab::title).collect()
+    }
+
+    #[test]
+    fn test_keys_switch_and_forward_to_active_child() {
+        let mut tabs = tabs(&["a", "b"]);
+        send(&mut tabs, alt(KeyCode::Right, KeyModifiers::NONE));
+        assert_eq!(tabs.active(), 1);
+
+        send(&mut tabs, Event::Key(KeyEvent::from(KeyCode::Char('x'))));
+        assert_eq!(tabs.tabs()[1].content().count, 1);
+        assert_eq!(tabs.tabs()[0].content().count, 0);
+
+        let buffer = draw(&tabs, 20);
+        assert_eq!(buffer[(0, 1)].symbol(), "1");
+    }
+
+    #[test]
+    fn test_keyboard_reorder_keeps_tab_active() {
+        let mut tabs = tabs(&["a", "b", "c"]);
+        send(&mut tabs, alt(KeyCode::Right, KeyModifiers::SHIFT));
+        assert_eq!(titles(&tabs), ["b", "a", "c"]);
+        assert_eq!(tabs.active(), 1);
+    }
+
+    #[test]
+    fn test_dirty_tab_waits_for_confirmation() {
+        let mut tabs = tabs(&["a", "b"]);
+        tabs.tab_mut(0).unwrap().set_dirty(true);
+
+        tabs.update(TabsMessage::Close(0));
+        assert_eq!(tabs.pending_close(), Some(0));
+        tabs.update(TabsMessage::CancelClose);
+        assert_eq!(tabs.len(), 2);
+
+        tabs.update(TabsMessage::Close(0));
+        tabs.update(TabsMessage::ConfirmClose);
+        assert_eq!(titles(&tabs), ["b"]);
+
+        tabs.update(TabsMessage::Close(0));
+        assert!(tabs.is_empty());
+    }
+
+    #[test]
+    fn test_mouse_select_close_and_drag() {
+        let mut tabs = tabs(&["one", "two", "three"]);
+        draw(&tabs, 40);
+
+        let two = tabs.hit_regions.area(TabRegion::Tab(1)).unwrap();
+        send(
+            &mut tabs,
+            mouse(MouseEventKind::Down(MouseButton::Left), two.x, 0),
+        );
+        assert_eq!(tabs.active(), 1);
+
+        let three = tabs.hit_regions.area(TabRegion::Tab(2)).unwrap();
+        send(
+            &mut tabs,
+            mouse(MouseEventKind::Drag(MouseButton::Left), three.x, 0),
+        );
+        send(
+            &mut tabs,
+            mouse(MouseEventKind::Up(MouseButton::Left), three.x, 0),
+        );
+        assert_eq!(titles(&tabs), ["one", "three", "two"]);
+        assert_eq!(tabs.active(), 2);
+
+        draw(&tabs, 40);
+        let close = tabs.hit_regions.area(TabRegion::Close(0)).unwrap();
+        send(
+            &mut tabs,
+            mouse(MouseEventKind::Down(MouseButton::Left), close.x, 0),
+        );
+        assert_eq!(titles(&tabs), ["three", "two"]);
+    }
+
+    #[test]
+    fn test_overflow_shows_arrows_and_keeps_active_visible() {
+        let mut tabs = tabs(&["alpha", "bravo", "charlie", "delta", "echo"]);
+        tabs.select(4);
+        let buffer = draw(&tabs, 24);
+        assert_eq!(buffer[(0, 0)].symbol(), "◀");
+        assert_eq!(buffer[(23, 0)].symbol(), "▶");
+        assert!(tabs.hit_regions.area(TabRegion::Tab(4)).is_some());
+        assert!(tabs.hit_regions.area(TabRegion::Tab(0)).is_none());
+
+        send(
+            &mut tabs,
+            mouse(MouseEventKind::Down(MouseButton::Left), 0, 0),
+        );
+        draw(&tabs, 24);
+        assert_eq!(tabs.active(), 4);
+        assert!(tabs.scroll.get() < 4);
+    }
+    #[test]
+    fn test_styles_override_tab_slots() {
+        let theme = Theme::default();
+        let tabs = tabs(&["a", "b"])
+            .styles(StyleSheet::new().slot(Slot::TabActive, Style::default().bg(Color::Yellow)));
+        let buffer = draw(&tabs, 20);
+        assert_eq!(buffer[(0, 0)].bg, Color::Yellow);
+        assert_eq!(buffer[(0, 0)].fg, theme.background);
+
+        let separator = (0..20).find(|&x| buffer[(x, 0)].symbol() == "│").unwrap();
+        assert_eq!(buffer[(separator, 0)].fg, theme.muted);
+    }
+}
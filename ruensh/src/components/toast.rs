@@ -0,0 +1,500 @@
+//! Toast notifications with queueing and auto-dismiss
+
+use super::hit_test::HitRegions;
+use super::Component;
+use super::overlay::{Layer, LayerResponse};
+use crate::events::Event;
+use crate::state::Action;
+use crate::style::{markup, Slot, StyleSheet, Theme, Variant};
+use crate::svg::{Transition, TransitionPresets};
+use crossterm::event::{KeyCode, KeyEvent, MouseEventKind};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Clear, Paragraph};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Message types for toasts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ToastMessage {
+    /// Advance timers and animations to the given time
+    Tick(Instant),
+    /// Dismiss the toast with the given id
+    Dismiss(u64),
+    /// Dismiss the newest visible toast
+    DismissLatest,
+}
+
+/// Severity of a notification, mapped to theme colours
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// Theme colour used for the toast border and icon
+    pub fn color(&self, theme: &Theme) -> Color {
+        match self {
+            Severity::Info => theme.accent,
+            Severity::Success => theme.success,
+            Severity::Warning => theme.warning,
+            Severity::Error => theme.error,
+        }
+    }
+
+    /// Icon shown before the message
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Severity::Info => "ℹ",
+            Severity::Success => "✔",
+            Severity::Warning => "⚠",
+            Severity::Error => "✖",
+        }
+    }
+}
+
+/// Corner of the screen toasts stack from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastPosition {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// A single notification
+pub struct Toast {
+    id: u64,
+    message: String,
+    severity: Severity,
+    duration: Option<Duration>,
+    shown_at: Option<Instant>,
+    transition: Transition<f32>,
+}
+
+impl Toast {
+    /// Create an info toast with the default duration
+    pub fn new(message: impl Into<String>) -> Self {
+        Toast {
+            id: 0,
+            message: message.into(),
+            severity: Severity::Info,
+            duration: Some(Duration::from_secs(3)),
+            shown_at: None,
+            transition: TransitionPresets::fade_in(Duration::ZERO).with_loop(Some(1)),
+        }
+    }
+
+    /// Set severity level
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Set how long the toast stays visible (None = until dismissed)
+    pub fn duration(mut self, duration: Option<Duration>) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Get toast id
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Get toast message
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Check whether the display time has run out
+    fn is_expired(&self, now: Instant) -> bool {
+        match (self.shown_at, self.duration) {
+            (Some(shown_at), Some(duration)) => now.saturating_duration_since(shown_at) >= duration,
+            _ => false,
+        }
+    }
+}
+
+/// Stack of transient notifications
+pub struct Toasts {
+    queue: VecDeque<Toast>,
+    next_id: u64,
+    max_visible: usize,
+    position: ToastPosition,
+    width: u16,
+    transition_duration: Duration,
+    dismiss_key: KeyCode,
+    theme: Theme,
+    styles: StyleSheet,
+    /// Parse messages as rich text markup
+    markup: bool,
+    /// Toast areas recorded during the last render
+    hit_regions: HitRegions<u64>,
+}
+
+impl Toasts {
+    /// Create an empty toast stack
+    pub fn new() -> Self {
+        Toasts {
+            queue: VecDeque::new(),
+            next_id: 1,
+            max_visible: 3,
+            position: ToastPosition::BottomRight,
+            width: 40,
+            transition_duration: Duration::from_millis(200),
+            dismiss_key: KeyCode::Esc,
+            theme: Theme::default(),
+            styles: StyleSheet::new(),
+            markup: false,
+            hit_regions: HitRegions::new(),
+        }
+    }
+
+    /// Set the corner toasts stack from
+    pub fn position(mut self, position: ToastPosition) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Set how many toasts are shown at once; the rest wait in the queue
+    pub fn max_visible(mut self, max_visible: usize) -> Self {
+        self.max_visible = max_visible.max(1);
+        self
+    }
+
+    /// Set toast width in cells
+    pub fn width(mut self, width: u16) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Set the slide/fade-in duration
+    pub fn transition_duration(mut self, duration: Duration) -> Self {
+        self.transition_duration = duration;
+        self
+    }
+
+    /// Set the key that dismisses the newest toast
+    pub fn dismiss_key(mut self, key: KeyCode) -> Self {
+        self.dismiss_key = key;
+        self
+    }
+
+    /// Set theme
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Override slot styles (`base`, `border`); borders keep the severity colour
+    pub fn styles(mut self, styles: StyleSheet) -> Self {
+        self.styles = styles;
+        self
+    }
+
+    /// Parse messages as rich text markup, e.g. `[bold]Saved[/] 3 files`
+    pub fn markup(mut self, markup: bool) -> Self {
+        self.markup = markup;
+        self
+    }
+
+    /// Queue a toast, returning its id
+    pub fn push(&mut self, mut toast: Toast) -> u64 {
+        toast.id = self.next_id;
+        self.next_id += 1;
+        let id = toast.id;
+        self.queue.push_back(toast);
+        self.reveal(Instant::now());
+        id
+    }
+
+    /// Queue an info toast
+    pub fn info(&mut self, message: impl Into<String>) -> u64 {
+        self.push(Toast::new(message).severity(Severity::Info))
+    }
+
+    /// Queue a success toast
+    pub fn success(&mut self, message: impl Into<String>) -> u64 {
+        self.push(Toast::new(message).severity(Severity::Success))
+    }
+
+    /// Queue a warning toast
+    pub fn warning(&mut self, message: impl Into<String>) -> u64 {
+        self.push(Toast::new(message).severity(Severity::Warning))
+    }
+
+    /// Queue an error toast
+    pub fn error(&mut self, message: impl Into<String>) -> u64 {
+        self.push(Toast::new(message).severity(Severity::Error))
+    }
+
+    /// Remove a toast by id
+    pub fn dismiss(&mut self, id: u64) {
+        self.queue.retain(|toast| toast.id != id);
+        self.reveal(Instant::now());
+    }
+
+    /// Remove all toasts
+    pub fn clear(&mut self) {
+        self.queue.clear();
+    }
+
+    /// Number of queued toasts, visible or waiting
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Check whether there are no toasts
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Toasts currently on screen, oldest first
+    pub fn visible(&self) -> impl Iterator<Item = &Toast> {
+        self.queue.iter().take(self.max_visible)
+    }
+
+    /// Expire timed-out toasts and advance animations
+    pub fn tick(&mut self, now: Instant) {
+        self.queue.retain(|toast| !toast.is_expired(now));
+        self.reveal(now);
+        for toast in self.queue.iter_mut().take(self.max_visible) {
+            toast.transition.update_at(now);
+        }
+    }
+
+    /// Start timers and entrance transitions for toasts that became visible
+    fn reveal(&mut self, now: Instant) {
+        let duration = self.transition_duration;
+        for toast in self.queue.iter_mut().take(self.max_visible) {
+            if toast.shown_at.is_none() {
+                toast.shown_at = Some(now);
+                toast.transition = TransitionPresets::fade_in(duration).with_loop(Some(1));
+                toast.transition.start_at(now);
+            }
+        }
+    }
+}
+
+impl Default for Toasts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl super::Component for Toasts {
+    type Message = ToastMessage;
+
+    fn update(&mut self, msg: Self::Message) -> Option<Action> {
+        match msg {
+            ToastMessage::Tick(now) => self.tick(now),
+            ToastMessage::Dismiss(id) => self.dismiss(id),
+            ToastMessage::DismissLatest => {
+                if let Some(id) = self.visible().last().map(|toast| toast.id) {
+                    self.dismiss(id);
+                }
+            }
+        }
+        None
+    }
+
+    fn render(&self, frame: &mut Frame<'_>) {
+        let area = frame.area();
+        self.render_in(frame, area);
+    }
+
+    fn render_in(&self, frame: &mut Frame<'_>, area: Rect) {
+        self.hit_regions.clear();
+
+        let width = self.width.min(area.width);
+        let height = 3;
+        let from_top = matches!(self.position, ToastPosition::TopLeft | ToastPosition::TopRight);
+        let from_left = matches!(self.position, ToastPosition::TopLeft | ToastPosition::BottomLeft);
+
+        for (index, toast) in self.visible().enumerate() {
+            let stacked = index as u16 * height;
+            if stacked + height > area.height {
+                break;
+            }
+
+            // Slide in from the nearest side while fading in
+            let progress = toast.transition.current_value.unwrap_or(1.0);
+            let slide = ((1.0 - progress) * width as f32).round() as u16;
+            let x = if from_left {
+                area.x
+            } else {
+                area.right() - width + slide
+            };
+            let y = if from_top {
+                area.y + stacked
+            } else {
+                area.bottom() - stacked - height
+            };
+            let toast_area = Rect {
+                x,
+                y,
+                width: width.saturating_sub(slide),
+                height,
+            }
+            .intersection(area);
+            if toast_area.is_empty() {
+                continue;
+            }
+
+            let color = toast.severity.color(&self.theme);
+            let mut style = self.styles.resolve(&self.theme, Slot::Base, Variant::Normal);
+            if progress < 0.5 {
+                style = style.add_modifier(Modifier::DIM);
+            }
+
+            let block = self.theme.border_style.apply(Block::default())
+                .border_style(self.styles.resolve(&self.theme, Slot::Border, Variant::Normal).fg(color))
+                .style(style);
+            let icon = Span::styled(format!("{} ", toast.severity.icon()), Style::default().fg(color));
+            let mut text = if self.markup {
+                markup(&toast.message, &self.theme)
+            } else {
+                Text::raw(toast.message.as_str())
+            };
+            if let Some(first) = text.lines.first_mut() {
+                first.spans.insert(0, icon);
+            }
+
+            frame.render_widget(Clear, toast_area);
+            frame.render_widget(Paragraph::new(text).block(block), toast_area);
+            self.hit_regions.register(toast.id, toast_area);
+        }
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.theme = theme.clone();
+    }
+
+    fn handle_event(&mut self, event: &Event) -> Option<Self::Message> {
+        if self.queue.is_empty() {
+            return None;
+        }
+        match event {
+            Event::Tick => Some(ToastMessage::Tick(Instant::now())),
+            Event::Key(KeyEvent { code, .. }) if *code == self.dismiss_key => {
+                Some(ToastMessage::DismissLatest)
+            }
+            Event::Mouse(mouse_event) => match mouse_event.kind {
+                MouseEventKind::Down(_) => self
+                    .hit_regions
+                    .hit(mouse_event.column, mouse_event.row)
+                    .map(ToastMessage::Dismiss),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl Layer for Toasts {
+    fn render_layer(&self, frame: &mut Frame<'_>, area: Rect) {
+        self.render_in(frame, area);
+    }
+
+    fn layer_event(&mut self, event: &Event) -> LayerResponse {
+        match self.handle_event(event) {
+            Some(ToastMessage::Tick(now)) => {
+                self.tick(now);
+                // Ticks are shared with the layers beneath
+                LayerResponse::Ignored
+            }
+            Some(msg) => {
+                self.update(msg);
+                LayerResponse::Consumed
+            }
+            None => LayerResponse::Ignored,
+        }
+    }
+
+    fn is_modal(&self) -> bool {
+        false
+    }
+
+    fn on_theme_change(&mut self, theme: &Theme) {
+        self.set_theme(theme);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::Component;
+    use crossterm::event::{KeyModifiers, MouseButton, MouseEvent};
+    use ratatui::backend::TestBackend;
+
+    fn draw(toasts: &Toasts) {
+        let mut terminal = ratatui::Terminal::new(TestBackend::new(80, 24)).unwrap();
+        terminal.draw(|frame| toasts.render(frame)).unwrap();
+    }
+
+    #[test]
+    fn test_toasts_expire_on_tick() {
+        let mut toasts = Toasts::new();
+        toasts.push(Toast::new("Saved").duration(Some(Duration::from_millis(100))));
+        toasts.push(Toast::new("Sticky").duration(None));
+        let start = toasts.queue[0].shown_at.unwrap();
+
+        toasts.update(ToastMessage::Tick(start + Duration::from_millis(50)));
+        assert_eq!(toasts.len(), 2);
+        toasts.update(ToastMessage::Tick(start + Duration::from_millis(150)));
+        assert_eq!(toasts.visible().map(Toast::message).collect::<Vec<_>>(), ["Sticky"]);
+    }
+
+    #[test]
+    fn test_queued_toasts_wait_for_a_free_slot() {
+        let mut toasts = Toasts::new().max_visible(1);
+        let first = toasts.success("Copied");
+        toasts.error("Build failed");
+        assert_eq!(toasts.visible().count(), 1);
+        assert!(toasts.queue[1].shown_at.is_none());
+
+        toasts.dismiss(first);
+        assert_eq!(toasts.visible().next().unwrap().message(), "Build failed");
+        assert!(toasts.queue[0].shown_at.is_some());
+    }
+
+    #[test]
+    fn test_dismiss_with_key_and_mouse() {
+        let mut toasts = Toasts::new().transition_duration(Duration::ZERO);
+        let first = toasts.info("one");
+        toasts.info("two");
+
+        let key = Event::Key(KeyEvent::from(KeyCode::Esc));
+        let msg = toasts.handle_event(&key).unwrap();
+        toasts.update(msg);
+        assert_eq!(toasts.visible().map(Toast::id).collect::<Vec<_>>(), [first]);
+
+        toasts.tick(Instant::now());
+        draw(&toasts);
+        let area = toasts.hit_regions.area(first).unwrap();
+        let click = Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: area.x + 1,
+            row: area.y + 1,
+            modifiers: KeyModifiers::NONE,
+        });
+        assert_eq!(toasts.handle_event(&click), Some(ToastMessage::Dismiss(first)));
+    }
+
+    #[test]
+    fn test_toasts_stack_from_corner() {
+        let mut toasts = Toasts::new()
+            .position(ToastPosition::TopLeft)
+            .transition_duration(Duration::ZERO);
+        let first = toasts.info("one");
+        let second = toasts.warning("two");
+        toasts.tick(Instant::now());
+        draw(&toasts);
+
+        assert_eq!(toasts.hit_regions.area(first), Some(Rect::new(0, 0, 40, 3)));
+        assert_eq!(toasts.hit_regions.area(second), Some(Rect::new(0, 3, 40, 3)));
+    }
+}
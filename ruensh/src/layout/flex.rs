@@ -0,0 +1,539 @@
+//! Flexbox-style layout tree
+//!
+//! A `Flex` node is either a leaf item or a container laying out its
+//! children along a main axis. Each node has a basis size plus grow/shrink
+//! factors that decide how free space is shared, and containers add gap,
+//! padding, alignment and wrapping. `compute` resolves the whole tree in one
+//! pass and returns the `Rect` of every keyed node. Optional nodes are left
+//! out of the result when their line cannot fit everyone's minimum size.
+
+use ratatui::layout::{Direction, Rect};
+use ratatui::widgets::Padding;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Distribution of free space along the main axis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Justify {
+    #[default]
+    Start,
+    Center,
+    End,
+    /// First and last item touch the edges, free space goes between items
+    SpaceBetween,
+}
+
+/// Placement of items on the cross axis
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Align {
+    Start,
+    Center,
+    End,
+    /// Fill the line unless the item has a fixed cross size
+    #[default]
+    Stretch,
+}
+
+/// Node in a flex layout tree
+#[derive(Debug, Clone)]
+pub struct Flex<K> {
+    key: Option<K>,
+    // Sizing inside the parent
+    basis: u16,
+    grow: f32,
+    shrink: f32,
+    min: u16,
+    max: u16,
+    cross_size: Option<u16>,
+    optional: bool,
+    // Container properties
+    direction: Direction,
+    gap: u16,
+    padding: Padding,
+    justify: Justify,
+    align: Align,
+    wrap: bool,
+    children: Vec<Flex<K>>,
+}
+
+impl<K: Clone + Eq + Hash> Flex<K> {
+    fn new(key: Option<K>, direction: Direction) -> Self {
+        Flex {
+            key,
+            basis: 0,
+            grow: 0.0,
+            shrink: 1.0,
+            min: 0,
+            max: u16::MAX,
+            cross_size: None,
+            optional: false,
+            direction,
+            gap: 0,
+            padding: Padding::ZERO,
+            justify: Justify::default(),
+            align: Align::default(),
+            wrap: false,
+            children: Vec::new(),
+        }
+    }
+
+    /// Create a leaf item identified by `key`
+    pub fn item(key: K) -> Self {
+        Self::new(Some(key), Direction::Horizontal)
+    }
+
+    /// Create a container laying out children left to right
+    pub fn horizontal() -> Self {
+        Self::new(None, Direction::Horizontal)
+    }
+
+    /// Create a container laying out children top to bottom
+    pub fn vertical() -> Self {
+        Self::new(None, Direction::Vertical)
+    }
+
+    /// Identify this node in the computed layout
+    pub fn key(mut self, key: K) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    /// Set the preferred size along the parent's main axis
+    pub fn basis(mut self, basis: u16) -> Self {
+        self.basis = basis;
+        self
+    }
+
+    /// Set the share of free space this node takes when there is room
+    pub fn grow(mut self, grow: f32) -> Self {
+        self.grow = grow.max(0.0);
+        self
+    }
+
+    /// Set how much this node gives up when space is short (default 1)
+    pub fn shrink(mut self, shrink: f32) -> Self {
+        self.shrink = shrink.max(0.0);
+        self
+    }
+
+    /// Set the minimum size along the parent's main axis
+    pub fn min(mut self, min: u16) -> Self {
+        self.min = min;
+        self
+    }
+
+    /// Set the maximum size along the parent's main axis
+    pub fn max(mut self, max: u16) -> Self {
+        self.max = max;
+        self
+    }
+
+    /// Set a fixed size on the parent's cross axis
+    pub fn cross_size(mut self, size: u16) -> Self {
+        self.cross_size = Some(size);
+        self
+    }
+
+    /// Hide this node when its line cannot fit every minimum size
+    ///
+    /// Optional nodes are dropped last to first; give them a `min` so
+    /// they disappear before squeezing the rest.
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+
+    /// Set spacing between children (and between wrapped lines)
+    pub fn gap(mut self, gap: u16) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Set inner padding
+    pub fn padding(mut self, padding: Padding) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Set main axis alignment
+    pub fn justify(mut self, justify: Justify) -> Self {
+        self.justify = justify;
+        self
+    }
+
+    /// Set cross axis alignment
+    pub fn align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Wrap children onto new lines when they don't fit
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Add a child node
+    pub fn child(mut self, child: Flex<K>) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Add several child nodes
+    pub fn children(mut self, children: impl IntoIterator<Item = Flex<K>>) -> Self {
+        self.children.extend(children);
+        self
+    }
+
+    /// Compute the area of every keyed node in the tree
+    pub fn compute(&self, area: Rect) -> HashMap<K, Rect> {
+        let mut areas = HashMap::new();
+        self.layout(area, &mut areas);
+        areas
+    }
+
+    /// Basis clamped to the min/max range
+    fn hypothetical(&self) -> u16 {
+        self.basis.clamp(self.min, self.max.max(self.min))
+    }
+
+    fn layout(&self, area: Rect, areas: &mut HashMap<K, Rect>) {
+        if let Some(key) = &self.key {
+            areas.insert(key.clone(), area);
+        }
+        if self.children.is_empty() {
+            return;
+        }
+
+        let inner = pad(area, self.padding);
+        let horizontal = self.direction == Direction::Horizontal;
+        let (main_start, main_len, cross_start, cross_len) = if horizontal {
+            (inner.x, inner.width, inner.y, inner.height)
+        } else {
+            (inner.y, inner.height, inner.x, inner.width)
+        };
+
+        let lines = self.lines(main_len);
+        let line_sizes = self.line_cross_sizes(&lines, cross_len);
+
+        let mut cross = cross_start;
+        for (line, line_cross) in lines.iter().zip(line_sizes) {
+            let line = self.fitting(line, main_len);
+            let gaps = self.gap.saturating_mul(line.len().saturating_sub(1) as u16);
+            let sizes = resolve(&line, main_len.saturating_sub(gaps));
+            let used = sizes.iter().sum::<u16>() + gaps;
+            let free = main_len.saturating_sub(used);
+
+            let (offset, spacing) = match self.justify {
+                Justify::Start => (0, 0),
+                Justify::Center => (free / 2, 0),
+                Justify::End => (free, 0),
+                Justify::SpaceBetween if line.len() > 1 => (0, free / (line.len() as u16 - 1)),
+                Justify::SpaceBetween => (0, 0),
+            };
+            // Cells left over from dividing space between items go to the first gaps
+            let mut leftover = match self.justify {
+                Justify::SpaceBetween if line.len() > 1 => free % (line.len() as u16 - 1),
+                _ => 0,
+            };
+
+            let mut main = main_start.saturating_add(offset);
+            for (child, size) in line.iter().zip(sizes) {
+                let child_cross = match (self.align, child.cross_size) {
+                    (Align::Stretch, None) => line_cross,
+                    (_, Some(fixed)) => fixed.min(line_cross),
+                    (_, None) => line_cross,
+                };
+                let cross_offset = match self.align {
+                    Align::Start | Align::Stretch => 0,
+                    Align::Center => (line_cross - child_cross) / 2,
+                    Align::End => line_cross - child_cross,
+                };
+
+                let rect = if horizontal {
+                    Rect::new(main, cross + cross_offset, size, child_cross)
+                } else {
+                    Rect::new(cross + cross_offset, main, child_cross, size)
+                };
+                child.layout(rect.intersection(inner), areas);
+
+                main = main.saturating_add(size).saturating_add(self.gap.saturating_add(spacing));
+                if leftover > 0 {
+                    main = main.saturating_add(1);
+                    leftover -= 1;
+                }
+            }
+            cross = cross.saturating_add(line_cross).saturating_add(self.gap);
+        }
+    }
+
+    /// Break children into lines that fit `main_len`
+    fn lines(&self, main_len: u16) -> Vec<Vec<&Flex<K>>> {
+        if !self.wrap {
+            return vec![self.children.iter().collect()];
+        }
+        let mut lines: Vec<Vec<&Flex<K>>> = Vec::new();
+        let mut current = Vec::new();
+        let mut used = 0u16;
+        for child in &self.children {
+            let size = child.hypothetical();
+            let needed = if current.is_empty() { size } else { used.saturating_add(self.gap).saturating_add(size) };
+            if !current.is_empty() && needed > main_len {
+                lines.push(std::mem::take(&mut current));
+                used = size;
+            } else {
+                used = needed;
+            }
+            current.push(child);
+        }
+        lines.push(current);
+        lines
+    }
+
+    /// Drop optional children, last first, until the minimum sizes fit
+    fn fitting<'a>(&self, line: &[&'a Flex<K>], main_len: u16) -> Vec<&'a Flex<K>> {
+        let mut line = line.to_vec();
+        loop {
+            let gaps = self.gap as u32 * line.len().saturating_sub(1) as u32;
+            let needed = gaps + line.iter().map(|child| child.min as u32).sum::<u32>();
+            if needed <= main_len as u32 {
+                return line;
+            }
+            match line.iter().rposition(|child| child.optional) {
+                Some(index) => {
+                    line.remove(index);
+                }
+                None => return line,
+            }
+        }
+    }
+
+    /// Cross size of each line
+    ///
+    /// A single line fills the container. Wrapped lines take the largest
+    /// fixed cross size of their items, or share the remaining space.
+    fn line_cross_sizes(&self, lines: &[Vec<&Flex<K>>], cross_len: u16) -> Vec<u16> {
+        if lines.len() == 1 {
+            return vec![cross_len];
+        }
+        let fixed: Vec<Option<u16>> = lines
+            .iter()
+            .map(|line| line.iter().filter_map(|child| child.cross_size).max())
+            .collect();
+        let gaps = self.gap.saturating_mul(lines.len() as u16 - 1);
+        let taken: u16 = fixed.iter().flatten().sum();
+        let flexible = fixed.iter().filter(|size| size.is_none()).count() as u16;
+        let share = cross_len
+            .saturating_sub(gaps)
+            .saturating_sub(taken)
+            .checked_div(flexible)
+            .unwrap_or(0);
+        fixed.into_iter().map(|size| size.unwrap_or(share)).collect()
+    }
+}
+
+/// Resolve main sizes of one line so they fill `available`
+///
+/// Free space is shared by grow factors and missing space is taken by
+/// shrink factors weighted by size. Items hitting their min or max are
+/// frozen and the rest is redistributed among the others.
+fn resolve<K: Clone + Eq + Hash>(items: &[&Flex<K>], available: u16) -> Vec<u16> {
+    let mut sizes: Vec<u16> = items.iter().map(|item| item.hypothetical()).collect();
+    let mut frozen = vec![false; items.len()];
+
+    loop {
+        let used: i32 = sizes.iter().map(|&size| size as i32).sum();
+        let free = available as i32 - used;
+        if free == 0 {
+            break;
+        }
+        let weights: Vec<f32> = items
+            .iter()
+            .zip(&sizes)
+            .zip(&frozen)
+            .map(|((item, &size), &frozen)| match (frozen, free > 0) {
+                (true, _) => 0.0,
+                (false, true) => item.grow,
+                (false, false) => item.shrink * size as f32,
+            })
+            .collect();
+        let total: f32 = weights.iter().sum();
+        if total <= 0.0 {
+            break;
+        }
+
+        let amount = free.abs();
+        let mut shares: Vec<i32> = weights
+            .iter()
+            .map(|weight| (amount as f32 * weight / total).floor() as i32)
+            .collect();
+        let mut remainder = amount - shares.iter().sum::<i32>();
+        for (share, weight) in shares.iter_mut().zip(&weights) {
+            if remainder == 0 {
+                break;
+            }
+            if *weight > 0.0 {
+                *share += 1;
+                remainder -= 1;
+            }
+        }
+
+        let mut clamped = false;
+        for (index, item) in items.iter().enumerate() {
+            if weights[index] <= 0.0 {
+                continue;
+            }
+            let target = sizes[index] as i32 + free.signum() * shares[index];
+            let bounded = target.clamp(item.min as i32, item.max.max(item.min) as i32);
+            if bounded != target {
+                frozen[index] = true;
+                clamped = true;
+            }
+            sizes[index] = bounded as u16;
+        }
+        if !clamped {
+            break;
+        }
+    }
+    sizes
+}
+
+/// Shrink `area` by `padding`
+fn pad(area: Rect, padding: Padding) -> Rect {
+    let horizontal = padding.left.saturating_add(padding.right);
+    let vertical = padding.top.saturating_add(padding.bottom);
+    Rect {
+        x: area.x.saturating_add(padding.left.min(area.width)),
+        y: area.y.saturating_add(padding.top.min(area.height)),
+        width: area.width.saturating_sub(horizontal),
+        height: area.height.saturating_sub(vertical),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grow_shares_free_space_after_basis() {
+        let layout = Flex::horizontal()
+            .child(Flex::item("sidebar").basis(20))
+            .child(Flex::item("main").grow(2.0))
+            .child(Flex::item("aside").grow(1.0));
+        let areas = layout.compute(Rect::new(0, 0, 80, 10));
+        assert_eq!(areas["sidebar"], Rect::new(0, 0, 20, 10));
+        assert_eq!(areas["main"], Rect::new(20, 0, 40, 10));
+        assert_eq!(areas["aside"], Rect::new(60, 0, 20, 10));
+    }
+
+    #[test]
+    fn test_max_freezes_and_redistributes() {
+        let layout = Flex::horizontal()
+            .gap(2)
+            .child(Flex::item("a").grow(1.0).max(10))
+            .child(Flex::item("b").grow(1.0));
+        let areas = layout.compute(Rect::new(0, 0, 52, 1));
+        assert_eq!(areas["a"].width, 10);
+        assert_eq!(areas["b"], Rect::new(12, 0, 40, 1));
+    }
+
+    #[test]
+    fn test_shrink_respects_min() {
+        let layout = Flex::vertical()
+            .child(Flex::item("header").basis(10).min(8))
+            .child(Flex::item("body").basis(30));
+        let areas = layout.compute(Rect::new(0, 0, 10, 20));
+        assert_eq!(areas["header"].height, 8);
+        assert_eq!(areas["body"], Rect::new(0, 8, 10, 12));
+    }
+
+    #[test]
+    fn test_justify_and_align() {
+        let row = |justify| {
+            Flex::horizontal()
+                .justify(justify)
+                .align(Align::Center)
+                .child(Flex::item(1).basis(4).cross_size(2))
+                .child(Flex::item(2).basis(4).cross_size(2))
+                .compute(Rect::new(0, 0, 20, 6))
+        };
+        let areas = row(Justify::Center);
+        assert_eq!(areas[&1], Rect::new(6, 2, 4, 2));
+        assert_eq!(areas[&2], Rect::new(10, 2, 4, 2));
+
+        let areas = row(Justify::End);
+        assert_eq!(areas[&2].right(), 20);
+
+        let areas = row(Justify::SpaceBetween);
+        assert_eq!(areas[&1].x, 0);
+        assert_eq!(areas[&2].x, 16);
+    }
+
+    #[test]
+    fn test_wrap_moves_items_to_new_lines() {
+        let layout = Flex::horizontal()
+            .wrap(true)
+            .gap(1)
+            .children((0..5).map(|i| Flex::item(i).basis(8).cross_size(3)));
+        let areas = layout.compute(Rect::new(0, 0, 26, 10));
+        assert_eq!(areas[&0], Rect::new(0, 0, 8, 3));
+        assert_eq!(areas[&2], Rect::new(18, 0, 8, 3));
+        assert_eq!(areas[&3], Rect::new(0, 4, 8, 3));
+    }
+
+    #[test]
+    fn test_huge_gap_saturates() {
+        let children = || (0..3).map(|i| Flex::item(i).basis(8));
+        let areas = Flex::horizontal()
+            .gap(u16::MAX - 1)
+            .justify(Justify::SpaceBetween)
+            .children(children())
+            .compute(Rect::new(0, 0, 200, 1));
+        assert_eq!(areas[&0].x, 0);
+
+        let areas = Flex::horizontal()
+            .wrap(true)
+            .gap(u16::MAX - 1)
+            .children(children())
+            .compute(Rect::new(0, 0, 200, 10));
+        assert_eq!((areas[&0].x, areas[&0].width), (0, 8));
+    }
+
+    #[test]
+    fn test_optional_items_hide_when_space_is_short() {
+        let layout = Flex::horizontal()
+            .child(Flex::item("nav").basis(20).min(15).optional())
+            .child(Flex::item("main").grow(1.0).min(60))
+            .child(Flex::item("aside").basis(30).min(20).optional());
+
+        let areas = layout.compute(Rect::new(0, 0, 120, 1));
+        assert_eq!(areas.len(), 3);
+
+        let areas = layout.compute(Rect::new(0, 0, 80, 1));
+        assert!(!areas.contains_key("aside"));
+        assert_eq!(areas["nav"].width, 20);
+        assert_eq!(areas["main"], Rect::new(20, 0, 60, 1));
+
+        let areas = layout.compute(Rect::new(0, 0, 70, 1));
+        assert_eq!(areas.len(), 1);
+        assert_eq!(areas["main"].width, 70);
+    }
+
+    #[test]
+    fn test_nested_containers_with_padding() {
+        let layout = Flex::vertical()
+            .padding(Padding::uniform(1))
+            .child(Flex::item("title").basis(1))
+            .child(
+                Flex::horizontal()
+                    .key("body")
+                    .grow(1.0)
+                    .child(Flex::item("left").grow(1.0))
+                    .child(Flex::item("right").grow(1.0)),
+            );
+        let areas = layout.compute(Rect::new(0, 0, 42, 12));
+        assert_eq!(areas["title"], Rect::new(1, 1, 40, 1));
+        assert_eq!(areas["body"], Rect::new(1, 2, 40, 9));
+        assert_eq!(areas["right"], Rect::new(21, 2, 20, 9));
+    }
+}
@@ -0,0 +1,277 @@
+//! Two-dimensional grid layout
+//!
+//! Rows and columns are sized with `layout::Constraint`s, then named areas
+//! are placed on the grid, each spanning one or more cells. Areas can be
+//! placed by index or described with a CSS `grid-template-areas` style
+//! template.
+
+use super::Constraint;
+use ratatui::layout::Rect;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Cell range covered by a named area
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridArea {
+    pub row: usize,
+    pub column: usize,
+    pub row_span: usize,
+    pub column_span: usize,
+}
+
+impl GridArea {
+    /// Area covering a single cell
+    pub fn cell(row: usize, column: usize) -> Self {
+        GridArea {
+            row,
+            column,
+            row_span: 1,
+            column_span: 1,
+        }
+    }
+
+    /// Span several rows and columns from the starting cell
+    pub fn span(mut self, rows: usize, columns: usize) -> Self {
+        self.row_span = rows.max(1);
+        self.column_span = columns.max(1);
+        self
+    }
+}
+
+/// Error from placing areas on a grid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    /// Template does not have one row per grid row and one name per column
+    TemplateSize { rows: usize, columns: usize },
+    /// Cells sharing a name do not form a rectangle
+    NotRectangular(String),
+    /// Area extends past the last row or column
+    OutOfBounds(String),
+    /// Area spans zero rows or columns
+    EmptySpan(String),
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::TemplateSize { rows, columns } => {
+                write!(f, "template must be {} rows of {} names", rows, columns)
+            }
+            GridError::NotRectangular(name) => write!(f, "grid area '{}' is not a rectangle", name),
+            GridError::OutOfBounds(name) => write!(f, "grid area '{}' is outside the grid", name),
+            GridError::EmptySpan(name) => write!(f, "grid area '{}' spans no cells", name),
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+/// Grid layout builder
+#[derive(Debug, Clone)]
+pub struct Grid {
+    rows: Vec<Constraint>,
+    columns: Vec<Constraint>,
+    row_gap: u16,
+    column_gap: u16,
+    areas: Vec<(String, GridArea)>,
+}
+
+impl Grid {
+    /// Create a grid with the given row and column sizes
+    pub fn new(rows: impl Into<Vec<Constraint>>, columns: impl Into<Vec<Constraint>>) -> Self {
+        Grid {
+            rows: rows.into(),
+            columns: columns.into(),
+            row_gap: 0,
+            column_gap: 0,
+            areas: Vec::new(),
+        }
+    }
+
+    /// Set gutters between rows and between columns
+    pub fn gutter(mut self, row_gap: u16, column_gap: u16) -> Self {
+        self.row_gap = row_gap;
+        self.column_gap = column_gap;
+        self
+    }
+
+    /// Place a named area, replacing any area with the same name
+    pub fn area(mut self, name: impl Into<String>, area: GridArea) -> Result<Self, GridError> {
+        let name = name.into();
+        if area.row_span == 0 || area.column_span == 0 {
+            return Err(GridError::EmptySpan(name));
+        }
+        if area.row + area.row_span > self.rows.len()
+            || area.column + area.column_span > self.columns.len()
+        {
+            return Err(GridError::OutOfBounds(name));
+        }
+        self.areas.retain(|(existing, _)| *existing != name);
+        self.areas.push((name, area));
+        Ok(self)
+    }
+
+    /// Place areas from a template with one string per row
+    ///
+    /// Names are separated by whitespace and `.` marks an empty cell:
+    ///
+    /// ```
+    /// # use ruensh::layout::{Constraint, Grid};
+    /// let grid = Grid::new(
+    ///     [Constraint::Length(3), Constraint::Min(0)],
+    ///     [Constraint::Length(20), Constraint::Min(0)],
+    /// )
+    /// .template(["header header", "nav main"])
+    /// .unwrap();
+    /// ```
+    pub fn template<'a>(mut self, rows: impl IntoIterator<Item = &'a str>) -> Result<Self, GridError> {
+        let cells: Vec<Vec<&str>> = rows
+            .into_iter()
+            .map(|row| row.split_whitespace().collect())
+            .collect();
+        let size_error = GridError::TemplateSize {
+            rows: self.rows.len(),
+            columns: self.columns.len(),
+        };
+        if cells.len() != self.rows.len() || cells.iter().any(|row| row.len() != self.columns.len()) {
+            return Err(size_error);
+        }
+
+        // Bounding box of each name, in order of first appearance
+        let mut bounds: Vec<(&str, GridArea)> = Vec::new();
+        for (row, names) in cells.iter().enumerate() {
+            for (column, &name) in names.iter().enumerate() {
+                if name == "." {
+                    continue;
+                }
+                match bounds.iter_mut().find(|(existing, _)| *existing == name) {
+                    Some((_, area)) => {
+                        let last_row = (area.row + area.row_span).max(row + 1);
+                        let last_column = (area.column + area.column_span).max(column + 1);
+                        area.column = area.column.min(column);
+                        area.row_span = last_row - area.row;
+                        area.column_span = last_column - area.column;
+                    }
+                    None => bounds.push((name, GridArea::cell(row, column))),
+                }
+            }
+        }
+
+        for (name, area) in bounds {
+            let filled = (area.row..area.row + area.row_span).all(|row| {
+                (area.column..area.column + area.column_span).all(|column| cells[row][column] == name)
+            });
+            if !filled {
+                return Err(GridError::NotRectangular(name.to_string()));
+            }
+            self = self.area(name, area)?;
+        }
+        Ok(self)
+    }
+
+    /// Resolve every named area within `area`
+    pub fn split(&self, area: Rect) -> HashMap<String, Rect> {
+        let rows = tracks(&self.rows, self.row_gap, area, ratatui::layout::Direction::Vertical);
+        let columns = tracks(
+            &self.columns,
+            self.column_gap,
+            area,
+            ratatui::layout::Direction::Horizontal,
+        );
+
+        self.areas
+            .iter()
+            .map(|(name, cell)| {
+                let first_row = rows[cell.row];
+                let last_row = rows[cell.row + cell.row_span - 1];
+                let first_column = columns[cell.column];
+                let last_column = columns[cell.column + cell.column_span - 1];
+                let rect = Rect {
+                    x: first_column.x,
+                    y: first_row.y,
+                    width: last_column.right().saturating_sub(first_column.x),
+                    height: last_row.bottom().saturating_sub(first_row.y),
+                };
+                (name.clone(), rect)
+            })
+            .collect()
+    }
+}
+
+/// Split `area` into tracks along one axis
+fn tracks(
+    constraints: &[Constraint],
+    gap: u16,
+    area: Rect,
+    direction: ratatui::layout::Direction,
+) -> Vec<Rect> {
+    let constraints: Vec<_> = constraints
+        .iter()
+        .map(|c| c.to_ratatui_constraint())
+        .collect();
+
+    ratatui::layout::Layout::default()
+        .direction(direction)
+        .constraints(constraints)
+        .spacing(gap)
+        .split(area)
+        .to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dashboard() -> Grid {
+        Grid::new(
+            [Constraint::Length(3), Constraint::Min(0), Constraint::Length(1)],
+            [Constraint::Length(20), Constraint::Min(0)],
+        )
+    }
+
+    #[test]
+    fn test_spans_cover_gutters() {
+        let grid = dashboard()
+            .gutter(1, 2)
+            .area("header", GridArea::cell(0, 0).span(1, 2))
+            .unwrap()
+            .area("nav", GridArea::cell(1, 0).span(2, 1))
+            .unwrap()
+            .area("main", GridArea::cell(1, 1))
+            .unwrap();
+        let areas = grid.split(Rect::new(0, 0, 80, 24));
+        assert_eq!(areas["header"], Rect::new(0, 0, 80, 3));
+        assert_eq!(areas["nav"], Rect::new(0, 4, 20, 20));
+        assert_eq!(areas["main"], Rect::new(22, 4, 58, 18));
+    }
+
+    #[test]
+    fn test_template_areas() {
+        let grid = dashboard()
+            .template(["header header", "nav main", "status status"])
+            .unwrap();
+        let areas = grid.split(Rect::new(0, 0, 80, 24));
+        assert_eq!(areas["header"], Rect::new(0, 0, 80, 3));
+        assert_eq!(areas["nav"], Rect::new(0, 3, 20, 20));
+        assert_eq!(areas["main"], Rect::new(20, 3, 60, 20));
+        assert_eq!(areas["status"], Rect::new(0, 23, 80, 1));
+    }
+
+    #[test]
+    fn test_template_errors() {
+        assert_eq!(
+            dashboard().template(["a a", "b"]).unwrap_err(),
+            GridError::TemplateSize { rows: 3, columns: 2 }
+        );
+        assert_eq!(
+            dashboard().template(["a a", "a .", "b b"]).unwrap_err(),
+            GridError::NotRectangular("a".to_string())
+        );
+        assert_eq!(
+            dashboard().area("x", GridArea::cell(2, 1).span(2, 1)).unwrap_err(),
+            GridError::OutOfBounds("x".to_string())
+        );
+        let empty = GridArea { row_span: 0, ..GridArea::cell(0, 0) };
+        assert_eq!(dashboard().area("x", empty).unwrap_err(), GridError::EmptySpan("x".to_string()));
+    }
+}
@@ -1,7 +1,15 @@
 //! Layout system for composing components
 
+pub mod flex;
+pub mod grid;
+pub mod responsive;
+
 use ratatui::prelude::*;
 
+pub use flex::{Align, Flex, Justify};
+pub use grid::{Grid, GridArea, GridError};
+pub use responsive::Breakpoints;
+
 /// Constraint for layout sizing
 #[derive(Debug, Clone, Copy)]
 pub enum Constraint {
@@ -66,8 +74,24 @@ impl Layout {
 
         ratatui::layout::Layout::default()
             .direction(self.direction)
+            .vertical_margin(self.margin.0)
+            .horizontal_margin(self.margin.1)
             .constraints(constraints)
             .split(area)
             .to_vec()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_applies_margin() {
+        let areas = Layout::vertical([Constraint::Length(2), Constraint::Min(0)])
+            .with_margin(1, 2)
+            .split(Rect::new(0, 0, 20, 10));
+        assert_eq!(areas[0], Rect::new(2, 1, 16, 2));
+        assert_eq!(areas[1], Rect::new(2, 3, 16, 6));
+    }
+}
@@ -0,0 +1,142 @@
+//! Breakpoint-based layout selection
+//!
+//! `Breakpoints` holds one value (usually a `Flex` tree or a `Grid`) per
+//! minimum terminal width and picks the widest one that fits. Feed it
+//! `Event::Resize` to keep the current choice up to date, or let
+//! `compute`/`split` pick from the area being laid out.
+
+use super::{Flex, Grid};
+use crate::events::Event;
+use ratatui::layout::Rect;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Values selected by terminal width
+#[derive(Debug, Clone)]
+pub struct Breakpoints<T> {
+    /// Sorted by minimum width; the first entry starts at 0
+    entries: Vec<(u16, T)>,
+    current: usize,
+}
+
+impl<T> Breakpoints<T> {
+    /// Create breakpoints with the value used at any width
+    pub fn new(base: T) -> Self {
+        Breakpoints {
+            entries: vec![(0, base)],
+            current: 0,
+        }
+    }
+
+    /// Use `value` from `min_width` columns upwards
+    pub fn at(mut self, min_width: u16, value: T) -> Self {
+        match self.entries.binary_search_by_key(&min_width, |(width, _)| *width) {
+            Ok(index) => self.entries[index].1 = value,
+            Err(index) => self.entries.insert(index, (min_width, value)),
+        }
+        self
+    }
+
+    /// Index of the entry for the given width
+    fn index_for(&self, width: u16) -> usize {
+        self.entries
+            .iter()
+            .rposition(|(min_width, _)| *min_width <= width)
+            .unwrap_or(0)
+    }
+
+    /// Get the value for the given width
+    pub fn select(&self, width: u16) -> &T {
+        &self.entries[self.index_for(width)].1
+    }
+
+    /// Get the value for the last known width
+    pub fn current(&self) -> &T {
+        &self.entries[self.current].1
+    }
+
+    /// Minimum width of the current value
+    pub fn current_min_width(&self) -> u16 {
+        self.entries[self.current].0
+    }
+
+    /// Re-evaluate for a new width; returns true if the selection changed
+    pub fn resize(&mut self, width: u16) -> bool {
+        let index = self.index_for(width);
+        let changed = index != self.current;
+        self.current = index;
+        changed
+    }
+
+    /// Re-evaluate on `Event::Resize`; returns true if the selection changed
+    pub fn handle_event(&mut self, event: &Event) -> bool {
+        match event {
+            Event::Resize(width, _) => self.resize(*width),
+            _ => false,
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash> Breakpoints<Flex<K>> {
+    /// Compute the flex tree chosen for the width of `area`
+    pub fn compute(&self, area: Rect) -> HashMap<K, Rect> {
+        self.select(area.width).compute(area)
+    }
+}
+
+impl Breakpoints<Grid> {
+    /// Split with the grid chosen for the width of `area`
+    pub fn split(&self, area: Rect) -> HashMap<String, Rect> {
+        self.select(area.width).split(area)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout() -> Breakpoints<Flex<&'static str>> {
+        let panels = || {
+            [
+                Flex::item("files").grow(1.0),
+                Flex::item("editor").grow(3.0),
+                Flex::item("preview").grow(2.0).min(30).optional(),
+            ]
+        };
+        Breakpoints::new(Flex::vertical().children(panels()))
+            .at(100, Flex::horizontal().children(panels()))
+    }
+
+    #[test]
+    fn test_selects_widest_fitting_breakpoint() {
+        let layout = layout();
+        let narrow = layout.compute(Rect::new(0, 0, 80, 30));
+        assert_eq!(narrow["files"].width, 80);
+        assert_eq!(narrow["editor"].x, 0);
+
+        let wide = layout.compute(Rect::new(0, 0, 120, 30));
+        // Preview starts from its min and grows on top of it
+        assert_eq!(wide["files"], Rect::new(0, 0, 15, 30));
+        assert_eq!(wide["preview"], Rect::new(60, 0, 60, 30));
+    }
+
+    #[test]
+    fn test_resize_event_updates_current() {
+        let mut layout = layout();
+        assert_eq!(layout.current_min_width(), 0);
+        assert!(layout.handle_event(&Event::Resize(140, 40)));
+        assert_eq!(layout.current_min_width(), 100);
+        assert!(!layout.handle_event(&Event::Resize(150, 40)));
+        assert!(!layout.handle_event(&Event::Tick));
+        assert!(layout.handle_event(&Event::Resize(99, 40)));
+        assert_eq!(layout.current_min_width(), 0);
+    }
+
+    #[test]
+    fn test_optional_panel_hides_in_short_terminal() {
+        let layout = layout();
+        let areas = layout.compute(Rect::new(0, 0, 80, 20));
+        assert!(!areas.contains_key("preview"));
+        assert_eq!(areas["editor"].height, 15);
+    }
+}
@@ -0,0 +1,288 @@
+//! Borders with custom character sets, per-side edges, edge titles and gradients
+//!
+//! Plain bordered blocks come from `BorderStyle::apply`. `Border` covers the
+//! rest: any subset of sides, titles on all four edges and a `GradientFill`
+//! running along the frame.
+
+use super::{BorderStyle, Theme};
+use crate::svg::{GradientDirection, GradientFill};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Alignment, Rect};
+use ratatui::style::Style;
+use ratatui::symbols::border;
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Widget};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// ASCII-only border for terminals without box-drawing glyphs
+pub const ASCII: border::Set = border::Set {
+    top_left: "+",
+    top_right: "+",
+    bottom_left: "+",
+    bottom_right: "+",
+    vertical_left: "|",
+    vertical_right: "|",
+    horizontal_top: "-",
+    horizontal_bottom: "-",
+};
+
+/// Dashed lines with square corners
+pub const DASHED: border::Set = border::Set {
+    top_left: "┌",
+    top_right: "┐",
+    bottom_left: "└",
+    bottom_right: "┘",
+    vertical_left: "╎",
+    vertical_right: "╎",
+    horizontal_top: "╌",
+    horizontal_bottom: "╌",
+};
+
+/// Light lines with heavy corners
+pub const HEAVY_CORNERS: border::Set = border::Set {
+    top_left: "┏",
+    top_right: "┓",
+    bottom_left: "┗",
+    bottom_right: "┛",
+    vertical_left: "│",
+    vertical_right: "│",
+    horizontal_top: "─",
+    horizontal_bottom: "─",
+};
+
+/// Edge of a border
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+/// Border drawn around an area
+///
+/// ```
+/// use ratatui::layout::Alignment;
+/// use ruensh::style::{Border, BorderStyle, Edge};
+/// use ruensh::svg::{ColorScheme, GradientFill};
+/// use ratatui::widgets::Borders;
+///
+/// let scheme = ColorScheme::cyberpunk();
+/// let border = Border::new(BorderStyle::HeavyCorners)
+///     .sides(Borders::TOP | Borders::BOTTOM)
+///     .title("Files")
+///     .title_at(Edge::Bottom, Alignment::Right, "3 items")
+///     .gradient(GradientFill::new(vec![scheme.primary(), scheme.glow()]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Border<'a> {
+    border_style: BorderStyle,
+    sides: Borders,
+    style: Style,
+    gradient: Option<GradientFill>,
+    titles: Vec<(Edge, Alignment, Line<'a>)>,
+}
+
+impl<'a> Border<'a> {
+    /// Create a border on all sides
+    pub fn new(border_style: BorderStyle) -> Self {
+        Border {
+            border_style,
+            sides: Borders::ALL,
+            style: Style::default(),
+            gradient: None,
+            titles: Vec::new(),
+        }
+    }
+
+    /// Create a border with the theme's border style and colour
+    pub fn from_theme(theme: &Theme) -> Self {
+        Border::new(theme.border_style).style(Style::default().fg(theme.secondary))
+    }
+
+    /// Draw only the given sides
+    pub fn sides(mut self, sides: Borders) -> Self {
+        self.sides = sides;
+        self
+    }
+
+    /// Set the style of the border lines
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Colour the border lines with a gradient
+    ///
+    /// Horizontal gradients run left to right, vertical ones top to bottom,
+    /// diagonal ones from the top-left corner and radial ones from the centre.
+    pub fn gradient(mut self, gradient: GradientFill) -> Self {
+        self.gradient = Some(gradient);
+        self
+    }
+
+    /// Add a title at the left of the top edge
+    pub fn title(self, title: impl Into<Line<'a>>) -> Self {
+        self.title_at(Edge::Top, Alignment::Left, title)
+    }
+
+    /// Add a title on an edge
+    ///
+    /// On the left and right edges the title runs downwards; `Left` alignment
+    /// puts it at the top and `Right` at the bottom.
+    pub fn title_at(mut self, edge: Edge, alignment: Alignment, title: impl Into<Line<'a>>) -> Self {
+        self.titles.push((edge, alignment, title.into()));
+        self
+    }
+
+    /// Sides actually drawn; `BorderStyle::None` draws nothing
+    fn drawn_sides(&self) -> Borders {
+        if self.border_style == BorderStyle::None {
+            Borders::NONE
+        } else {
+            self.sides
+        }
+    }
+
+    /// Area left inside the border
+    pub fn inner(&self, area: Rect) -> Rect {
+        Block::default().borders(self.drawn_sides()).inner(area)
+    }
+
+    fn render_titles(&self, area: Rect, buf: &mut Buffer) {
+        let sides = self.drawn_sides();
+        let left = area.x + u16::from(sides.contains(Borders::LEFT));
+        let right = area.right().saturating_sub(u16::from(sides.contains(Borders::RIGHT)));
+        let top = area.y + u16::from(sides.contains(Borders::TOP));
+        let bottom = area.bottom().saturating_sub(u16::from(sides.contains(Borders::BOTTOM)));
+
+        for (edge, alignment, title) in &self.titles {
+            let length = title.width() as u16;
+            let place = |start: u16, end: u16| {
+                let space = end.saturating_sub(start);
+                let offset = match alignment {
+                    Alignment::Left => 0,
+                    Alignment::Center => space.saturating_sub(length) / 2,
+                    Alignment::Right => space.saturating_sub(length),
+                };
+                (start + offset, space)
+            };
+            match edge {
+                Edge::Top | Edge::Bottom => {
+                    let y = if *edge == Edge::Top { area.y } else { area.bottom().saturating_sub(1) };
+                    let (x, space) = place(left, right);
+                    buf.set_line(x, y, title, space.min(right.saturating_sub(x)));
+                }
+                Edge::Left | Edge::Right => {
+                    let x = if *edge == Edge::Left { area.x } else { area.right().saturating_sub(1) };
+                    let (mut y, _) = place(top, bottom);
+                    let graphemes = title.spans.iter().flat_map(|span| {
+                        let style = title.style.patch(span.style);
+                        span.content.graphemes(true).map(move |grapheme| (grapheme, style))
+                    });
+                    for (grapheme, style) in graphemes.filter(|(grapheme, _)| grapheme.width() > 0) {
+                        if y >= bottom {
+                            break;
+                        }
+                        buf[(x, y)].set_symbol(grapheme).set_style(style);
+                        y += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    fn render_gradient(&self, gradient: &GradientFill, area: Rect, buf: &mut Buffer) {
+        let inner = self.inner(area);
+        let span = |length: u16| length.saturating_sub(1).max(1) as f32;
+        for position in area.positions() {
+            if inner.contains(position) {
+                continue;
+            }
+            let x = (position.x - area.x) as f32 / span(area.width);
+            let y = (position.y - area.y) as f32 / span(area.height);
+            let t = match gradient.direction {
+                GradientDirection::Horizontal => x,
+                GradientDirection::Vertical => y,
+                GradientDirection::Diagonal => (x + y) / 2.0,
+                GradientDirection::Radial => {
+                    ((x - 0.5).powi(2) + (y - 0.5).powi(2)).sqrt() / 0.5f32.sqrt()
+                }
+            };
+            buf[position].set_fg(gradient.color_at(t));
+        }
+    }
+}
+
+impl Widget for Border<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let sides = self.drawn_sides();
+        if let Some(set) = self.border_style.border_set() {
+            Block::default()
+                .borders(sides)
+                .border_set(set)
+                .border_style(self.style)
+                .render(area, buf);
+        }
+        if let Some(gradient) = &self.gradient {
+            self.render_gradient(gradient, area, buf);
+        }
+        self.render_titles(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Color;
+
+    fn draw(border: Border<'_>, width: u16, height: u16) -> Buffer {
+        let mut buf = Buffer::empty(Rect::new(0, 0, width, height));
+        border.render(buf.area, &mut buf);
+        buf
+    }
+
+    fn row(buf: &Buffer, y: u16) -> String {
+        (0..buf.area.width).map(|x| buf[(x, y)].symbol()).collect()
+    }
+
+    #[test]
+    fn test_custom_sets_and_none() {
+        let buf = draw(Border::new(BorderStyle::Ascii), 4, 3);
+        assert_eq!(row(&buf, 0), "+--+");
+        assert_eq!(row(&buf, 1), "|  |");
+
+        let buf = draw(Border::new(BorderStyle::HeavyCorners), 3, 2);
+        assert_eq!(row(&buf, 0), "┏─┓");
+
+        let buf = draw(Border::new(BorderStyle::None).title("x"), 3, 2);
+        assert_eq!(row(&buf, 0), "x  ");
+        assert_eq!(Border::new(BorderStyle::None).inner(buf.area), buf.area);
+    }
+
+    #[test]
+    fn test_sides_and_edge_titles() {
+        let border = Border::new(BorderStyle::Single)
+            .sides(Borders::TOP | Borders::LEFT)
+            .title_at(Edge::Top, Alignment::Right, "ab")
+            .title_at(Edge::Left, Alignment::Center, "xy");
+        assert_eq!(border.inner(Rect::new(0, 0, 6, 6)), Rect::new(1, 1, 5, 5));
+
+        let buf = draw(border, 6, 6);
+        assert_eq!(row(&buf, 0), "┌───ab");
+        assert_eq!(row(&buf, 5), "│     ");
+        assert_eq!(buf[(0, 2)].symbol(), "x");
+        assert_eq!(buf[(0, 3)].symbol(), "y");
+    }
+
+    #[test]
+    fn test_gradient_runs_along_edges() {
+        let gradient = GradientFill::new(vec![Color::Rgb(0, 0, 0), Color::Rgb(200, 0, 0)]);
+        let buf = draw(Border::new(BorderStyle::Rounded).gradient(gradient), 5, 3);
+        assert_eq!(buf[(0, 0)].fg, Color::Rgb(0, 0, 0));
+        assert_eq!(buf[(2, 2)].fg, Color::Rgb(100, 0, 0));
+        assert_eq!(buf[(4, 1)].fg, Color::Rgb(200, 0, 0));
+        assert_eq!(buf[(2, 1)].fg, Color::Reset);
+    }
+}
@@ -0,0 +1,360 @@
+//! Terminal colour capability detection and downsampling
+//!
+//! Themes and SVG effects use `Color::Rgb` freely. `ColorSupport` works out
+//! what the terminal can show and maps every colour in a rendered buffer to
+//! the nearest palette entry it supports, measuring distance in Oklab so
+//! the match looks right rather than being numerically close.
+
+use ratatui::buffer::Buffer;
+use ratatui::style::Color;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// Environment variable forcing a colour level: `truecolor`, `256`, `16` or `none`
+pub const COLOR_OVERRIDE_VAR: &str = "RUENSH_COLOR";
+
+/// Colours a terminal can display, from least to most capable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColorSupport {
+    /// Colour disabled (`NO_COLOR` or a dumb terminal); only modifiers remain
+    NoColor,
+    /// The 16 standard ANSI colours
+    Ansi16,
+    /// The xterm 256-colour palette
+    Ansi256,
+    /// 24-bit RGB
+    TrueColor,
+}
+
+impl ColorSupport {
+    /// Detect the colour level from the process environment and terminfo
+    pub fn detect() -> Self {
+        Self::detect_with(|name| std::env::var(name).ok())
+    }
+
+    /// Detect the colour level using `env` to look up variables
+    ///
+    /// Checked in order: `RUENSH_COLOR`, `NO_COLOR`, `COLORTERM`, `TERM`,
+    /// then the `colors` capability of the terminfo entry for `TERM`.
+    pub fn detect_with(env: impl Fn(&str) -> Option<String>) -> Self {
+        if let Some(level) = env(COLOR_OVERRIDE_VAR).and_then(|value| Self::parse(&value)) {
+            return level;
+        }
+        if env("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+            return ColorSupport::NoColor;
+        }
+        if let Some(colorterm) = env("COLORTERM") {
+            let colorterm = colorterm.to_lowercase();
+            if colorterm == "truecolor" || colorterm == "24bit" {
+                return ColorSupport::TrueColor;
+            }
+        }
+
+        let term = env("TERM").unwrap_or_default();
+        if term.is_empty() || term == "dumb" {
+            return ColorSupport::NoColor;
+        }
+        if term.ends_with("-direct") || term.ends_with("-truecolor") {
+            return ColorSupport::TrueColor;
+        }
+        if let Some(colors) = terminfo_colors(&term, &env) {
+            return Self::from_color_count(colors);
+        }
+        if term.contains("256color") {
+            ColorSupport::Ansi256
+        } else {
+            ColorSupport::Ansi16
+        }
+    }
+
+    /// Parse an override value such as `truecolor`, `256`, `16` or `none`
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "truecolor" | "24bit" | "rgb" => Some(ColorSupport::TrueColor),
+            "256" | "ansi256" => Some(ColorSupport::Ansi256),
+            "16" | "ansi16" | "ansi" => Some(ColorSupport::Ansi16),
+            "none" | "0" | "never" => Some(ColorSupport::NoColor),
+            _ => None,
+        }
+    }
+
+    /// Level for a terminfo `colors` count
+    pub fn from_color_count(colors: u32) -> Self {
+        match colors {
+            0..=7 => ColorSupport::NoColor,
+            8..=255 => ColorSupport::Ansi16,
+            256..=0xFF_FFFF => ColorSupport::Ansi256,
+            _ => ColorSupport::TrueColor,
+        }
+    }
+
+    /// Map a colour to the nearest one this level can display
+    pub fn downsample(self, color: Color) -> Color {
+        match (self, color) {
+            (_, Color::Reset) | (ColorSupport::TrueColor, _) => color,
+            (ColorSupport::NoColor, _) => Color::Reset,
+            (ColorSupport::Ansi256, Color::Rgb(r, g, b)) => {
+                Color::Indexed(nearest(&palette()[16..], [r, g, b]) as u8 + 16)
+            }
+            (ColorSupport::Ansi256, _) => color,
+            (ColorSupport::Ansi16, Color::Rgb(r, g, b)) => ANSI16[nearest(&palette()[..16], [r, g, b])],
+            (ColorSupport::Ansi16, Color::Indexed(index)) if index < 16 => ANSI16[index as usize],
+            (ColorSupport::Ansi16, Color::Indexed(index)) => {
+                let [r, g, b] = indexed_rgb(index);
+                ANSI16[nearest(&palette()[..16], [r, g, b])]
+            }
+            (ColorSupport::Ansi16, _) => color,
+        }
+    }
+
+    /// Downsample every cell colour in a buffer
+    ///
+    /// Call at the end of a draw so the whole frame, including SVG effects,
+    /// matches the terminal.
+    pub fn adapt_buffer(self, buffer: &mut Buffer) {
+        if self == ColorSupport::TrueColor {
+            return;
+        }
+        for cell in buffer.content.iter_mut() {
+            cell.fg = self.downsample(cell.fg);
+            cell.bg = self.downsample(cell.bg);
+            cell.underline_color = self.downsample(cell.underline_color);
+        }
+    }
+}
+
+/// Named colours in ANSI index order
+const ANSI16: [Color; 16] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::Gray,
+    Color::DarkGray,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightYellow,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::LightCyan,
+    Color::White,
+];
+
+/// xterm default RGB values for the 16 ANSI colours
+const ANSI16_RGB: [[u8; 3]; 16] = [
+    [0, 0, 0],
+    [205, 0, 0],
+    [0, 205, 0],
+    [205, 205, 0],
+    [0, 0, 238],
+    [205, 0, 205],
+    [0, 205, 205],
+    [229, 229, 229],
+    [127, 127, 127],
+    [255, 0, 0],
+    [0, 255, 0],
+    [255, 255, 0],
+    [92, 92, 255],
+    [255, 0, 255],
+    [0, 255, 255],
+    [255, 255, 255],
+];
+
+/// RGB value of a colour, using xterm defaults for palette entries
+///
+/// `None` for `Reset`, whose value depends on the terminal.
+pub(crate) fn color_rgb(color: Color) -> Option<[u8; 3]> {
+    match color {
+        Color::Reset => None,
+        Color::Rgb(r, g, b) => Some([r, g, b]),
+        Color::Indexed(index) => Some(indexed_rgb(index)),
+        named => ANSI16.iter().position(|ansi| *ansi == named).map(|index| ANSI16_RGB[index]),
+    }
+}
+
+/// RGB value of a 256-colour palette entry
+fn indexed_rgb(index: u8) -> [u8; 3] {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    match index {
+        0..=15 => ANSI16_RGB[index as usize],
+        16..=231 => {
+            let i = index - 16;
+            [
+                LEVELS[(i / 36) as usize],
+                LEVELS[(i / 6 % 6) as usize],
+                LEVELS[(i % 6) as usize],
+            ]
+        }
+        _ => {
+            let level = 8 + 10 * (index - 232);
+            [level, level, level]
+        }
+    }
+}
+
+/// Oklab coordinates of the 256-colour palette
+fn palette() -> &'static [[f32; 3]; 256] {
+    static PALETTE: OnceLock<[[f32; 3]; 256]> = OnceLock::new();
+    PALETTE.get_or_init(|| std::array::from_fn(|index| oklab(indexed_rgb(index as u8))))
+}
+
+/// Index of the palette entry closest to `rgb`
+fn nearest(candidates: &[[f32; 3]], rgb: [u8; 3]) -> usize {
+    let target = oklab(rgb);
+    let distance = |lab: &[f32; 3]| {
+        (0..3).map(|i| (lab[i] - target[i]).powi(2)).sum::<f32>()
+    };
+    candidates
+        .iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| distance(a).total_cmp(&distance(b)))
+        .map_or(0, |(index, _)| index)
+}
+
+/// Convert sRGB to Oklab
+fn oklab([r, g, b]: [u8; 3]) -> [f32; 3] {
+    let linear = |c: u8| {
+        let c = c as f32 / 255.0;
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    let (r, g, b) = (linear(r), linear(g), linear(b));
+    let l = (0.412_221_47 * r + 0.536_332_55 * g + 0.051_445_99 * b).cbrt();
+    let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+    let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+    [
+        0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+        1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+        0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+    ]
+}
+
+/// Read the `colors` number from the compiled terminfo entry for `term`
+fn terminfo_colors(term: &str, env: &impl Fn(&str) -> Option<String>) -> Option<u32> {
+    let first = term.chars().next()?;
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    if let Some(dir) = env("TERMINFO") {
+        dirs.push(dir.into());
+    }
+    if let Some(home) = env("HOME") {
+        dirs.push(PathBuf::from(home).join(".terminfo"));
+    }
+    if let Some(list) = env("TERMINFO_DIRS") {
+        dirs.extend(list.split(':').filter(|dir| !dir.is_empty()).map(PathBuf::from));
+    }
+    dirs.extend(["/etc/terminfo", "/lib/terminfo", "/usr/share/terminfo"].map(PathBuf::from));
+
+    dirs.iter().find_map(|dir| {
+        // Entries live under their first letter, or its hex code on macOS
+        [first.to_string(), format!("{:x}", first as u32)]
+            .iter()
+            .find_map(|sub| std::fs::read(dir.join(sub).join(term)).ok())
+            .and_then(|data| parse_terminfo_colors(&data))
+    })
+}
+
+/// Parse the `colors` capability (number index 13) from compiled terminfo
+fn parse_terminfo_colors(data: &[u8]) -> Option<u32> {
+    const COLORS: usize = 13;
+    let short = |offset: usize| -> Option<usize> {
+        let bytes = data.get(offset..offset + 2)?;
+        Some(u16::from_le_bytes([bytes[0], bytes[1]]) as usize)
+    };
+    let number_size = match short(0)? {
+        0o432 => 2,
+        0o1036 => 4,
+        _ => return None,
+    };
+    let names_size = short(2)?;
+    let bools = short(4)?;
+    let numbers = short(6)?;
+    if COLORS >= numbers {
+        return None;
+    }
+    let mut offset = 12 + names_size + bools;
+    offset += offset % 2;
+    let start = offset + COLORS * number_size;
+    let bytes = data.get(start..start + number_size)?;
+    let value = if number_size == 2 {
+        i16::from_le_bytes([bytes[0], bytes[1]]) as i32
+    } else {
+        i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+    };
+    u32::try_from(value).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn detect(vars: &[(&str, &str)]) -> ColorSupport {
+        let vars: HashMap<String, String> =
+            vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        // Point terminfo lookups at an empty location
+        ColorSupport::detect_with(|name| match name {
+            "TERMINFO" | "HOME" | "TERMINFO_DIRS" => Some("/nonexistent".into()),
+            _ => vars.get(name).cloned(),
+        })
+    }
+
+    #[test]
+    fn test_detection_order() {
+        assert_eq!(detect(&[("COLORTERM", "truecolor"), ("TERM", "xterm")]), ColorSupport::TrueColor);
+        assert_eq!(detect(&[("TERM", "xterm-256color")]), ColorSupport::Ansi256);
+        assert_eq!(detect(&[("TERM", "screen")]), ColorSupport::Ansi16);
+        assert_eq!(detect(&[("TERM", "dumb"), ("COLORTERM", "")]), ColorSupport::NoColor);
+        assert_eq!(
+            detect(&[("NO_COLOR", "1"), ("COLORTERM", "truecolor")]),
+            ColorSupport::NoColor
+        );
+        assert_eq!(
+            detect(&[("NO_COLOR", "1"), (COLOR_OVERRIDE_VAR, "256")]),
+            ColorSupport::Ansi256
+        );
+    }
+
+    #[test]
+    fn test_parse_terminfo_colors() {
+        // Legacy format: names "x\0", no booleans, 14 numbers with colors = 256
+        let mut data = Vec::new();
+        for value in [0o432u16, 2, 0, 14, 0, 0] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(b"x\0");
+        for index in 0..14u16 {
+            let value: u16 = if index == 13 { 256 } else { 0xFFFF };
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        assert_eq!(parse_terminfo_colors(&data), Some(256));
+        assert_eq!(parse_terminfo_colors(b"garbage"), None);
+    }
+
+    #[test]
+    fn test_downsample_picks_perceptual_neighbours() {
+        let hot_pink = Color::Rgb(255, 0, 128);
+        assert_eq!(ColorSupport::TrueColor.downsample(hot_pink), hot_pink);
+        assert_eq!(ColorSupport::Ansi256.downsample(Color::Rgb(255, 0, 135)), Color::Indexed(198));
+        assert_eq!(ColorSupport::Ansi256.downsample(Color::Rgb(128, 128, 128)), Color::Indexed(244));
+        assert_eq!(ColorSupport::Ansi16.downsample(Color::Rgb(240, 20, 230)), Color::LightMagenta);
+        assert_eq!(ColorSupport::Ansi16.downsample(Color::Rgb(0, 255, 65)), Color::LightGreen);
+        assert_eq!(ColorSupport::Ansi16.downsample(Color::Indexed(196)), Color::LightRed);
+        assert_eq!(ColorSupport::NoColor.downsample(Color::Red), Color::Reset);
+    }
+
+    #[test]
+    fn test_adapt_buffer() {
+        let mut buffer = Buffer::empty(ratatui::layout::Rect::new(0, 0, 2, 1));
+        buffer[(0, 0)].set_fg(Color::Rgb(0, 0, 0)).set_bg(Color::Rgb(255, 255, 255));
+        ColorSupport::Ansi16.adapt_buffer(&mut buffer);
+        assert_eq!(buffer[(0, 0)].fg, Color::Black);
+        assert_eq!(buffer[(0, 0)].bg, Color::White);
+        assert_eq!(buffer[(1, 0)].fg, Color::Reset);
+    }
+}
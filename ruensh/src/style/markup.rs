@@ -0,0 +1,317 @@
+//! Rich text markup for styled strings
+//!
+//! `[bold primary]Saved[/] 3 files` becomes a `Line` with a bold span in the
+//! theme's primary colour followed by plain text. Tags nest, `[/]` closes the
+//! most recent one and `[/bold primary]` closes it by name.
+//!
+//! Words inside a tag:
+//!
+//! - modifiers: `bold`, `dim`, `italic`, `underline`, `blink`, `reverse`,
+//!   `hidden`, `strike`
+//! - colours: theme roles (`primary`, `muted`, `error`, ...), ratatui colour
+//!   names (`red`, `light-blue`), `#RRGGBB` or a palette index
+//! - `on <colour>` for the background
+//! - `link=<url>` to mark a link; the span is underlined and its position is
+//!   reported in `Markup::links`
+//!
+//! `\[` and `\]` produce literal brackets and `\\` a backslash.
+
+use super::Theme;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span, Text};
+use std::fmt;
+use std::str::FromStr;
+use unicode_width::UnicodeWidthStr;
+
+/// Error in a markup string; positions are byte offsets into the source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MarkupError {
+    /// A word in a tag is not a modifier, colour or link
+    UnknownStyle { position: usize, word: String },
+    /// A `[` without a matching `]`
+    UnclosedBracket { position: usize },
+    /// A `[/]` with no open tag
+    UnmatchedClose { position: usize },
+    /// A `[/name]` that does not close the most recent tag
+    MismatchedClose {
+        position: usize,
+        expected: String,
+        found: String,
+    },
+    /// A tag still open at the end of the source
+    UnclosedTag { tag: String },
+}
+
+impl fmt::Display for MarkupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MarkupError::UnknownStyle { position, word } => {
+                write!(f, "unknown style `{}` at {}", word, position)
+            }
+            MarkupError::UnclosedBracket { position } => write!(f, "unclosed `[` at {}", position),
+            MarkupError::UnmatchedClose { position } => {
+                write!(f, "closing tag at {} has no open tag", position)
+            }
+            MarkupError::MismatchedClose { position, expected, found } => write!(
+                f,
+                "closing tag `{}` at {} does not match `{}`",
+                found, position, expected
+            ),
+            MarkupError::UnclosedTag { tag } => write!(f, "tag `{}` is never closed", tag),
+        }
+    }
+}
+
+impl std::error::Error for MarkupError {}
+
+/// A link found in the markup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    /// Line index in `Markup::text`
+    pub line: usize,
+    /// Display column where the link text starts
+    pub column: usize,
+    /// Display width of the link text
+    pub width: usize,
+    pub url: String,
+}
+
+/// Parsed markup: styled text plus the links inside it
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Markup {
+    pub text: Text<'static>,
+    pub links: Vec<Link>,
+}
+
+/// An open tag
+struct Tag {
+    source: String,
+    style: Style,
+    link: Option<String>,
+}
+
+impl Markup {
+    /// Parse markup, resolving theme roles against `theme`
+    pub fn parse(source: &str, theme: &Theme) -> Result<Self, MarkupError> {
+        let mut parser = Parser {
+            markup: Markup::default(),
+            line: Line::default(),
+            buffer: String::new(),
+            stack: Vec::new(),
+        };
+
+        let mut chars = source.char_indices().peekable();
+        while let Some((position, c)) = chars.next() {
+            match c {
+                '\\' => match chars.peek() {
+                    Some(&(_, next @ ('[' | ']' | '\\'))) => {
+                        parser.buffer.push(next);
+                        chars.next();
+                    }
+                    _ => parser.buffer.push('\\'),
+                },
+                '[' => {
+                    let end = source[position..]
+                        .find(']')
+                        .ok_or(MarkupError::UnclosedBracket { position })?;
+                    let tag = &source[position + 1..position + end];
+                    parser.flush();
+                    match tag.strip_prefix('/') {
+                        Some(name) => parser.close(name.trim(), position)?,
+                        None => parser.open(tag, position, theme)?,
+                    }
+                    while chars.peek().is_some_and(|&(index, _)| index <= position + end) {
+                        chars.next();
+                    }
+                }
+                '\n' => {
+                    parser.flush();
+                    let line = std::mem::take(&mut parser.line);
+                    parser.markup.text.lines.push(line);
+                }
+                c => parser.buffer.push(c),
+            }
+        }
+
+        parser.flush();
+        if let Some(tag) = parser.stack.pop() {
+            return Err(MarkupError::UnclosedTag { tag: tag.source });
+        }
+        let line = std::mem::take(&mut parser.line);
+        parser.markup.text.lines.push(line);
+        Ok(parser.markup)
+    }
+}
+
+/// Parse markup, falling back to the raw source if it is malformed
+pub fn markup(source: &str, theme: &Theme) -> Text<'static> {
+    Markup::parse(source, theme)
+        .map(|markup| markup.text)
+        .unwrap_or_else(|_| Text::raw(source.to_string()))
+}
+
+struct Parser {
+    markup: Markup,
+    line: Line<'static>,
+    buffer: String,
+    stack: Vec<Tag>,
+}
+
+impl Parser {
+    /// Move buffered text into a span with the current style
+    fn flush(&mut self) {
+        if self.buffer.is_empty() {
+            return;
+        }
+        let content = std::mem::take(&mut self.buffer);
+        let style = self.stack.last().map(|tag| tag.style).unwrap_or_default();
+        if let Some(url) = self.stack.iter().rev().find_map(|tag| tag.link.clone()) {
+            self.markup.links.push(Link {
+                line: self.markup.text.lines.len(),
+                column: self.line.width(),
+                width: content.width(),
+                url,
+            });
+        }
+        self.line.spans.push(Span::styled(content, style));
+    }
+
+    fn open(&mut self, tag: &str, position: usize, theme: &Theme) -> Result<(), MarkupError> {
+        let parent = self.stack.last().map(|tag| tag.style).unwrap_or_default();
+        let (style, link) = parse_tag(tag, position, theme)?;
+        self.stack.push(Tag {
+            source: tag.trim().to_string(),
+            style: parent.patch(style),
+            link,
+        });
+        Ok(())
+    }
+
+    fn close(&mut self, name: &str, position: usize) -> Result<(), MarkupError> {
+        let tag = self.stack.pop().ok_or(MarkupError::UnmatchedClose { position })?;
+        if !name.is_empty() && name != tag.source {
+            return Err(MarkupError::MismatchedClose {
+                position,
+                expected: tag.source,
+                found: name.to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Style and link target described by the words of a tag
+fn parse_tag(tag: &str, position: usize, theme: &Theme) -> Result<(Style, Option<String>), MarkupError> {
+    let mut style = Style::default();
+    let mut link = None;
+    let mut words = tag.split_whitespace();
+    let unknown = |word: &str| MarkupError::UnknownStyle {
+        position,
+        word: word.to_string(),
+    };
+
+    while let Some(word) = words.next() {
+        if let Some(url) = word.strip_prefix("link=") {
+            style = style.fg(theme.accent).add_modifier(Modifier::UNDERLINED);
+            link = Some(url.to_string());
+        } else if word == "on" {
+            let color = words.next().ok_or_else(|| unknown(word))?;
+            style = style.bg(parse_color(color, theme).ok_or_else(|| unknown(color))?);
+        } else if let Some(modifier) = parse_modifier(word) {
+            style = style.add_modifier(modifier);
+        } else {
+            style = style.fg(parse_color(word, theme).ok_or_else(|| unknown(word))?);
+        }
+    }
+    Ok((style, link))
+}
+
+fn parse_modifier(word: &str) -> Option<Modifier> {
+    Some(match word {
+        "bold" | "b" => Modifier::BOLD,
+        "dim" => Modifier::DIM,
+        "italic" | "i" => Modifier::ITALIC,
+        "underline" | "underlined" | "u" => Modifier::UNDERLINED,
+        "blink" => Modifier::SLOW_BLINK,
+        "reverse" | "reversed" => Modifier::REVERSED,
+        "hidden" => Modifier::HIDDEN,
+        "strike" | "strikethrough" | "s" => Modifier::CROSSED_OUT,
+        _ => return None,
+    })
+}
+
+/// Theme role, colour name, `#RRGGBB` or palette index
+fn parse_color(word: &str, theme: &Theme) -> Option<Color> {
+    Some(match word {
+        "primary" => theme.primary,
+        "secondary" => theme.secondary,
+        "background" => theme.background,
+        "surface" => theme.surface,
+        "text" => theme.text,
+        "muted" => theme.muted,
+        "selection" => theme.selection,
+        "accent" => theme.accent,
+        "glow" => theme.glow,
+        "success" => theme.success,
+        "warning" => theme.warning,
+        "error" => theme.error,
+        _ => return Color::from_str(word).ok(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nested_tags_and_theme_roles() {
+        let theme = Theme::cyberpunk();
+        let text = markup("[bold primary]Saved [italic on #102030]3[/][/] files", &theme);
+        let spans = &text.lines[0].spans;
+        assert_eq!(spans.len(), 3);
+        assert_eq!(spans[0].content, "Saved ");
+        assert_eq!(spans[0].style, Style::default().fg(theme.primary).add_modifier(Modifier::BOLD));
+        assert_eq!(
+            spans[1].style,
+            Style::default()
+                .fg(theme.primary)
+                .bg(Color::Rgb(16, 32, 48))
+                .add_modifier(Modifier::BOLD | Modifier::ITALIC)
+        );
+        assert_eq!(spans[2].style, Style::default());
+    }
+
+    #[test]
+    fn test_escapes_newlines_and_links() {
+        let theme = Theme::dark();
+        let parsed = Markup::parse("a \\[b\\] c\\\\\nsee [link=https://x.dev]docs[/link=https://x.dev]", &theme).unwrap();
+        assert_eq!(parsed.text.lines.len(), 2);
+        assert_eq!(parsed.text.lines[0].spans[0].content, "a [b] c\\");
+        assert_eq!(
+            parsed.links,
+            vec![Link {
+                line: 1,
+                column: 4,
+                width: 4,
+                url: "https://x.dev".into()
+            }]
+        );
+        assert!(parsed.text.lines[1].spans[1].style.add_modifier.contains(Modifier::UNDERLINED));
+    }
+
+    #[test]
+    fn test_errors_and_lenient_fallback() {
+        let theme = Theme::dark();
+        assert_eq!(
+            Markup::parse("[shiny]x[/]", &theme),
+            Err(MarkupError::UnknownStyle { position: 0, word: "shiny".into() })
+        );
+        assert_eq!(Markup::parse("x[/]", &theme), Err(MarkupError::UnmatchedClose { position: 1 }));
+        assert_eq!(Markup::parse("[bold]x", &theme), Err(MarkupError::UnclosedTag { tag: "bold".into() }));
+        assert!(matches!(
+            Markup::parse("[bold]x[/red]", &theme),
+            Err(MarkupError::MismatchedClose { .. })
+        ));
+        assert_eq!(markup("50% [off", &theme), Text::raw("50% [off"));
+    }
+}
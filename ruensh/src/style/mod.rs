@@ -0,0 +1,294 @@
+//! Styling system for components
+
+mod border;
+mod color_support;
+mod markup;
+mod sheet;
+mod theme_file;
+
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::symbols;
+use ratatui::widgets::{Block, Borders};
+use serde::{Deserialize, Serialize};
+
+pub use border::{Border, Edge};
+pub use color_support::{ColorSupport, COLOR_OVERRIDE_VAR};
+pub(crate) use color_support::color_rgb;
+pub use markup::{markup, Link, Markup, MarkupError};
+pub use sheet::{Slot, StyleSheet, Variant};
+pub use theme_file::ThemeError;
+
+/// Border style options
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BorderStyle {
+    Rounded,
+    Single,
+    Double,
+    Thick,
+    /// `+`, `-` and `|` only, for terminals without box-drawing glyphs
+    Ascii,
+    /// Dashed lines with square corners
+    Dashed,
+    /// Light lines with heavy corners
+    HeavyCorners,
+    None,
+}
+
+impl BorderStyle {
+    /// Closest ratatui border type
+    ///
+    /// Custom sets and `None` have no exact equivalent: `None` maps to a
+    /// plain border. `apply` draws every style correctly.
+    #[deprecated(note = "use `BorderStyle::apply`, which handles custom sets and `None`")]
+    pub fn to_ratatui_border(&self) -> ratatui::widgets::BorderType {
+        match self {
+            BorderStyle::Rounded => ratatui::widgets::BorderType::Rounded,
+            BorderStyle::Single | BorderStyle::Ascii | BorderStyle::Dashed => {
+                ratatui::widgets::BorderType::Plain
+            }
+            BorderStyle::Double => ratatui::widgets::BorderType::Double,
+            BorderStyle::Thick | BorderStyle::HeavyCorners => ratatui::widgets::BorderType::Thick,
+            BorderStyle::None => ratatui::widgets::BorderType::Plain,
+        }
+    }
+
+    /// Characters used to draw the border, or `None` for no border
+    pub fn border_set(&self) -> Option<symbols::border::Set> {
+        match self {
+            BorderStyle::Rounded => Some(symbols::border::ROUNDED),
+            BorderStyle::Single => Some(symbols::border::PLAIN),
+            BorderStyle::Double => Some(symbols::border::DOUBLE),
+            BorderStyle::Thick => Some(symbols::border::THICK),
+            BorderStyle::Ascii => Some(border::ASCII),
+            BorderStyle::Dashed => Some(border::DASHED),
+            BorderStyle::HeavyCorners => Some(border::HEAVY_CORNERS),
+            BorderStyle::None => None,
+        }
+    }
+
+    /// Give a block borders on all sides in this style, or none for `None`
+    pub fn apply<'a>(&self, block: Block<'a>) -> Block<'a> {
+        match self.border_set() {
+            Some(set) => block.borders(Borders::ALL).border_set(set),
+            None => block.borders(Borders::NONE),
+        }
+    }
+}
+
+/// Theme configuration
+///
+/// Colours are semantic roles rather than fixed widgets. Missing roles in a
+/// theme file fall back to `Theme::dark()`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    pub name: String,
+    pub primary: Color,
+    pub secondary: Color,
+    pub background: Color,
+    /// Background of raised areas (panels, popups)
+    pub surface: Color,
+    pub text: Color,
+    /// De-emphasised text (hints, placeholders, disabled items)
+    pub muted: Color,
+    /// Background of selected items
+    pub selection: Color,
+    pub border_style: BorderStyle,
+    pub accent: Color,
+    /// Highlight colour for glow effects
+    pub glow: Color,
+    pub success: Color,
+    pub warning: Color,
+    pub error: Color,
+}
+
+impl Theme {
+    /// Create a dark theme (default)
+    pub fn dark() -> Self {
+        Theme {
+            name: String::from("dark"),
+            primary: Color::Magenta,
+            secondary: Color::Blue,
+            background: Color::Black,
+            surface: Color::Black,
+            text: Color::White,
+            muted: Color::DarkGray,
+            selection: Color::Blue,
+            border_style: BorderStyle::Rounded,
+            accent: Color::Cyan,
+            glow: Color::Magenta,
+            success: Color::Green,
+            warning: Color::Yellow,
+            error: Color::Red,
+        }
+    }
+
+    /// Create a light theme
+    pub fn light() -> Self {
+        Theme {
+            name: String::from("light"),
+            primary: Color::Magenta,
+            secondary: Color::Blue,
+            background: Color::White,
+            surface: Color::White,
+            text: Color::Black,
+            muted: Color::Gray,
+            selection: Color::LightBlue,
+            border_style: BorderStyle::Rounded,
+            accent: Color::Cyan,
+            glow: Color::Magenta,
+            success: Color::Green,
+            warning: Color::Yellow,
+            error: Color::Red,
+        }
+    }
+
+    /// Create the CyberPunk theme (hot pink and cyan on dark purple)
+    pub fn cyberpunk() -> Self {
+        Theme {
+            name: String::from("cyberpunk"),
+            surface: Color::Rgb(30, 0, 50),
+            text: Color::Rgb(240, 230, 255),
+            muted: Color::Rgb(120, 90, 150),
+            selection: Color::Rgb(70, 0, 110),
+            success: Color::Rgb(0, 255, 160),
+            warning: Color::Rgb(255, 200, 0),
+            error: Color::Rgb(255, 40, 80),
+            ..Theme::from(&crate::svg::ColorScheme::cyberpunk())
+        }
+    }
+
+    /// Create the Neon Tokyo theme (deep pink and sky blue on navy)
+    pub fn neon_tokyo() -> Self {
+        Theme {
+            name: String::from("neon_tokyo"),
+            surface: Color::Rgb(30, 30, 55),
+            text: Color::Rgb(230, 230, 250),
+            muted: Color::Rgb(110, 110, 150),
+            selection: Color::Rgb(60, 30, 110),
+            success: Color::Rgb(80, 250, 123),
+            warning: Color::Rgb(255, 215, 0),
+            error: Color::Rgb(255, 60, 100),
+            ..Theme::from(&crate::svg::ColorScheme::neon_tokyo())
+        }
+    }
+
+    /// Create the Matrix theme (greens on black)
+    pub fn matrix() -> Self {
+        Theme {
+            name: String::from("matrix"),
+            surface: Color::Rgb(0, 20, 5),
+            text: Color::Rgb(180, 255, 180),
+            muted: Color::Rgb(0, 120, 30),
+            selection: Color::Rgb(0, 70, 20),
+            success: Color::Rgb(0, 255, 65),
+            warning: Color::Rgb(200, 255, 0),
+            error: Color::Rgb(255, 60, 60),
+            border_style: BorderStyle::Single,
+            ..Theme::from(&crate::svg::ColorScheme::matrix())
+        }
+    }
+
+    /// Create the Holographic theme (pastel blue and purple on deep blue)
+    pub fn holographic() -> Self {
+        Theme {
+            name: String::from("holographic"),
+            surface: Color::Rgb(20, 30, 55),
+            text: Color::Rgb(230, 240, 255),
+            muted: Color::Rgb(110, 130, 170),
+            selection: Color::Rgb(50, 60, 110),
+            success: Color::Rgb(120, 255, 200),
+            warning: Color::Rgb(255, 220, 120),
+            error: Color::Rgb(255, 110, 150),
+            ..Theme::from(&crate::svg::ColorScheme::holographic())
+        }
+    }
+
+    /// Names accepted by `Theme::preset`
+    pub fn preset_names() -> &'static [&'static str] {
+        &["dark", "light", "cyberpunk", "neon_tokyo", "matrix", "holographic"]
+    }
+
+    /// Get a bundled theme by name
+    pub fn preset(name: &str) -> Option<Self> {
+        match name.to_lowercase().replace(['-', ' '], "_").as_str() {
+            "dark" => Some(Theme::dark()),
+            "light" => Some(Theme::light()),
+            "cyberpunk" => Some(Theme::cyberpunk()),
+            "neon_tokyo" => Some(Theme::neon_tokyo()),
+            "matrix" => Some(Theme::matrix()),
+            "holographic" => Some(Theme::holographic()),
+            _ => None,
+        }
+    }
+
+    pub fn set_primary(mut self, color: Color) -> Self {
+        self.primary = color;
+        self
+    }
+
+    pub fn set_secondary(mut self, color: Color) -> Self {
+        self.secondary = color;
+        self
+    }
+
+    pub fn set_border_style(mut self, style: BorderStyle) -> Self {
+        self.border_style = style;
+        self
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::dark()
+    }
+}
+
+/// Build a theme from an SVG colour scheme, keeping dark defaults for other roles
+impl From<&crate::svg::ColorScheme> for Theme {
+    fn from(scheme: &crate::svg::ColorScheme) -> Self {
+        let (primary, secondary, accent, background, glow) = scheme.colors();
+        Theme {
+            primary,
+            secondary,
+            accent,
+            background,
+            surface: background,
+            selection: glow,
+            glow,
+            ..Theme::dark()
+        }
+    }
+}
+
+/// Helper to create styled text
+pub fn highlight_style(theme: &Theme) -> Style {
+    Style::default()
+        .fg(theme.primary)
+        .add_modifier(Modifier::BOLD)
+}
+
+pub fn normal_style(theme: &Theme) -> Style {
+    Style::default().fg(theme.text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::svg::ColorScheme;
+
+    #[test]
+    fn test_presets_share_svg_palettes() {
+        for name in Theme::preset_names() {
+            assert_eq!(&Theme::preset(name).unwrap().name, name);
+        }
+        assert_eq!(Theme::preset("Neon-Tokyo"), Some(Theme::neon_tokyo()));
+        assert!(Theme::preset("sepia").is_none());
+
+        let scheme = ColorScheme::matrix();
+        let theme = Theme::matrix();
+        assert_eq!(theme.primary, scheme.primary());
+        assert_eq!(theme.glow, scheme.glow());
+        assert_eq!(scheme.theme().primary, theme.primary);
+    }
+}
@@ -0,0 +1,202 @@
+//! Named style slots with interaction variants
+//!
+//! Components draw each part (border, title, items, buttons) with the style
+//! of a `Slot`. Defaults come from the theme roles; a `StyleSheet` attached
+//! to a component overrides them per instance, either in the normal state
+//! (inherited by the other variants) or only while hovered, focused or
+//! disabled.
+
+use super::Theme;
+use ratatui::style::{Modifier, Style};
+use std::collections::HashMap;
+use std::fmt;
+
+/// A styleable part of a component
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Slot {
+    /// Background and default text of the whole component
+    Base,
+    Border,
+    Title,
+    /// Body text such as a modal message
+    Text,
+    /// An unselected list item
+    Item,
+    /// The selected list item
+    ItemSelected,
+    /// The main button
+    Button,
+    /// A secondary button such as Cancel
+    ButtonSecondary,
+    /// Hint text shown in an empty input
+    Placeholder,
+    /// Validation and error messages
+    Error,
+    /// Dividers between tabs
+    Separator,
+    /// An inactive tab
+    Tab,
+    /// The active tab
+    TabActive,
+}
+
+impl Slot {
+    /// Name of the slot, e.g. `item.selected`
+    pub fn name(self) -> &'static str {
+        match self {
+            Slot::Base => "base",
+            Slot::Border => "border",
+            Slot::Title => "title",
+            Slot::Text => "text",
+            Slot::Item => "item",
+            Slot::ItemSelected => "item.selected",
+            Slot::Button => "button",
+            Slot::ButtonSecondary => "button.secondary",
+            Slot::Placeholder => "placeholder",
+            Slot::Error => "error",
+            Slot::Separator => "separator",
+            Slot::Tab => "tab",
+            Slot::TabActive => "tab.active",
+        }
+    }
+}
+
+impl fmt::Display for Slot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Interaction state a slot is drawn in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Variant {
+    #[default]
+    Normal,
+    Hover,
+    Focus,
+    Disabled,
+}
+
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Variant::Normal => "normal",
+            Variant::Hover => "hover",
+            Variant::Focus => "focused",
+            Variant::Disabled => "disabled",
+        })
+    }
+}
+
+/// Per-instance style overrides, resolved on top of the theme
+///
+/// ```
+/// use ratatui::style::{Color, Style};
+/// use ruensh::style::{Slot, StyleSheet, Theme, Variant};
+///
+/// let sheet = StyleSheet::new()
+///     .slot(Slot::ItemSelected, Style::default().fg(Color::Yellow))
+///     .variant(Slot::Button, Variant::Disabled, Style::default().fg(Color::DarkGray));
+/// let style = sheet.resolve(&Theme::dark(), Slot::ItemSelected, Variant::Normal);
+/// assert_eq!(style.fg, Some(Color::Yellow));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StyleSheet {
+    rules: HashMap<(Slot, Variant), Style>,
+}
+
+impl StyleSheet {
+    /// Create an empty sheet; every slot uses the theme defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Override a slot's normal style
+    ///
+    /// Other variants start from it, but the theme's hover, focus and
+    /// disabled styles still apply on top; use `variant` to override those.
+    pub fn slot(self, slot: Slot, style: Style) -> Self {
+        self.variant(slot, Variant::Normal, style)
+    }
+
+    /// Override a slot in one variant only
+    pub fn variant(mut self, slot: Slot, variant: Variant, style: Style) -> Self {
+        let rule = self.rules.entry((slot, variant)).or_default();
+        *rule = rule.patch(style);
+        self
+    }
+
+    /// Style for a slot in the given variant
+    ///
+    /// Layers, from lowest to highest: theme default, this sheet's slot
+    /// override, theme variant, this sheet's variant override. Like CSS
+    /// pseudo-classes, variants win over plain slot rules.
+    pub fn resolve(&self, theme: &Theme, slot: Slot, variant: Variant) -> Style {
+        let rule = |variant| self.rules.get(&(slot, variant)).copied().unwrap_or_default();
+        let mut style = theme_style(theme, slot, Variant::Normal).patch(rule(Variant::Normal));
+        if variant != Variant::Normal {
+            style = style.patch(theme_style(theme, slot, variant)).patch(rule(variant));
+        }
+        style
+    }
+}
+
+/// Theme defaults; non-normal variants are patched over the normal style
+fn theme_style(theme: &Theme, slot: Slot, variant: Variant) -> Style {
+    let style = Style::default();
+    let disabled = style.fg(theme.muted).remove_modifier(Modifier::BOLD);
+    match (slot, variant) {
+        (Slot::Base, Variant::Normal) => style.fg(theme.text).bg(theme.background),
+        (Slot::Border, Variant::Normal) => style.fg(theme.secondary),
+        (Slot::Border, Variant::Hover) => style.fg(theme.accent),
+        (Slot::Border, Variant::Focus) => style.fg(theme.primary).add_modifier(Modifier::BOLD),
+        (Slot::Title, Variant::Normal) => style.fg(theme.primary).add_modifier(Modifier::BOLD),
+        (Slot::Text | Slot::Item | Slot::Tab, Variant::Normal) => style.fg(theme.text),
+        (Slot::Text, Variant::Focus) => style.fg(theme.primary).add_modifier(Modifier::BOLD),
+        (Slot::Placeholder | Slot::Separator, Variant::Normal) => style.fg(theme.muted),
+        (Slot::Error, Variant::Normal) => style.fg(theme.error),
+        (Slot::TabActive, Variant::Normal) => style
+            .fg(theme.background)
+            .bg(theme.primary)
+            .add_modifier(Modifier::BOLD),
+        (Slot::Item, Variant::Hover) => style.fg(theme.accent),
+        (Slot::ItemSelected, Variant::Normal) => style.fg(theme.primary).add_modifier(Modifier::BOLD),
+        (Slot::ItemSelected, Variant::Focus) => style.bg(theme.selection),
+        (Slot::Button, Variant::Normal) => style.fg(theme.primary).add_modifier(Modifier::BOLD),
+        (Slot::ButtonSecondary, Variant::Normal) => style.fg(theme.secondary).add_modifier(Modifier::BOLD),
+        (Slot::Button, Variant::Focus) => style.fg(theme.background).bg(theme.primary),
+        (Slot::ButtonSecondary, Variant::Focus) => style.fg(theme.background).bg(theme.secondary),
+        (Slot::Button | Slot::ButtonSecondary, Variant::Hover) => style.add_modifier(Modifier::UNDERLINED),
+        (_, Variant::Disabled) => disabled,
+        _ => style,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Color;
+
+    #[test]
+    fn test_resolve_layers_theme_and_overrides() {
+        let theme = Theme::dark();
+        let sheet = StyleSheet::new()
+            .slot(Slot::Button, Style::default().fg(Color::Yellow))
+            .variant(Slot::Button, Variant::Focus, Style::default().bg(Color::Red));
+
+        let normal = sheet.resolve(&theme, Slot::Button, Variant::Normal);
+        assert_eq!(normal.fg, Some(Color::Yellow));
+        assert!(normal.add_modifier.contains(Modifier::BOLD));
+
+        let focused = sheet.resolve(&theme, Slot::Button, Variant::Focus);
+        assert_eq!((focused.fg, focused.bg), (Some(theme.background), Some(Color::Red)));
+
+        let disabled = sheet.resolve(&theme, Slot::Button, Variant::Disabled);
+        assert_eq!(disabled.fg, Some(theme.muted));
+        assert!(!disabled.add_modifier.contains(Modifier::BOLD));
+
+        let border = StyleSheet::new().resolve(&theme, Slot::Border, Variant::Focus);
+        assert_eq!(border.fg, Some(theme.primary));
+        assert_eq!(Slot::ItemSelected.to_string(), "item.selected");
+    }
+}
@@ -0,0 +1,169 @@
+//! Loading and saving themes as TOML or JSON
+//!
+//! Every role is a string key holding a colour name, a `#RRGGBB` hex value
+//! or a 256-colour index. TOML files may put the keys in a `[theme]` table. Unknown keys are ignored and missing keys fall
+//! back to the dark theme.
+
+use super::Theme;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+
+/// Error reading or writing a theme file
+#[derive(Debug)]
+pub enum ThemeError {
+    /// The file is not valid TOML or JSON
+    Syntax { line: usize, message: String },
+    /// A key or value is not a valid theme role
+    Invalid(String),
+    /// The file could not be read or written
+    Io(std::io::Error),
+}
+
+impl fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeError::Syntax { line, message } => write!(f, "line {}: {}", line, message),
+            ThemeError::Invalid(message) => write!(f, "invalid theme: {}", message),
+            ThemeError::Io(err) => write!(f, "cannot access theme file: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ThemeError {}
+
+impl From<std::io::Error> for ThemeError {
+    fn from(err: std::io::Error) -> Self {
+        ThemeError::Io(err)
+    }
+}
+
+/// TOML layout written by `to_toml`, with the roles under `[theme]`
+#[derive(Serialize)]
+struct TomlFile<'a> {
+    theme: &'a Theme,
+}
+
+impl Theme {
+    /// Parse a theme from TOML
+    pub fn from_toml(source: &str) -> Result<Self, ThemeError> {
+        let mut table: toml::Table = source.parse().map_err(|err: toml::de::Error| {
+            // Spans are byte offsets into `source`
+            let offset = err.span().map_or(0, |span| span.start);
+            ThemeError::Syntax {
+                line: source[..offset].matches('\n').count() + 1,
+                message: err.message().to_string(),
+            }
+        })?;
+        let roles = match table.remove("theme") {
+            Some(toml::Value::Table(theme)) => theme,
+            Some(_) => return Err(ThemeError::Invalid("`theme` must be a table".to_string())),
+            None => table,
+        };
+        Theme::deserialize(roles).map_err(|err| ThemeError::Invalid(err.message().to_string()))
+    }
+
+    /// Write the theme as TOML
+    pub fn to_toml(&self) -> String {
+        toml::to_string(&TomlFile { theme: self }).expect("theme roles are plain strings")
+    }
+
+    /// Parse a theme from a JSON object of strings
+    pub fn from_json(source: &str) -> Result<Self, ThemeError> {
+        let value: serde_json::Value = serde_json::from_str(source).map_err(|err| ThemeError::Syntax {
+            line: err.line(),
+            message: err.to_string(),
+        })?;
+        Theme::deserialize(value).map_err(|err| ThemeError::Invalid(err.to_string()))
+    }
+
+    /// Write the theme as a JSON object
+    pub fn to_json(&self) -> String {
+        let mut json = serde_json::to_string_pretty(self).expect("theme roles are plain strings");
+        json.push('\n');
+        json
+    }
+
+    /// Load a theme file; `.json` files are read as JSON, anything else as TOML
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ThemeError> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path)?;
+        if is_json(path) {
+            Theme::from_json(&source)
+        } else {
+            Theme::from_toml(&source)
+        }
+    }
+
+    /// Save the theme; `.json` files are written as JSON, anything else as TOML
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ThemeError> {
+        let path = path.as_ref();
+        let contents = if is_json(path) { self.to_json() } else { self.to_toml() };
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::BorderStyle;
+    use ratatui::style::Color;
+
+    #[test]
+    fn test_toml_round_trip() {
+        let theme = Theme::cyberpunk();
+        assert_eq!(Theme::from_toml(&theme.to_toml()).unwrap(), theme);
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let theme = Theme::light();
+        assert_eq!(Theme::from_json(&theme.to_json()).unwrap(), theme);
+    }
+
+    #[test]
+    fn test_partial_file_falls_back_to_dark() {
+        let theme = Theme::from_toml(
+            "# my theme\n[theme]\nprimary = \"#FF8800\" # orange\nborder_style = \"Double\"\n",
+        )
+        .unwrap();
+        assert_eq!(theme.primary, Color::Rgb(255, 136, 0));
+        assert_eq!(theme.border_style, BorderStyle::Double);
+        assert_eq!(theme.error, Theme::dark().error);
+
+        let theme = Theme::from_json(r#"{ "muted": "8", "text": "light-yellow" }"#).unwrap();
+        assert_eq!(theme.muted, Color::Indexed(8));
+        assert_eq!(theme.text, Color::LightYellow);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            Theme::from_toml("primary = red"),
+            Err(ThemeError::Syntax { line: 1, .. })
+        ));
+        assert!(matches!(
+            Theme::from_json("{\n\"primary\": \"red\"\n\"text\": \"red\"}"),
+            Err(ThemeError::Syntax { line: 3, .. })
+        ));
+        assert!(matches!(
+            Theme::from_toml("primary = \"not a colour\""),
+            Err(ThemeError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_literal_strings_and_unicode_escapes() {
+        let theme = Theme::from_toml("[theme]\nname = 'x'\nprimary = '#FF8800'\n").unwrap();
+        assert_eq!(theme.name, "x");
+        assert_eq!(theme.primary, Color::Rgb(255, 136, 0));
+
+        let theme = Theme::from_json(r#"{ "name": "caf\u00e9" }"#).unwrap();
+        assert_eq!(theme.name, "café");
+    }
+}
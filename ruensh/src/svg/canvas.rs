@@ -1,10 +1,21 @@
 //! Canvas for rendering SVG-inspired graphics
+//!
+//! Text and box-drawing glyphs go straight into the cell buffer. Lines,
+//! fills and ellipses follow the canvas resolution: at `CharCell` they pick
+//! glyphs per cell, at `Braille`, `Block` and `HalfBlock` they are rasterized
+//! into a sub-cell `PixelBuffer` and encoded when the canvas is read. Later
+//! drawing wins: writing a character clears the pixels of its cell.
 
 use ratatui::Frame;
+use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::widgets::{Block, Borders};
 use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Widget;
 use super::Resolution;
+use super::raster::{clip_segment, PixelBuffer};
+use super::shapes::Point;
+use super::transitions::Interpolate;
 
 /// SVG-inspired canvas for rendering vector graphics in terminal
 pub struct SvgCanvas {
@@ -13,31 +24,136 @@ pub struct SvgCanvas {
     resolution: Resolution,
     /// Buffer for character rendering
     buffer: Vec<Vec<char>>,
-    /// Color buffer
-    colors: Vec<Vec<Option<Color>>>,
+    /// Style buffer: colours and modifiers per cell
+    styles: Vec<Vec<Style>>,
+    /// Sub-cell pixels, drawn over the character buffer
+    pixels: PixelBuffer,
+    antialias: bool,
+    /// Colour anti-aliased edges fade into
+    background: Option<Color>,
 }
 
 impl SvgCanvas {
     /// Create a new canvas
     pub fn new(width: u16, height: u16) -> Self {
         let buffer = vec![vec![' '; width as usize]; height as usize];
-        let colors = vec![vec![None; width as usize]; height as usize];
+        let styles = vec![vec![Style::default(); width as usize]; height as usize];
         
         Self {
             width,
             height,
             resolution: Resolution::CharCell,
             buffer,
-            colors,
+            styles,
+            pixels: PixelBuffer::for_cells(width, height, Resolution::CharCell),
+            antialias: false,
+            background: None,
         }
     }
 
     /// Set resolution mode
     pub fn resolution(mut self, resolution: Resolution) -> Self {
         self.resolution = resolution;
+        self.pixels = PixelBuffer::for_cells(self.width, self.height, resolution);
         self
     }
 
+    /// Draw lines with Wu's algorithm instead of Bresenham's
+    ///
+    /// Only applies at sub-cell resolutions.
+    pub fn antialias(mut self, antialias: bool) -> Self {
+        self.antialias = antialias;
+        self
+    }
+
+    /// Set the canvas background
+    ///
+    /// Cells without a background colour of their own get this one, and
+    /// anti-aliased edges fade into it. Without it, coverage only decides
+    /// which pixels are set.
+    pub fn background(mut self, color: Color) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    /// Pixels per cell at the current resolution, as (columns, rows)
+    pub fn pixels_per_cell(&self) -> (u16, u16) {
+        self.resolution.pixels_per_cell()
+    }
+
+    /// Sub-cell pixel buffer, for drawing with the raster algorithms directly
+    pub fn pixels_mut(&mut self) -> &mut PixelBuffer {
+        &mut self.pixels
+    }
+
+    /// Canvas width in cells
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    /// Canvas height in cells
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    /// Character and foreground colour at a cell, with pixels encoded
+    pub fn cell(&self, x: u16, y: u16) -> Option<(char, Option<Color>)> {
+        self.styled_cell(x, y).map(|(ch, style)| (ch, style.fg))
+    }
+
+    /// Character and full style at a cell, with pixels encoded
+    ///
+    /// Pixels set the foreground; the background and modifiers come from
+    /// the cell's own style, falling back to the canvas background.
+    pub fn styled_cell(&self, x: u16, y: u16) -> Option<(char, Style)> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let mut style = Style::default();
+        if let Some(background) = self.background {
+            style = style.bg(background);
+        }
+        style = style.patch(self.styles[y as usize][x as usize]);
+
+        if let Some((ch, fg, bg)) = self.encoded_pixels(x, y) {
+            style = style.fg(fg);
+            if let Some(bg) = bg {
+                style = style.bg(bg);
+            }
+            return Some((ch, style));
+        }
+        Some((self.buffer[y as usize][x as usize], style))
+    }
+
+    /// Glyph and colours for the pixels of a cell
+    fn encoded_pixels(&self, x: u16, y: u16) -> Option<(char, Color, Option<Color>)> {
+        // Half blocks can show two colours: the top as foreground, the bottom as background
+        if self.resolution == Resolution::HalfBlock {
+            let (top, bottom) = (y as i32 * 2, y as i32 * 2 + 1);
+            if self.pixels.is_set(x as i32, top) && self.pixels.is_set(x as i32, bottom) {
+                let (top, bottom) = (self.pixels.get(x as i32, top), self.pixels.get(x as i32, bottom));
+                if let (Some((top, _)), Some((bottom, _))) = (top, bottom) {
+                    if top != bottom {
+                        return Some(('▀', top, Some(bottom)));
+                    }
+                }
+            }
+        }
+
+        let (ch, color, coverage) = self.pixels.encode_cell(x, y, self.resolution)?;
+        let color = match self.background {
+            Some(background) if coverage < 1.0 => background.lerp(&color, coverage),
+            _ => color,
+        };
+        Some((ch, color, None))
+    }
+
+    /// Map a point in cell coordinates to pixel coordinates
+    fn to_pixels(&self, point: Point) -> (f32, f32) {
+        let (px, py) = self.pixels_per_cell();
+        (point.x * px as f32, point.y * py as f32)
+    }
+
     /// Clear the canvas
     pub fn clear(&mut self) {
         for row in &mut self.buffer {
@@ -45,18 +161,47 @@ impl SvgCanvas {
                 *cell = ' ';
             }
         }
-        for row in &mut self.colors {
+        for row in &mut self.styles {
             for cell in row {
-                *cell = None;
+                *cell = Style::default();
             }
         }
+        self.pixels.clear();
     }
 
     /// Draw a character at position
     pub fn draw_char(&mut self, x: u16, y: u16, ch: char, color: Option<Color>) {
+        let style = match color {
+            Some(color) => Style::default().fg(color),
+            None => Style::default(),
+        };
+        self.draw_styled_char(x, y, ch, style);
+    }
+
+    /// Draw a character with a full style: colours and modifiers
+    pub fn draw_styled_char(&mut self, x: u16, y: u16, ch: char, style: Style) {
         if x < self.width && y < self.height {
             self.buffer[y as usize][x as usize] = ch;
-            self.colors[y as usize][x as usize] = color;
+            self.styles[y as usize][x as usize] = style;
+            let (px, py) = self.pixels_per_cell();
+            self.pixels.clear_rect((x * px) as i32, (y * py) as i32, px as u32, py as u32);
+        }
+    }
+
+    /// Patch the style of a cell, keeping its character or pixels
+    ///
+    /// Useful for putting a background behind Braille or block pixels.
+    pub fn set_style(&mut self, x: u16, y: u16, style: Style) {
+        if x < self.width && y < self.height {
+            let cell = &mut self.styles[y as usize][x as usize];
+            *cell = cell.patch(style);
+        }
+    }
+
+    /// Draw a character at a signed position, ignoring cells off the canvas
+    pub(crate) fn plot(&mut self, x: i32, y: i32, ch: char, color: Option<Color>) {
+        if (0..self.width as i32).contains(&x) && (0..self.height as i32).contains(&y) {
+            self.draw_char(x as u16, y as u16, ch, color);
         }
     }
 
@@ -120,6 +265,177 @@ impl SvgCanvas {
         }
     }
 
+    /// Draw a line between two points in cell coordinates
+    ///
+    /// At `CharCell` resolution the glyph follows the slope: `─`, `│`, `╲` or
+    /// `╱`, and widths of 2 and more use heavy glyphs. Otherwise the line is
+    /// rasterized into pixels and `width` counts pixels. Wider lines add
+    /// parallel lines across the line's minor axis.
+    pub fn draw_line(&mut self, from: Point, to: Point, width: u8, color: Option<Color>) {
+        if self.pixels_per_cell() != (1, 1) {
+            self.raster_line(from, to, width, color.unwrap_or(Color::Reset));
+            return;
+        }
+
+        let (dx, dy) = (to.x - from.x, to.y - from.y);
+        let heavy = width > 1;
+        let ch = if dy.abs() * 2.0 < dx.abs() {
+            if heavy { '━' } else { '─' }
+        } else if dx.abs() * 2.0 < dy.abs() {
+            if heavy { '┃' } else { '│' }
+        } else if (dx > 0.0) == (dy > 0.0) {
+            '╲'
+        } else {
+            '╱'
+        };
+
+        // Clip with room for the parallel lines of wide strokes
+        let margin = width as f64 + 1.0;
+        let max = (self.width as f64 + margin, self.height as f64 + margin);
+        let clipped = clip_segment((from.x as f64, from.y as f64), (to.x as f64, to.y as f64), (-margin, -margin), max);
+        let Some(((x0, y0), (x1, y1))) = clipped else {
+            return;
+        };
+        let (x0, y0, x1, y1) = (x0.floor() as i64, y0.floor() as i64, x1.floor() as i64, y1.floor() as i64);
+        let steps = (x1 - x0).abs().max((y1 - y0).abs());
+        let horizontal = dx.abs() >= dy.abs();
+        for shift in thickness_offsets(width) {
+            let (sx, sy) = if horizontal { (0, shift) } else { (shift, 0) };
+            for step in 0..=steps {
+                let t = if steps == 0 { 0.0 } else { step as f64 / steps as f64 };
+                let x = x0 as f64 + (x1 - x0) as f64 * t;
+                let y = y0 as f64 + (y1 - y0) as f64 * t;
+                self.plot(x.round() as i32 + sx, y.round() as i32 + sy, ch, color);
+            }
+        }
+    }
+
+    fn raster_line(&mut self, from: Point, to: Point, width: u8, color: Color) {
+        let (x0, y0) = self.to_pixels(from);
+        let (x1, y1) = self.to_pixels(to);
+        // Clip before converting to integer pixels, which would saturate
+        let margin = width as f64 + 1.0;
+        let max = (self.pixels.width() as f64 + margin, self.pixels.height() as f64 + margin);
+        let clipped = clip_segment((x0 as f64, y0 as f64), (x1 as f64, y1 as f64), (-margin, -margin), max);
+        let Some(((x0, y0), (x1, y1))) = clipped else {
+            return;
+        };
+        let (x0, y0, x1, y1) = (x0 as f32, y0 as f32, x1 as f32, y1 as f32);
+        let horizontal = (x1 - x0).abs() >= (y1 - y0).abs();
+        for shift in thickness_offsets(width) {
+            let (sx, sy) = if horizontal { (0.0, shift as f32) } else { (shift as f32, 0.0) };
+            if self.antialias {
+                // Wu works on pixel centres
+                self.pixels.line_aa(x0 + sx - 0.5, y0 + sy - 0.5, x1 + sx - 0.5, y1 + sy - 0.5, color);
+            } else {
+                let floor = |value: f32| value.floor() as i32;
+                self.pixels.line(floor(x0 + sx), floor(y0 + sy), floor(x1 + sx), floor(y1 + sy), color);
+            }
+        }
+    }
+
+    /// Fill every cell whose centre is inside a region
+    ///
+    /// `min` and `max` bound the region in cell coordinates; only cells in
+    /// that box are tested. At sub-cell resolutions pixel centres are tested
+    /// instead and `ch` is not used.
+    pub fn fill_region(&mut self, min: Point, max: Point, inside: impl Fn(Point) -> bool, ch: char, color: Option<Color>) {
+        let (px, py) = self.pixels_per_cell();
+        if (px, py) != (1, 1) {
+            let color = color.unwrap_or(Color::Reset);
+            let (left, top) = self.to_pixels(min);
+            let (right, bottom) = self.to_pixels(max);
+            let x_end = (right.ceil() as i32).min(self.pixels.width() as i32);
+            let y_end = (bottom.ceil() as i32).min(self.pixels.height() as i32);
+            for y in (top.floor().max(0.0) as i32)..y_end {
+                for x in (left.floor().max(0.0) as i32)..x_end {
+                    let centre = Point::new((x as f32 + 0.5) / px as f32, (y as f32 + 0.5) / py as f32);
+                    if inside(centre) {
+                        self.pixels.set(x, y, color);
+                    }
+                }
+            }
+            return;
+        }
+
+        let x_start = min.x.floor().max(0.0) as i32;
+        let y_start = min.y.floor().max(0.0) as i32;
+        let x_end = (max.x.ceil() as i32).min(self.width as i32);
+        let y_end = (max.y.ceil() as i32).min(self.height as i32);
+        for y in y_start..y_end {
+            for x in x_start..x_end {
+                if inside(Point::new(x as f32 + 0.5, y as f32 + 0.5)) {
+                    self.plot(x, y, ch, color);
+                }
+            }
+        }
+    }
+
+    /// Fill a polygon given in cell coordinates, using the even-odd rule
+    ///
+    /// Filled cells are drawn as `█` at `CharCell` resolution.
+    pub fn fill_polygon(&mut self, points: &[Point], color: Option<Color>) {
+        let points: Vec<(f32, f32)> = points.iter().map(|point| self.to_pixels(*point)).collect();
+        self.pixels.fill_polygon(&points, color.unwrap_or(Color::Reset));
+    }
+
+    /// Fill an ellipse given in cell coordinates
+    pub fn fill_ellipse(&mut self, center: Point, rx: f32, ry: f32, color: Option<Color>) {
+        if self.pixels_per_cell() == (1, 1) {
+            let min = Point::new(center.x - rx, center.y - ry);
+            let max = Point::new(center.x + rx, center.y + ry);
+            self.fill_region(min, max, |point| in_ellipse(point, center, rx, ry), '█', color);
+            return;
+        }
+        let (cx, cy, rx, ry) = self.ellipse_pixels(center, rx, ry);
+        self.pixels.fill_ellipse(cx, cy, rx, ry, color.unwrap_or(Color::Reset));
+    }
+
+    /// Outline an ellipse given in cell coordinates
+    ///
+    /// The stroke runs inside the edge, `width` cells or pixels deep. At
+    /// `CharCell` resolution it is drawn with `●`; otherwise circles use the
+    /// midpoint circle algorithm and other ellipses the midpoint ellipse one.
+    pub fn draw_ellipse(&mut self, center: Point, rx: f32, ry: f32, width: u8, color: Option<Color>) {
+        if width == 0 {
+            return;
+        }
+        if self.pixels_per_cell() == (1, 1) {
+            let min = Point::new(center.x - rx, center.y - ry);
+            let max = Point::new(center.x + rx, center.y + ry);
+            let depth = width as f32;
+            let (inner_x, inner_y) = ((rx - depth).max(0.0), (ry - depth).max(0.0));
+            let on_ring = |point: Point| {
+                in_ellipse(point, center, rx, ry)
+                    && (inner_x == 0.0 || inner_y == 0.0 || !in_ellipse(point, center, inner_x, inner_y))
+            };
+            self.fill_region(min, max, on_ring, '●', color);
+            return;
+        }
+        let color = color.unwrap_or(Color::Reset);
+        let (cx, cy, rx, ry) = self.ellipse_pixels(center, rx, ry);
+        for inset in 0..(width as i32).min(rx.min(ry) + 1) {
+            let (rx, ry) = (rx - inset, ry - inset);
+            if rx == ry {
+                self.pixels.circle(cx, cy, rx, color);
+            } else {
+                self.pixels.ellipse(cx, cy, rx, ry, color);
+            }
+        }
+    }
+
+    /// Centre pixel and radii in pixels for an ellipse in cell coordinates
+    fn ellipse_pixels(&self, center: Point, rx: f32, ry: f32) -> (i32, i32, i32, i32) {
+        let (px, py) = self.pixels_per_cell();
+        let (cx, cy) = self.to_pixels(center);
+        (
+            cx.floor() as i32,
+            cy.floor() as i32,
+            (rx * px as f32).round() as i32,
+            (ry * py as f32).round() as i32,
+        )
+    }
+
     /// Draw text
     pub fn draw_text(&mut self, x: u16, y: u16, text: &str, color: Option<Color>) {
         for (i, ch) in text.chars().enumerate() {
@@ -127,25 +443,100 @@ impl SvgCanvas {
         }
     }
 
+    /// Draw text with a full style
+    pub fn draw_styled_text(&mut self, x: u16, y: u16, text: &str, style: Style) {
+        for (i, ch) in text.chars().enumerate() {
+            self.draw_styled_char(x + i as u16, y, ch, style);
+        }
+    }
+
+    /// Styled lines, one per row, with runs of equally styled cells merged into spans
+    pub fn to_lines(&self) -> Vec<Line<'static>> {
+        (0..self.height)
+            .map(|y| {
+                let mut spans: Vec<Span<'static>> = Vec::new();
+                let mut run = String::new();
+                let mut run_style = None;
+                for x in 0..self.width {
+                    let Some((ch, style)) = self.styled_cell(x, y) else {
+                        continue;
+                    };
+                    if run_style.is_some_and(|run_style| run_style != style) {
+                        spans.push(Span::styled(std::mem::take(&mut run), run_style.unwrap_or_default()));
+                    }
+                    run.push(ch);
+                    run_style = Some(style);
+                }
+                if let Some(style) = run_style {
+                    spans.push(Span::styled(run, style));
+                }
+                Line::from(spans)
+            })
+            .collect()
+    }
+
+    /// The canvas drawn into a buffer of its own size, for exporting
+    ///
+    /// Returns `None` for canvases over 65,535 cells: ratatui indexes buffer
+    /// cells with a `u16`, so a buffer that large cannot be built. Render
+    /// parts of such a canvas into smaller buffers instead.
+    pub fn to_buffer(&self) -> Option<Buffer> {
+        if self.width as u32 * self.height as u32 > u16::MAX as u32 {
+            return None;
+        }
+        // `Rect::new` would shrink the area to fit instead of failing
+        let area = Rect { x: 0, y: 0, width: self.width, height: self.height };
+        let mut buffer = Buffer::empty(area);
+        Widget::render(self, area, &mut buffer);
+        Some(buffer)
+    }
+
     /// Render the canvas to a frame
+    ///
+    /// Same as `frame.render_widget(&canvas, area)`.
     pub fn render(&self, frame: &mut Frame, area: Rect) {
-        use ratatui::text::{Line, Span};
-        use ratatui::widgets::Paragraph;
+        frame.render_widget(self, area);
+    }
+}
 
-        let mut lines = Vec::new();
-        for row in &self.buffer {
-            let line_str: String = row.iter().collect();
-            lines.push(Line::from(line_str));
+impl Widget for &SvgCanvas {
+    /// Write cells straight into the buffer; the canvas is clipped to `area`
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = area.intersection(buf.area);
+        for y in 0..self.height.min(area.height) {
+            for x in 0..self.width.min(area.width) {
+                if let Some((ch, style)) = self.styled_cell(x, y) {
+                    buf[(area.x + x, area.y + y)].set_char(ch).set_style(style);
+                }
+            }
         }
+    }
+}
 
-        let paragraph = Paragraph::new(lines);
-        frame.render_widget(paragraph, area);
+impl Widget for SvgCanvas {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Widget::render(&self, area, buf);
     }
 }
 
+/// Line offsets for a stroke width: 0, +1, -1, +2, ...
+fn thickness_offsets(width: u8) -> impl Iterator<Item = i32> {
+    (0..width.max(1) as i32).map(|offset| if offset % 2 == 1 { offset / 2 + 1 } else { -(offset / 2) })
+}
+
+/// Check whether a point is inside an axis-aligned ellipse
+fn in_ellipse(point: Point, center: Point, rx: f32, ry: f32) -> bool {
+    if rx <= 0.0 || ry <= 0.0 {
+        return false;
+    }
+    let (dx, dy) = ((point.x - center.x) / rx, (point.y - center.y) / ry);
+    dx * dx + dy * dy <= 1.0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ratatui::style::Modifier;
 
     #[test]
     fn test_canvas_creation() {
@@ -170,4 +561,111 @@ mod tests {
         assert_eq!(canvas.buffer[6][2], '└');
         assert_eq!(canvas.buffer[6][11], '┘');
     }
+
+    #[test]
+    fn test_draw_line_glyphs_and_width() {
+        let mut canvas = SvgCanvas::new(10, 10);
+        canvas.draw_line(Point::new(1.0, 1.0), Point::new(6.0, 1.0), 1, None);
+        assert_eq!(canvas.buffer[1][1..7].iter().collect::<String>(), "──────");
+
+        canvas.draw_line(Point::new(0.0, 3.0), Point::new(3.0, 6.0), 1, None);
+        assert_eq!((canvas.buffer[3][0], canvas.buffer[6][3]), ('╲', '╲'));
+
+        canvas.draw_line(Point::new(8.0, 0.0), Point::new(8.0, 4.0), 2, None);
+        assert_eq!((canvas.buffer[2][8], canvas.buffer[2][9], canvas.buffer[2][7]), ('┃', '┃', ' '));
+
+        // Off-canvas parts are clipped
+        canvas.draw_line(Point::new(-5.0, 9.0), Point::new(20.0, 9.0), 1, None);
+        assert_eq!(canvas.buffer[9][0], '─');
+
+        // Far-away endpoints are clipped before stepping
+        let mut canvas = SvgCanvas::new(10, 3);
+        canvas.draw_line(Point::new(-3e9, 1.0), Point::new(3e9, 1.0), 1, None);
+        assert_eq!(canvas.buffer[1].iter().collect::<String>(), "──────────");
+        let mut braille = SvgCanvas::new(4, 2).resolution(Resolution::Braille);
+        braille.draw_line(Point::new(-3e9, 0.0), Point::new(3e9, 2.0), 1, Some(Color::White));
+        assert!(braille.pixels.is_set(0, 4));
+    }
+
+    #[test]
+    fn test_braille_line_and_text_overlay() {
+        let mut canvas = SvgCanvas::new(3, 1).resolution(Resolution::Braille);
+        canvas.draw_line(Point::new(0.0, 0.0), Point::new(3.0, 0.0), 1, Some(Color::Red));
+        assert_eq!(canvas.cell(0, 0), Some(('⠉', Some(Color::Red))));
+        assert_eq!(canvas.cell(2, 0), Some(('⠉', Some(Color::Red))));
+
+        canvas.draw_char(1, 0, 'x', None);
+        assert_eq!(canvas.cell(1, 0), Some(('x', None)));
+
+        let mut canvas = SvgCanvas::new(4, 2).resolution(Resolution::HalfBlock);
+        canvas.fill_polygon(
+            &[Point::new(0.0, 0.0), Point::new(4.0, 0.0), Point::new(4.0, 1.5), Point::new(0.0, 1.5)],
+            Some(Color::Blue),
+        );
+        assert_eq!(canvas.cell(0, 0).unwrap().0, '█');
+        assert_eq!(canvas.cell(0, 1).unwrap().0, '▀');
+    }
+
+    #[test]
+    fn test_styles_merge_into_spans() {
+        let mut canvas = SvgCanvas::new(5, 1).background(Color::Black);
+        canvas.draw_text(0, 0, "ab", Some(Color::Red));
+        canvas.draw_styled_char(2, 0, 'c', Style::default().fg(Color::Red).bg(Color::Blue).add_modifier(Modifier::BOLD));
+
+        let lines = canvas.to_lines();
+        let spans: Vec<(&str, Style)> = lines[0].spans.iter().map(|span| (span.content.as_ref(), span.style)).collect();
+        assert_eq!(
+            spans,
+            [
+                ("ab", Style::default().fg(Color::Red).bg(Color::Black)),
+                ("c", Style::default().fg(Color::Red).bg(Color::Blue).add_modifier(Modifier::BOLD)),
+                ("  ", Style::default().bg(Color::Black)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_widget_writes_cells_and_clips() {
+        let mut canvas = SvgCanvas::new(4, 2).resolution(Resolution::HalfBlock);
+        canvas.pixels_mut().set(0, 0, Color::Red);
+        canvas.pixels_mut().set(0, 1, Color::Blue);
+        canvas.draw_char(1, 0, 'x', Some(Color::Green));
+        canvas.set_style(1, 0, Style::default().add_modifier(Modifier::ITALIC));
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 3, 3));
+        Widget::render(&canvas, Rect::new(1, 1, 5, 5), &mut buf);
+        let cell = &buf[(1, 1)];
+        assert_eq!((cell.symbol(), cell.fg, cell.bg), ("▀", Color::Red, Color::Blue));
+        let cell = &buf[(2, 1)];
+        assert_eq!((cell.symbol(), cell.fg), ("x", Color::Green));
+        assert!(cell.modifier.contains(Modifier::ITALIC));
+        assert_eq!(buf[(0, 0)].symbol(), " ");
+    }
+
+    #[test]
+    fn test_antialiased_edges_fade_into_background() {
+        let mut canvas = SvgCanvas::new(4, 1)
+            .resolution(Resolution::Block)
+            .antialias(true)
+            .background(Color::Rgb(0, 0, 0));
+        canvas.draw_line(Point::new(0.0, 0.25), Point::new(4.0, 0.75), 1, Some(Color::Rgb(200, 200, 200)));
+        let colors: Vec<Color> = (0..4).map(|x| canvas.cell(x, 0).unwrap().1.unwrap()).collect();
+        assert!(colors.iter().all(|color| *color != Color::Rgb(0, 0, 0)));
+        assert!(colors.iter().any(|color| *color != Color::Rgb(200, 200, 200)));
+    }
+
+    #[test]
+    fn test_ellipse_at_cell_and_braille_resolution() {
+        let mut canvas = SvgCanvas::new(7, 3);
+        canvas.fill_ellipse(Point::new(3.5, 1.5), 3.5, 1.5, Some(Color::Blue));
+        let row: String = (0..7).map(|x| canvas.cell(x, 1).unwrap().0).collect();
+        assert_eq!(row, "███████");
+        assert_eq!(canvas.cell(0, 0).unwrap().0, ' ');
+
+        let mut canvas = SvgCanvas::new(4, 2).resolution(Resolution::Braille);
+        canvas.draw_ellipse(Point::new(2.0, 1.0), 1.5, 0.75, 1, Some(Color::Green));
+        let pixels = canvas.pixels_mut();
+        assert!(pixels.is_set(1, 4) && pixels.is_set(7, 4));
+        assert!(!pixels.is_set(4, 4));
+    }
 }
@@ -0,0 +1,489 @@
+//! Exporting rendered cells as SVG, HTML or ANSI text
+//!
+//! Exporters read a ratatui `Buffer`, so they work on anything that can be
+//! drawn: a `TestBackend` frame, a component rendered into a scratch buffer,
+//! or an `SvgCanvas` via `SvgCanvas::to_buffer`. Nothing touches the
+//! terminal, which makes them usable in tests and docs tooling.
+//!
+//! Raster formats such as PNG are out of scope: they need font rendering,
+//! so rasterize the SVG output with an external tool instead.
+
+use crate::style::color_rgb;
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier, Style};
+use std::fmt::Write as _;
+use std::path::Path;
+use unicode_width::UnicodeWidthStr;
+
+/// Cells in a row that share a style
+#[derive(Debug, Clone, PartialEq)]
+struct Run {
+    column: u16,
+    /// Width in cells, which differs from the character count for wide glyphs
+    width: u16,
+    text: String,
+    style: Style,
+}
+
+/// Writes buffers as SVG, HTML or ANSI text
+///
+/// ```
+/// use ratatui::{backend::TestBackend, widgets::Paragraph, Terminal};
+/// use ruensh::svg::Exporter;
+///
+/// let mut terminal = Terminal::new(TestBackend::new(20, 2)).unwrap();
+/// terminal.draw(|frame| frame.render_widget(Paragraph::new("hello"), frame.area())).unwrap();
+/// let svg = Exporter::new().font_size(16.0).svg(terminal.backend().buffer());
+/// assert!(svg.contains(">hello</text>"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Exporter {
+    font_family: String,
+    font_size: f32,
+    cell_width: f32,
+    cell_height: f32,
+    foreground: Color,
+    background: Color,
+    title: String,
+}
+
+impl Default for Exporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Exporter {
+    /// Create an exporter with a 14px monospace font on a black background
+    pub fn new() -> Self {
+        Self {
+            font_family: "ui-monospace, Menlo, Consolas, monospace".to_string(),
+            font_size: 14.0,
+            cell_width: 8.4,
+            cell_height: 17.0,
+            foreground: Color::Rgb(229, 229, 229),
+            background: Color::Rgb(0, 0, 0),
+            title: "ruensh".to_string(),
+        }
+    }
+
+    /// Set the CSS font family
+    pub fn font_family(mut self, family: impl Into<String>) -> Self {
+        self.font_family = family.into();
+        self
+    }
+
+    /// Set the font size in pixels
+    pub fn font_size(mut self, size: f32) -> Self {
+        self.font_size = size;
+        self
+    }
+
+    /// Set the size of one cell in pixels
+    ///
+    /// SVG places every cell on this grid; HTML uses the height as line
+    /// height and leaves widths to the font.
+    pub fn cell_size(mut self, width: f32, height: f32) -> Self {
+        self.cell_width = width;
+        self.cell_height = height;
+        self
+    }
+
+    /// Set the colour used for `Color::Reset` text
+    pub fn foreground(mut self, color: Color) -> Self {
+        self.foreground = color;
+        self
+    }
+
+    /// Set the page colour, also used for `Color::Reset` backgrounds
+    pub fn background(mut self, color: Color) -> Self {
+        self.background = color;
+        self
+    }
+
+    /// Set the HTML page title
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// SVG document with a background rectangle per coloured run and a `<text>` per non-blank run
+    pub fn svg(&self, buffer: &Buffer) -> String {
+        let area = buffer.area;
+        let width = number(area.width as f32 * self.cell_width);
+        let height = number(area.height as f32 * self.cell_height);
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="{}" font-size="{}">"#,
+            escape(&self.font_family),
+            number(self.font_size),
+        );
+        let background = self.hex(self.background, self.background);
+        let _ = writeln!(out, r#"<rect width="100%" height="100%" fill="{background}"/>"#);
+
+        for (row, runs) in rows(buffer).into_iter().enumerate() {
+            let top = row as f32 * self.cell_height;
+            for run in &runs {
+                let (_, bg) = self.colors(run.style);
+                if bg == background {
+                    continue;
+                }
+                let _ = writeln!(
+                    out,
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{bg}"/>"#,
+                    number(run.column as f32 * self.cell_width),
+                    number(top),
+                    number(run.width as f32 * self.cell_width),
+                    number(self.cell_height),
+                );
+            }
+            // Baseline near the bottom of the cell, leaving room for descenders
+            let baseline = top + (self.cell_height + self.font_size * 0.7) / 2.0;
+            for run in &runs {
+                let modifiers = run.style.add_modifier - run.style.sub_modifier;
+                // Surrounding blanks are covered by the background; spaces are one cell each
+                let text = run.text.trim_matches(' ');
+                if text.is_empty() || modifiers.contains(Modifier::HIDDEN) {
+                    continue;
+                }
+                let column = run.column + (run.text.len() - run.text.trim_start_matches(' ').len()) as u16;
+                let width = run.width - (run.text.len() - text.len()) as u16;
+                let (fg, _) = self.colors(run.style);
+                let mut attributes = format!(r#"fill="{fg}""#);
+                if modifiers.contains(Modifier::BOLD) {
+                    attributes.push_str(r#" font-weight="bold""#);
+                }
+                if modifiers.contains(Modifier::ITALIC) {
+                    attributes.push_str(r#" font-style="italic""#);
+                }
+                if let Some(decoration) = decoration(modifiers) {
+                    let _ = write!(attributes, r#" text-decoration="{decoration}""#);
+                }
+                if modifiers.contains(Modifier::DIM) {
+                    attributes.push_str(r#" opacity="0.5""#);
+                }
+                let _ = writeln!(
+                    out,
+                    r#"<text x="{}" y="{}" textLength="{}" lengthAdjust="spacingAndGlyphs" xml:space="preserve" {attributes}>{}</text>"#,
+                    number(column as f32 * self.cell_width),
+                    number(baseline),
+                    number(width as f32 * self.cell_width),
+                    escape(text),
+                );
+            }
+        }
+        out.push_str("</svg>\n");
+        out
+    }
+
+    /// Standalone HTML page with the cells in a `<pre>` of inline-styled spans
+    pub fn html(&self, buffer: &Buffer) -> String {
+        let background = self.hex(self.background, self.background);
+        let foreground = self.hex(self.foreground, self.foreground);
+        let mut out = String::new();
+        let _ = writeln!(out, "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">");
+        let _ = writeln!(out, "<title>{}</title>\n</head>", escape(&self.title));
+        let _ = writeln!(out, r#"<body style="margin:0;background:{background}">"#);
+        let _ = write!(
+            out,
+            r#"<pre style="margin:0;font-family:{};font-size:{}px;line-height:{}px;color:{foreground};background:{background}">"#,
+            escape(&self.font_family),
+            number(self.font_size),
+            number(self.cell_height),
+        );
+        for (row, runs) in rows(buffer).into_iter().enumerate() {
+            if row > 0 {
+                out.push('\n');
+            }
+            for run in runs {
+                let modifiers = run.style.add_modifier - run.style.sub_modifier;
+                let text = if modifiers.contains(Modifier::HIDDEN) {
+                    " ".repeat(run.width as usize)
+                } else {
+                    run.text
+                };
+                let (fg, bg) = self.colors(run.style);
+                let mut css = Vec::new();
+                if fg != foreground {
+                    css.push(format!("color:{fg}"));
+                }
+                if bg != background {
+                    css.push(format!("background:{bg}"));
+                }
+                if modifiers.contains(Modifier::BOLD) {
+                    css.push("font-weight:bold".to_string());
+                }
+                if modifiers.contains(Modifier::ITALIC) {
+                    css.push("font-style:italic".to_string());
+                }
+                if let Some(decoration) = decoration(modifiers) {
+                    css.push(format!("text-decoration:{decoration}"));
+                }
+                if modifiers.contains(Modifier::DIM) {
+                    css.push("opacity:0.5".to_string());
+                }
+                if css.is_empty() {
+                    out.push_str(&escape(&text));
+                } else {
+                    let _ = write!(out, r#"<span style="{}">{}</span>"#, css.join(";"), escape(&text));
+                }
+            }
+        }
+        out.push_str("</pre>\n</body>\n</html>\n");
+        out
+    }
+
+    /// Text with ANSI SGR escapes, one line per row, reset at the end of each line
+    ///
+    /// Colours are written as the buffer holds them, so `cat` shows the
+    /// frame with the viewer's own palette.
+    pub fn ansi(&self, buffer: &Buffer) -> String {
+        let mut out = String::new();
+        for runs in rows(buffer) {
+            for run in runs {
+                if run.style == Style::default() {
+                    out.push_str("\x1b[0m");
+                } else {
+                    let _ = write!(out, "\x1b[{}m", sgr(run.style));
+                }
+                out.push_str(&run.text);
+            }
+            out.push_str("\x1b[0m\n");
+        }
+        out
+    }
+
+    /// Write a buffer to a file, choosing the format from the extension
+    ///
+    /// `.svg` writes SVG, `.html` and `.htm` write HTML, anything else ANSI text.
+    pub fn save(&self, buffer: &Buffer, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let path = path.as_ref();
+        let extension = path.extension().and_then(|extension| extension.to_str()).unwrap_or("");
+        let contents = match extension.to_ascii_lowercase().as_str() {
+            "svg" => self.svg(buffer),
+            "html" | "htm" => self.html(buffer),
+            _ => self.ansi(buffer),
+        };
+        std::fs::write(path, contents)
+    }
+
+    /// Foreground and background as CSS hex, with reverse video applied
+    fn colors(&self, style: Style) -> (String, String) {
+        let fg = self.hex(style.fg.unwrap_or(Color::Reset), self.foreground);
+        let bg = self.hex(style.bg.unwrap_or(Color::Reset), self.background);
+        let modifiers = style.add_modifier - style.sub_modifier;
+        if modifiers.contains(Modifier::REVERSED) {
+            (bg, fg)
+        } else {
+            (fg, bg)
+        }
+    }
+
+    fn hex(&self, color: Color, reset: Color) -> String {
+        let [r, g, b] = color_rgb(color).or_else(|| color_rgb(reset)).unwrap_or([0, 0, 0]);
+        format!("#{r:02x}{g:02x}{b:02x}")
+    }
+}
+
+/// Split each row into runs of equally styled cells
+///
+/// Wide glyphs cover the cells after them, which are left out of the text.
+fn rows(buffer: &Buffer) -> Vec<Vec<Run>> {
+    let area = buffer.area;
+    (area.top()..area.bottom())
+        .map(|y| {
+            let mut runs: Vec<Run> = Vec::new();
+            let mut x = area.left();
+            while x < area.right() {
+                let cell = &buffer[(x, y)];
+                let (symbol, style) = if cell.skip {
+                    (" ", Style::default())
+                } else {
+                    (cell.symbol(), cell.style())
+                };
+                let width = symbol.width().clamp(1, (area.right() - x) as usize) as u16;
+                match runs.last_mut() {
+                    Some(run) if run.style == style => {
+                        run.text.push_str(symbol);
+                        run.width += width;
+                    }
+                    _ => runs.push(Run {
+                        column: x - area.left(),
+                        width,
+                        text: symbol.to_string(),
+                        style,
+                    }),
+                }
+                x += width;
+            }
+            runs
+        })
+        .collect()
+}
+
+/// SGR parameters for a style, starting with a reset
+fn sgr(style: Style) -> String {
+    const MODIFIERS: [(Modifier, u8); 9] = [
+        (Modifier::BOLD, 1),
+        (Modifier::DIM, 2),
+        (Modifier::ITALIC, 3),
+        (Modifier::UNDERLINED, 4),
+        (Modifier::SLOW_BLINK, 5),
+        (Modifier::RAPID_BLINK, 6),
+        (Modifier::REVERSED, 7),
+        (Modifier::HIDDEN, 8),
+        (Modifier::CROSSED_OUT, 9),
+    ];
+    let modifiers = style.add_modifier - style.sub_modifier;
+    let mut codes = vec!["0".to_string()];
+    codes.extend(
+        MODIFIERS
+            .iter()
+            .filter(|(modifier, _)| modifiers.contains(*modifier))
+            .map(|(_, code)| code.to_string()),
+    );
+    codes.extend(style.fg.and_then(|color| color_code(color, 30)));
+    codes.extend(style.bg.and_then(|color| color_code(color, 40)));
+    codes.join(";")
+}
+
+/// SGR code for a colour; `base` is 30 for foreground and 40 for background
+fn color_code(color: Color, base: u8) -> Option<String> {
+    let named = |index: u8| {
+        if index < 8 {
+            base + index
+        } else {
+            base + 60 + index - 8
+        }
+    };
+    Some(match color {
+        Color::Reset => return None,
+        Color::Black => named(0).to_string(),
+        Color::Red => named(1).to_string(),
+        Color::Green => named(2).to_string(),
+        Color::Yellow => named(3).to_string(),
+        Color::Blue => named(4).to_string(),
+        Color::Magenta => named(5).to_string(),
+        Color::Cyan => named(6).to_string(),
+        Color::Gray => named(7).to_string(),
+        Color::DarkGray => named(8).to_string(),
+        Color::LightRed => named(9).to_string(),
+        Color::LightGreen => named(10).to_string(),
+        Color::LightYellow => named(11).to_string(),
+        Color::LightBlue => named(12).to_string(),
+        Color::LightMagenta => named(13).to_string(),
+        Color::LightCyan => named(14).to_string(),
+        Color::White => named(15).to_string(),
+        Color::Indexed(index) => format!("{};5;{}", base + 8, index),
+        Color::Rgb(r, g, b) => format!("{};2;{};{};{}", base + 8, r, g, b),
+    })
+}
+
+/// CSS `text-decoration` for underline and strike-through
+fn decoration(modifiers: Modifier) -> Option<&'static str> {
+    match (modifiers.contains(Modifier::UNDERLINED), modifiers.contains(Modifier::CROSSED_OUT)) {
+        (true, true) => Some("underline line-through"),
+        (true, false) => Some("underline"),
+        (false, true) => Some("line-through"),
+        (false, false) => None,
+    }
+}
+
+/// Format a coordinate with at most two decimals
+fn number(value: f32) -> String {
+    let rounded = (value * 100.0).round() / 100.0;
+    format!("{}", rounded)
+}
+
+/// Escape text for XML and HTML
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            ch => out.push(ch),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::svg::SvgCanvas;
+    use ratatui::layout::Rect;
+
+    fn sample() -> Buffer {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 6, 2));
+        buffer.set_string(0, 0, "a<b", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD));
+        buffer.set_string(4, 0, "界", Style::default().bg(Color::Rgb(0, 0, 255)));
+        buffer.set_string(0, 1, "ok", Style::default().add_modifier(Modifier::REVERSED));
+        buffer
+    }
+
+    #[test]
+    fn test_rows_merge_runs_and_skip_wide_tails() {
+        let rows = rows(&sample());
+        let texts: Vec<(&str, u16, u16)> = rows[0]
+            .iter()
+            .map(|run| (run.text.as_str(), run.column, run.width))
+            .collect();
+        assert_eq!(texts, [("a<b", 0, 3), (" ", 3, 1), ("界", 4, 2)]);
+        assert_eq!(rows[1].len(), 2);
+    }
+
+    #[test]
+    fn test_svg_places_cells_on_the_grid() {
+        let svg = Exporter::new().cell_size(10.0, 20.0).font_size(16.0).svg(&sample());
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="60" height="40""#));
+        assert!(svg.contains(r##"<rect x="40" y="0" width="20" height="20" fill="#0000ff"/>"##));
+        assert!(svg.contains(
+            r##"<text x="0" y="15.6" textLength="30" lengthAdjust="spacingAndGlyphs" xml:space="preserve" fill="#cd0000" font-weight="bold">a&lt;b</text>"##
+        ));
+        // Reverse video swaps the default colours
+        assert!(svg.contains(r##"<rect x="0" y="20" width="20" height="20" fill="#e5e5e5"/>"##));
+        assert!(svg.contains(r##"fill="#000000">ok</text>"##));
+        assert!(svg.contains(r#"<text x="40" y="15.6" textLength="20""#));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn test_html_and_ansi() {
+        let html = Exporter::new().title("Frame & co").html(&sample());
+        assert!(html.contains("<title>Frame &amp; co</title>"));
+        assert!(html.contains(
+            r##"<span style="color:#cd0000;font-weight:bold">a&lt;b</span> <span style="background:#0000ff">界</span>"##
+        ));
+        assert!(html.contains("\n<span style=\"color:#000000;background:#e5e5e5\">ok</span>    </pre>"));
+
+        let ansi = Exporter::new().ansi(&sample());
+        assert_eq!(
+            ansi,
+            "\x1b[0;1;31ma<b\x1b[0m \x1b[0;48;2;0;0;255m界\x1b[0m\n\x1b[0;7mok\x1b[0m    \x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn test_canvas_export_and_save() {
+        let mut canvas = SvgCanvas::new(4, 1);
+        canvas.draw_text(0, 0, "hi", Some(Color::Green));
+        let buffer = canvas.to_buffer().unwrap();
+        assert_eq!(buffer.area, Rect::new(0, 0, 4, 1));
+        assert!(Exporter::new().svg(&buffer).contains(r##"fill="#00cd00">hi</text>"##));
+
+        let path = std::env::temp_dir().join(format!("ruensh-export-{}.html", std::process::id()));
+        Exporter::new().save(&buffer, &path).unwrap();
+        let written = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(written.starts_with("<!DOCTYPE html>"));
+
+        let largest = SvgCanvas::new(255, 257).to_buffer().unwrap();
+        assert_eq!(largest.area, Rect::new(0, 0, 255, 257));
+        assert_eq!(largest.content.len(), 255 * 257);
+        assert!(SvgCanvas::new(300, 300).to_buffer().is_none());
+    }
+}
@@ -0,0 +1,934 @@
+//! Importing SVG documents as shapes
+//!
+//! Supports the subset used by logos and icons: `rect`, `circle`,
+//! `ellipse`, `line`, `polyline`, `polygon` and `path` elements, nested
+//! groups with `transform`, and `fill`, `stroke`, `stroke-width` and `color`
+//! given as attributes or in a `style` attribute. Gradients, text, `use`,
+//! clipping and masks are ignored.
+//!
+//! Geometry is kept in the document's coordinates and scaled when shapes
+//! are produced, so one document can be drawn at any size.
+
+use super::canvas::SvgCanvas;
+use super::path_data::{self, Subpath};
+use super::shapes::{Line, Path as PathShape, Point, Rectangle, Shape};
+use super::Resolution;
+use ratatui::style::Color;
+use std::f32::consts::PI;
+use std::fmt;
+use std::path::Path;
+
+/// Height of a terminal cell relative to its width
+const DEFAULT_CELL_ASPECT: f32 = 2.0;
+
+/// Points used to flatten a full ellipse
+const ELLIPSE_SEGMENTS: usize = 48;
+
+/// Elements whose content is never drawn directly
+const SKIPPED: &[&str] = &[
+    "defs", "clipPath", "mask", "symbol", "marker", "pattern", "title", "desc", "metadata", "style",
+    "script", "linearGradient", "radialGradient", "filter", "text",
+];
+
+/// Error reading an SVG document
+#[derive(Debug)]
+pub enum SvgError {
+    /// The document is not well-formed or has no `<svg>` element
+    Syntax { line: usize, message: String },
+    /// The file could not be read
+    Io(std::io::Error),
+}
+
+impl fmt::Display for SvgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SvgError::Syntax { line, message } => write!(f, "line {}: {}", line, message),
+            SvgError::Io(err) => write!(f, "cannot read SVG file: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for SvgError {}
+
+impl From<std::io::Error> for SvgError {
+    fn from(err: std::io::Error) -> Self {
+        SvgError::Io(err)
+    }
+}
+
+/// 2D affine transform `[a c e; b d f]`, as in SVG's `matrix(a b c d e f)`
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Transform {
+    a: f32,
+    b: f32,
+    c: f32,
+    d: f32,
+    e: f32,
+    f: f32,
+}
+
+impl Transform {
+    const IDENTITY: Transform = Transform { a: 1.0, b: 0.0, c: 0.0, d: 1.0, e: 0.0, f: 0.0 };
+
+    /// Apply `self` after `inner`
+    fn then(self, inner: Transform) -> Transform {
+        Transform {
+            a: self.a * inner.a + self.c * inner.b,
+            b: self.b * inner.a + self.d * inner.b,
+            c: self.a * inner.c + self.c * inner.d,
+            d: self.b * inner.c + self.d * inner.d,
+            e: self.a * inner.e + self.c * inner.f + self.e,
+            f: self.b * inner.e + self.d * inner.f + self.f,
+        }
+    }
+
+    fn apply(self, point: Point) -> Point {
+        Point::new(
+            self.a * point.x + self.c * point.y + self.e,
+            self.b * point.x + self.d * point.y + self.f,
+        )
+    }
+
+    /// Check whether the transform keeps rectangles axis-aligned
+    fn is_axis_aligned(self) -> bool {
+        self.b == 0.0 && self.c == 0.0
+    }
+
+    /// Average scale factor, for stroke widths
+    fn scale(self) -> f32 {
+        (self.a * self.d - self.b * self.c).abs().sqrt()
+    }
+
+    /// Parse a `transform` attribute; unknown functions are skipped
+    fn parse(text: &str) -> Transform {
+        let mut transform = Transform::IDENTITY;
+        let mut rest = text;
+        while let Some(open) = rest.find('(') {
+            let name = rest[..open].trim_matches(|c: char| c.is_whitespace() || c == ',');
+            let Some(close) = rest[open..].find(')') else {
+                break;
+            };
+            let args = path_data::numbers(&rest[open + 1..open + close]);
+            let arg = |index: usize, default: f32| args.get(index).copied().unwrap_or(default);
+            let step = match name {
+                "matrix" if args.len() == 6 => Transform {
+                    a: args[0],
+                    b: args[1],
+                    c: args[2],
+                    d: args[3],
+                    e: args[4],
+                    f: args[5],
+                },
+                "translate" => Transform { e: arg(0, 0.0), f: arg(1, 0.0), ..Transform::IDENTITY },
+                "scale" => {
+                    let x = arg(0, 1.0);
+                    Transform { a: x, d: arg(1, x), ..Transform::IDENTITY }
+                }
+                "rotate" => {
+                    let (sin, cos) = arg(0, 0.0).to_radians().sin_cos();
+                    let (cx, cy) = (arg(1, 0.0), arg(2, 0.0));
+                    let rotation = Transform { a: cos, b: sin, c: -sin, d: cos, ..Transform::IDENTITY };
+                    let to = Transform { e: cx, f: cy, ..Transform::IDENTITY };
+                    let back = Transform { e: -cx, f: -cy, ..Transform::IDENTITY };
+                    to.then(rotation).then(back)
+                }
+                "skewX" => Transform { c: arg(0, 0.0).to_radians().tan(), ..Transform::IDENTITY },
+                "skewY" => Transform { b: arg(0, 0.0).to_radians().tan(), ..Transform::IDENTITY },
+                _ => Transform::IDENTITY,
+            };
+            // Functions apply right to left: the list is a product in reading order
+            transform = transform.then(step);
+            rest = &rest[open + close + 1..];
+        }
+        transform
+    }
+}
+
+/// Fill or stroke paint before `currentColor` is resolved
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Paint {
+    None,
+    Color(Color),
+    CurrentColor,
+}
+
+/// Inherited presentation state
+#[derive(Debug, Clone, Copy)]
+struct Context {
+    transform: Transform,
+    fill: Paint,
+    stroke: Paint,
+    stroke_width: f32,
+    /// Value of `currentColor`; `Reset` is the terminal's foreground
+    color: Color,
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Context {
+            transform: Transform::IDENTITY,
+            fill: Paint::Color(Color::Black),
+            stroke: Paint::None,
+            stroke_width: 1.0,
+            color: Color::Reset,
+        }
+    }
+}
+
+impl Context {
+    fn resolve(&self, paint: Paint) -> Option<Color> {
+        match paint {
+            Paint::None => None,
+            Paint::Color(color) => Some(color),
+            Paint::CurrentColor => Some(self.color),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Geometry {
+    /// Axis-aligned rectangle in document coordinates
+    Rect { x: f32, y: f32, width: f32, height: f32, radius: f32 },
+    Line(Point, Point),
+    Subpaths(Vec<Subpath>),
+}
+
+/// A drawable element in document coordinates
+#[derive(Debug, Clone, PartialEq)]
+struct Item {
+    geometry: Geometry,
+    fill: Option<Color>,
+    stroke: Option<Color>,
+    stroke_width: f32,
+}
+
+/// A parsed SVG document
+///
+/// ```
+/// use ruensh::svg::{Resolution, SvgCanvas, SvgDocument};
+///
+/// let logo = SvgDocument::parse(
+///     r#"<svg viewBox="0 0 24 24"><circle cx="12" cy="12" r="10" fill="red"/></svg>"#,
+/// ).unwrap();
+/// let mut canvas = SvgCanvas::new(24, 12).resolution(Resolution::Braille);
+/// logo.render(&mut canvas);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgDocument {
+    /// `(x, y, width, height)` of the area to show
+    view_box: (f32, f32, f32, f32),
+    items: Vec<Item>,
+    cell_aspect: f32,
+}
+
+impl SvgDocument {
+    /// Parse an SVG document
+    pub fn parse(source: &str) -> Result<Self, SvgError> {
+        let tags = tokenize(source)?;
+        let mut items = Vec::new();
+        let mut stack: Vec<(String, Context)> = Vec::new();
+        let mut view_box = None;
+        let mut size = (None, None);
+        let mut seen_root = false;
+        // Depth inside an element whose content is not drawn
+        let mut skip = 0usize;
+
+        for tag in tags {
+            match tag.kind {
+                TagKind::Close => {
+                    match stack.pop() {
+                        Some((name, _)) if name == tag.name => {}
+                        Some((name, _)) => {
+                            return Err(syntax(tag.line, format!("`</{}>` closes `<{}>`", tag.name, name)))
+                        }
+                        None => return Err(syntax(tag.line, format!("`</{}>` has no opening tag", tag.name))),
+                    }
+                    skip = skip.saturating_sub(1);
+                    continue;
+                }
+                TagKind::Open | TagKind::Empty => {}
+            }
+
+            let parent = stack.last().map(|(_, context)| *context).unwrap_or_default();
+            let attribute = |name: &str| tag.attribute(name);
+            let hidden = attribute("display") == Some("none");
+            let is_root = !seen_root && tag.name == "svg";
+            seen_root |= is_root;
+
+            if skip == 0 && (SKIPPED.contains(&tag.name.as_str()) || hidden) {
+                skip = 1;
+            } else if skip > 0 {
+                skip += 1;
+            }
+            let context = context_for(&tag, parent);
+
+            if is_root {
+                view_box = attribute("viewBox")
+                    .map(path_data::numbers)
+                    .filter(|numbers| numbers.len() == 4 && numbers[2] > 0.0 && numbers[3] > 0.0)
+                    .map(|numbers| (numbers[0], numbers[1], numbers[2], numbers[3]));
+                size = (attribute("width").and_then(length), attribute("height").and_then(length));
+            } else if skip == 0 && seen_root {
+                if let Some(item) = item_for(&tag, &context) {
+                    items.push(item);
+                }
+            }
+
+            if tag.kind == TagKind::Open {
+                stack.push((tag.name.clone(), context));
+            } else {
+                skip = skip.saturating_sub(1);
+            }
+        }
+
+        if !seen_root {
+            return Err(syntax(1, "no <svg> element"));
+        }
+        if let Some((name, _)) = stack.last() {
+            return Err(syntax(source.lines().count().max(1), format!("`<{}>` is never closed", name)));
+        }
+
+        let view_box = view_box
+            .or(match size {
+                (Some(width), Some(height)) if width > 0.0 && height > 0.0 => Some((0.0, 0.0, width, height)),
+                _ => None,
+            })
+            .unwrap_or_else(|| bounds(&items));
+        Ok(SvgDocument {
+            view_box,
+            items,
+            cell_aspect: DEFAULT_CELL_ASPECT,
+        })
+    }
+
+    /// Read and parse an SVG file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SvgError> {
+        let source = std::fs::read_to_string(path)?;
+        SvgDocument::parse(&source)
+    }
+
+    /// Set how much taller than wide a terminal cell is; the default is 2
+    ///
+    /// Shapes are squashed vertically by this factor so circles stay round.
+    pub fn cell_aspect(mut self, aspect: f32) -> Self {
+        self.cell_aspect = aspect.max(f32::EPSILON);
+        self
+    }
+
+    /// The view box as `(x, y, width, height)`
+    ///
+    /// Taken from the `viewBox` attribute, then `width`/`height`, and
+    /// otherwise the bounds of the drawing.
+    pub fn view_box(&self) -> (f32, f32, f32, f32) {
+        self.view_box
+    }
+
+    /// Number of drawable elements
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Check whether the document has nothing to draw
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Shapes scaled to fit `width` × `height` cells, centred and keeping the aspect ratio
+    ///
+    /// Stroke widths are converted to pixels of `resolution`.
+    pub fn shapes(&self, width: u16, height: u16, resolution: Resolution) -> Vec<Box<dyn Shape>> {
+        let (vx, vy, vw, vh) = self.view_box;
+        let scale_x = (width as f32 / vw).min(height as f32 * self.cell_aspect / vh);
+        let scale_y = scale_x / self.cell_aspect;
+        let offset_x = (width as f32 - vw * scale_x) / 2.0;
+        let offset_y = (height as f32 - vh * scale_y) / 2.0;
+        let map = |point: Point| {
+            Point::new((point.x - vx) * scale_x + offset_x, (point.y - vy) * scale_y + offset_y)
+        };
+        let pixels_x = resolution.pixels_per_cell().0 as f32;
+        let stroke_width = |width: f32| {
+            if width <= 0.0 {
+                0
+            } else {
+                (width * scale_x * pixels_x).round().clamp(1.0, u8::MAX as f32) as u8
+            }
+        };
+
+        let mut shapes: Vec<Box<dyn Shape>> = Vec::new();
+        for item in &self.items {
+            let width = stroke_width(item.stroke_width);
+            let stroke = item.stroke.filter(|_| width > 0);
+            match &item.geometry {
+                Geometry::Rect { x, y, width: w, height: h, radius } => {
+                    let origin = map(Point::new(*x, *y));
+                    let mut rectangle = Rectangle::new(origin.x, origin.y, w * scale_x, h * scale_y);
+                    rectangle.fill = item.fill;
+                    rectangle.stroke = stroke;
+                    rectangle.stroke_width = width;
+                    rectangle.corner_radius = radius * scale_x;
+                    shapes.push(Box::new(rectangle));
+                }
+                Geometry::Line(from, to) => {
+                    if let Some(stroke) = stroke {
+                        shapes.push(Box::new(Line::new(map(*from), map(*to)).stroke(stroke).stroke_width(width)));
+                    }
+                }
+                Geometry::Subpaths(subpaths) => {
+                    if let Some(fill) = item.fill {
+                        // One polygon per element so the even-odd rule cuts holes;
+                        // closing each subpath makes the joins cancel out
+                        let mut path = PathShape::new().fill(fill);
+                        path.stroke = None;
+                        for subpath in subpaths {
+                            path.points.extend(subpath.points.iter().map(|point| map(*point)));
+                            path.points.push(map(subpath.points[0]));
+                        }
+                        shapes.push(Box::new(path));
+                    }
+                    if let Some(stroke) = stroke {
+                        for subpath in subpaths {
+                            let mut path = PathShape::new().stroke(stroke).stroke_width(width);
+                            path.points = subpath.points.iter().map(|point| map(*point)).collect();
+                            path.closed = subpath.closed;
+                            shapes.push(Box::new(path));
+                        }
+                    }
+                }
+            }
+        }
+        shapes
+    }
+
+    /// Draw the document onto a canvas, scaled to fit it
+    pub fn render(&self, canvas: &mut SvgCanvas) {
+        let resolution = match canvas.pixels_per_cell() {
+            (2, 4) => Resolution::Braille,
+            (2, 2) => Resolution::Block,
+            (1, 2) => Resolution::HalfBlock,
+            _ => Resolution::CharCell,
+        };
+        for shape in self.shapes(canvas.width(), canvas.height(), resolution) {
+            shape.render(canvas);
+        }
+    }
+}
+
+/// Context for an element: the parent's, updated by its own attributes
+fn context_for(tag: &Tag, parent: Context) -> Context {
+    let mut context = parent;
+    if let Some(transform) = tag.attribute("transform") {
+        context.transform = parent.transform.then(Transform::parse(transform));
+    }
+    if let Some(color) = tag.attribute("color").and_then(parse_color) {
+        context.color = color;
+    }
+    // Presentation attributes first, then the style attribute overrides them
+    let mut properties: Vec<(&str, &str)> = ["fill", "stroke", "stroke-width"]
+        .into_iter()
+        .filter_map(|name| tag.attribute(name).map(|value| (name, value)))
+        .collect();
+    if let Some(style) = tag.attribute("style") {
+        properties.extend(style.split(';').filter_map(|declaration| {
+            let (name, value) = declaration.split_once(':')?;
+            Some((name.trim(), value.trim()))
+        }));
+    }
+    for (name, value) in properties {
+        match name {
+            "fill" => context.fill = parse_paint(value).unwrap_or(context.fill),
+            "stroke" => context.stroke = parse_paint(value).unwrap_or(context.stroke),
+            "stroke-width" => context.stroke_width = length(value).unwrap_or(context.stroke_width),
+            "color" => context.color = parse_color(value).unwrap_or(context.color),
+            _ => {}
+        }
+    }
+    context
+}
+
+/// Geometry and paint for a shape element, in document coordinates
+fn item_for(tag: &Tag, context: &Context) -> Option<Item> {
+    let number = |name: &str| tag.attribute(name).and_then(length).unwrap_or(0.0);
+    let transform = context.transform;
+    let ellipse = |cx: f32, cy: f32, rx: f32, ry: f32| {
+        let points = (0..ELLIPSE_SEGMENTS)
+            .map(|step| {
+                let angle = 2.0 * PI * step as f32 / ELLIPSE_SEGMENTS as f32;
+                transform.apply(Point::new(cx + rx * angle.cos(), cy + ry * angle.sin()))
+            })
+            .collect();
+        Geometry::Subpaths(vec![Subpath { points, closed: true }])
+    };
+    let transformed = |subpaths: Vec<Subpath>| {
+        Geometry::Subpaths(
+            subpaths
+                .into_iter()
+                .map(|subpath| Subpath {
+                    points: subpath.points.into_iter().map(|point| transform.apply(point)).collect(),
+                    closed: subpath.closed,
+                })
+                .collect(),
+        )
+    };
+
+    let geometry = match tag.name.as_str() {
+        "rect" => {
+            let (x, y, width, height) = (number("x"), number("y"), number("width"), number("height"));
+            if width <= 0.0 || height <= 0.0 {
+                return None;
+            }
+            let rx = tag.attribute("rx").and_then(length);
+            let ry = tag.attribute("ry").and_then(length);
+            let rx = rx.or(ry).unwrap_or(0.0).min(width / 2.0);
+            let ry = ry.or(Some(rx)).unwrap_or(0.0).min(height / 2.0);
+            if transform.is_axis_aligned() {
+                let start = transform.apply(Point::new(x, y));
+                let end = transform.apply(Point::new(x + width, y + height));
+                Geometry::Rect {
+                    x: start.x.min(end.x),
+                    y: start.y.min(end.y),
+                    width: (end.x - start.x).abs(),
+                    height: (end.y - start.y).abs(),
+                    radius: rx.min(ry) * transform.a.abs(),
+                }
+            } else {
+                transformed(vec![rounded_rect(x, y, width, height, rx, ry)])
+            }
+        }
+        "circle" => {
+            let radius = number("r");
+            if radius <= 0.0 {
+                return None;
+            }
+            ellipse(number("cx"), number("cy"), radius, radius)
+        }
+        "ellipse" => {
+            let (rx, ry) = (number("rx"), number("ry"));
+            if rx <= 0.0 || ry <= 0.0 {
+                return None;
+            }
+            ellipse(number("cx"), number("cy"), rx, ry)
+        }
+        "line" => Geometry::Line(
+            transform.apply(Point::new(number("x1"), number("y1"))),
+            transform.apply(Point::new(number("x2"), number("y2"))),
+        ),
+        "polyline" | "polygon" => {
+            let numbers = path_data::numbers(tag.attribute("points")?);
+            let points: Vec<Point> = numbers.chunks_exact(2).map(|pair| Point::new(pair[0], pair[1])).collect();
+            if points.len() < 2 {
+                return None;
+            }
+            transformed(vec![Subpath { points, closed: tag.name == "polygon" }])
+        }
+        "path" => {
+            let subpaths = path_data::parse(tag.attribute("d")?);
+            if subpaths.is_empty() {
+                return None;
+            }
+            transformed(subpaths)
+        }
+        _ => return None,
+    };
+
+    // Lines have no interior
+    let fill = match geometry {
+        Geometry::Line(..) => None,
+        _ => context.resolve(context.fill),
+    };
+    Some(Item {
+        geometry,
+        fill,
+        stroke: context.resolve(context.stroke),
+        stroke_width: context.stroke_width * transform.scale(),
+    })
+}
+
+/// Outline of a rectangle with elliptical corners
+fn rounded_rect(x: f32, y: f32, width: f32, height: f32, rx: f32, ry: f32) -> Subpath {
+    let (right, bottom) = (x + width, y + height);
+    if rx <= 0.0 || ry <= 0.0 {
+        let points = vec![Point::new(x, y), Point::new(right, y), Point::new(right, bottom), Point::new(x, bottom)];
+        return Subpath { points, closed: true };
+    }
+    let mut points = vec![Point::new(x + rx, y)];
+    let corners = [
+        (Point::new(right - rx, y), Point::new(right, y + ry)),
+        (Point::new(right, bottom - ry), Point::new(right - rx, bottom)),
+        (Point::new(x + rx, bottom), Point::new(x, bottom - ry)),
+        (Point::new(x, y + ry), Point::new(x + rx, y)),
+    ];
+    for (start, end) in corners {
+        points.push(start);
+        points.extend(path_data::arc(start, end, rx, ry, 0.0, false, true));
+    }
+    points.pop();
+    Subpath { points, closed: true }
+}
+
+/// Bounds of every item as a view box; a unit box when there is nothing to draw
+fn bounds(items: &[Item]) -> (f32, f32, f32, f32) {
+    let mut points = Vec::new();
+    for item in items {
+        match &item.geometry {
+            Geometry::Rect { x, y, width, height, .. } => {
+                points.push(Point::new(*x, *y));
+                points.push(Point::new(x + width, y + height));
+            }
+            Geometry::Line(from, to) => points.extend([*from, *to]),
+            Geometry::Subpaths(subpaths) => {
+                points.extend(subpaths.iter().flat_map(|subpath| subpath.points.iter().copied()))
+            }
+        }
+    }
+    if points.is_empty() {
+        return (0.0, 0.0, 1.0, 1.0);
+    }
+    let min_x = points.iter().map(|point| point.x).fold(f32::INFINITY, f32::min);
+    let min_y = points.iter().map(|point| point.y).fold(f32::INFINITY, f32::min);
+    let max_x = points.iter().map(|point| point.x).fold(f32::NEG_INFINITY, f32::max);
+    let max_y = points.iter().map(|point| point.y).fold(f32::NEG_INFINITY, f32::max);
+    (min_x, min_y, (max_x - min_x).max(f32::EPSILON), (max_y - min_y).max(f32::EPSILON))
+}
+
+/// Parse a length in user units; `px` is accepted, other units are not
+fn length(value: &str) -> Option<f32> {
+    value.trim().trim_end_matches("px").trim().parse().ok()
+}
+
+/// Parse a fill or stroke value; `None` for values that cannot be drawn here, like gradients
+fn parse_paint(value: &str) -> Option<Paint> {
+    match value.trim() {
+        "none" | "transparent" => Some(Paint::None),
+        "currentColor" => Some(Paint::CurrentColor),
+        value => parse_color(value).map(Paint::Color),
+    }
+}
+
+/// Parse `#rgb`, `#rrggbb`, `rgb(r, g, b)` or a CSS colour keyword
+fn parse_color(value: &str) -> Option<Color> {
+    let value = value.trim();
+    if let Some(hex) = value.strip_prefix('#') {
+        let digits: Vec<u8> = hex
+            .chars()
+            .map(|c| c.to_digit(16).map(|digit| digit as u8))
+            .collect::<Option<_>>()?;
+        return match digits[..] {
+            [r, g, b] => Some(Color::Rgb(r * 17, g * 17, b * 17)),
+            [r1, r2, g1, g2, b1, b2] => Some(Color::Rgb(r1 * 16 + r2, g1 * 16 + g2, b1 * 16 + b2)),
+            _ => None,
+        };
+    }
+    if let Some(args) = value.strip_prefix("rgb(").and_then(|rest| rest.strip_suffix(')')) {
+        let channels: Vec<u8> = args
+            .split(',')
+            .map(|channel| {
+                let channel = channel.trim();
+                let value = match channel.strip_suffix('%') {
+                    Some(percent) => percent.trim().parse::<f32>().ok()? * 2.55,
+                    None => channel.parse::<f32>().ok()?,
+                };
+                Some(value.round().clamp(0.0, 255.0) as u8)
+            })
+            .collect::<Option<_>>()?;
+        return match channels[..] {
+            [r, g, b] => Some(Color::Rgb(r, g, b)),
+            _ => None,
+        };
+    }
+    let (r, g, b) = match value.to_ascii_lowercase().as_str() {
+        "black" => (0, 0, 0),
+        "white" => (255, 255, 255),
+        "red" => (255, 0, 0),
+        "green" => (0, 128, 0),
+        "lime" => (0, 255, 0),
+        "blue" => (0, 0, 255),
+        "yellow" => (255, 255, 0),
+        "cyan" | "aqua" => (0, 255, 255),
+        "magenta" | "fuchsia" => (255, 0, 255),
+        "gray" | "grey" => (128, 128, 128),
+        "silver" => (192, 192, 192),
+        "maroon" => (128, 0, 0),
+        "olive" => (128, 128, 0),
+        "teal" => (0, 128, 128),
+        "navy" => (0, 0, 128),
+        "purple" => (128, 0, 128),
+        "orange" => (255, 165, 0),
+        "pink" => (255, 192, 203),
+        "brown" => (165, 42, 42),
+        "gold" => (255, 215, 0),
+        _ => return None,
+    };
+    Some(Color::Rgb(r, g, b))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TagKind {
+    Open,
+    Close,
+    /// Self-closing, like `<rect/>`
+    Empty,
+}
+
+#[derive(Debug, Clone)]
+struct Tag {
+    name: String,
+    kind: TagKind,
+    attributes: Vec<(String, String)>,
+    line: usize,
+}
+
+impl Tag {
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+fn syntax(line: usize, message: impl Into<String>) -> SvgError {
+    SvgError::Syntax {
+        line,
+        message: message.into(),
+    }
+}
+
+/// Line numbers of increasing byte offsets, counted since the last call
+struct Lines<'a> {
+    source: &'a str,
+    offset: usize,
+    line: usize,
+}
+
+impl Lines<'_> {
+    fn at(&mut self, position: usize) -> usize {
+        self.line += self.source[self.offset..position].matches('\n').count();
+        self.offset = position;
+        self.line
+    }
+}
+
+/// Split XML into tags, dropping text, comments, declarations and CDATA
+fn tokenize(source: &str) -> Result<Vec<Tag>, SvgError> {
+    // Skipped constructs as (opening, closing, name); order matters for `<!`
+    const SKIPPED: [(&str, &str, &str); 4] = [
+        ("<!--", "-->", "comment"),
+        ("<![CDATA[", "]]>", "CDATA section"),
+        ("<?", "?>", "processing instruction"),
+        ("<!", ">", "declaration"),
+    ];
+    let mut lines = Lines { source, offset: 0, line: 1 };
+    let mut tags = Vec::new();
+    let mut position = 0;
+    while let Some(offset) = source[position..].find('<') {
+        position += offset;
+        let rest = &source[position..];
+        if let Some((_, end, what)) = SKIPPED.iter().find(|(start, ..)| rest.starts_with(start)) {
+            position = match rest.find(end) {
+                Some(offset) => position + offset + end.len(),
+                None => return Err(syntax(lines.at(position), format!("unterminated {}", what))),
+            };
+        } else {
+            let line = lines.at(position);
+            let (tag, length) = parse_tag(rest).ok_or_else(|| syntax(line, "malformed tag"))?;
+            tags.push(Tag { line, ..tag });
+            position += length;
+        }
+    }
+    Ok(tags)
+}
+
+/// Parse one tag at the start of `text`, returning it and its length in bytes
+fn parse_tag(text: &str) -> Option<(Tag, usize)> {
+    let name_end = |text: &str| text.find(|c: char| c.is_whitespace() || c == '/' || c == '>').unwrap_or(text.len());
+
+    if let Some(rest) = text.strip_prefix("</") {
+        let end = rest.find('>')?;
+        let tag = Tag {
+            name: rest[..end].trim().to_string(),
+            kind: TagKind::Close,
+            attributes: Vec::new(),
+            line: 0,
+        };
+        return Some((tag, end + 3));
+    }
+
+    let mut position = 1;
+    let name = &text[position..position + name_end(&text[position..])];
+    if name.is_empty() {
+        return None;
+    }
+    position += name.len();
+    let mut attributes = Vec::new();
+    loop {
+        let rest = &text[position..];
+        let trimmed = rest.trim_start();
+        position += rest.len() - trimmed.len();
+        if trimmed.starts_with("/>") {
+            let tag = Tag { name: name.to_string(), kind: TagKind::Empty, attributes, line: 0 };
+            return Some((tag, position + 2));
+        }
+        if trimmed.starts_with('>') {
+            let tag = Tag { name: name.to_string(), kind: TagKind::Open, attributes, line: 0 };
+            return Some((tag, position + 1));
+        }
+
+        let key_end = trimmed.find(|c: char| c == '=' || c.is_whitespace() || c == '>' || c == '/')?;
+        let key = &trimmed[..key_end];
+        if key.is_empty() {
+            return None;
+        }
+        let after_key = trimmed[key_end..].trim_start();
+        let value_part = after_key.strip_prefix('=')?.trim_start();
+        let quote = value_part.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let value_end = value_part[1..].find(quote)?;
+        attributes.push((key.to_string(), unescape(&value_part[1..1 + value_end])));
+        let consumed = trimmed.len() - value_part.len() + value_end + 2;
+        position += consumed;
+    }
+}
+
+/// Decode the predefined XML entities and numeric character references
+fn unescape(value: &str) -> String {
+    if !value.contains('&') {
+        return value.to_string();
+    }
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';') else {
+            break;
+        };
+        let entity = &rest[1..end];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|decimal| decimal.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ICON: &str = r##"<?xml version="1.0"?>
+<!-- an icon -->
+<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 20 10" width="200">
+  <defs><rect width="5" height="5"/></defs>
+  <g fill="none" stroke="currentColor" color="#ff0000" transform="translate(1 1)">
+    <rect x="0" y="0" width="8" height="8" rx="2"/>
+    <line x1="10" y1="0" x2="18" y2="0" style="stroke: blue; stroke-width: 2"/>
+    <polygon points="10,8 14,2 18,8" fill="rgb(0, 128, 0)"/>
+  </g>
+  <path d="M0 0 H20 V10 H0 Z M5 2 H15 V8 H5 Z" fill="orange" display="none"/>
+</svg>"##;
+
+    #[test]
+    fn test_parses_elements_groups_and_paint() {
+        let document = SvgDocument::parse(ICON).unwrap();
+        assert_eq!(document.view_box(), (0.0, 0.0, 20.0, 10.0));
+        assert_eq!(document.len(), 3);
+
+        let rect = &document.items[0];
+        assert_eq!(
+            rect.geometry,
+            Geometry::Rect { x: 1.0, y: 1.0, width: 8.0, height: 8.0, radius: 2.0 }
+        );
+        assert_eq!((rect.fill, rect.stroke), (None, Some(Color::Rgb(255, 0, 0))));
+
+        let line = &document.items[1];
+        assert_eq!(line.geometry, Geometry::Line(Point::new(11.0, 1.0), Point::new(19.0, 1.0)));
+        assert_eq!((line.stroke, line.stroke_width), (Some(Color::Rgb(0, 0, 255)), 2.0));
+
+        let polygon = &document.items[2];
+        assert_eq!(polygon.fill, Some(Color::Rgb(0, 128, 0)));
+        let Geometry::Subpaths(subpaths) = &polygon.geometry else { panic!("expected subpaths") };
+        assert_eq!(subpaths[0].points[1], Point::new(15.0, 3.0));
+        assert!(subpaths[0].closed);
+    }
+
+    #[test]
+    fn test_transforms_compose() {
+        let transform = Transform::parse("translate(10, 0) rotate(90) scale(2)");
+        let point = transform.apply(Point::new(1.0, 0.0));
+        assert!((point.x - 10.0).abs() < 1e-5 && (point.y - 2.0).abs() < 1e-5);
+        assert!(!transform.is_axis_aligned());
+        assert!((transform.scale() - 2.0).abs() < 1e-5);
+
+        let about = Transform::parse("rotate(180 5 5)").apply(Point::new(0.0, 0.0));
+        assert!((about.x - 10.0).abs() < 1e-4 && (about.y - 10.0).abs() < 1e-4);
+
+        // A rotated rect becomes a polygon
+        let document = SvgDocument::parse(
+            r##"<svg><rect width="4" height="2" transform="rotate(90)" fill="#000"/></svg>"##,
+        )
+        .unwrap();
+        assert!(matches!(document.items[0].geometry, Geometry::Subpaths(_)));
+        let (x, y, width, height) = document.view_box();
+        assert!((x + 2.0).abs() < 1e-5 && y.abs() < 1e-5 && (width - 2.0).abs() < 1e-5 && (height - 4.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_fit_and_render_keep_aspect() {
+        let document = SvgDocument::parse(
+            r#"<svg viewBox="0 0 10 10"><circle cx="5" cy="5" r="5" fill="red"/></svg>"#,
+        )
+        .unwrap();
+        // A square viewBox in a 20×10 area fills it: 10 units = 20 columns = 10 rows
+        let shapes = document.shapes(40, 10, Resolution::CharCell);
+        let (min, max) = shapes[0].bounds();
+        assert!((min.x - 10.0).abs() < 0.01 && (max.x - 30.0).abs() < 0.01);
+        assert!(min.y.abs() < 0.01 && (max.y - 10.0).abs() < 0.01);
+
+        let mut canvas = SvgCanvas::new(40, 10).resolution(Resolution::Braille);
+        document.render(&mut canvas);
+        assert_eq!(canvas.cell(20, 5), Some(('⣿', Some(Color::Rgb(255, 0, 0)))));
+        assert_eq!(canvas.cell(2, 5).unwrap().0, ' ');
+    }
+
+    #[test]
+    fn test_even_odd_holes_and_errors() {
+        let document = SvgDocument::parse(
+            r##"<svg viewBox="0 0 20 10"><path d="M0 0 H20 V10 H0 Z M5 2 H15 V8 H5 Z" fill="#fff"/></svg>"##,
+        )
+        .unwrap();
+        let mut canvas = SvgCanvas::new(20, 5);
+        document.render(&mut canvas);
+        assert_eq!(canvas.cell(1, 2).unwrap().0, '█');
+        assert_eq!(canvas.cell(10, 2).unwrap().0, ' ');
+
+        assert!(matches!(SvgDocument::parse("<g/>"), Err(SvgError::Syntax { .. })));
+        assert!(matches!(
+            SvgDocument::parse("<svg>\n<g></svg>"),
+            Err(SvgError::Syntax { line: 2, .. })
+        ));
+        assert!(matches!(SvgDocument::parse("<svg><!-- x</svg>"), Err(SvgError::Syntax { .. })));
+        let tags = tokenize("<svg>\n<!-- a\nb -->\n<rect/>\n\n<g>\n</g></svg>").unwrap();
+        let lines: Vec<usize> = tags.iter().map(|tag| tag.line).collect();
+        assert_eq!(lines, [1, 4, 6, 7, 7]);
+        assert!(matches!(tokenize("<svg>\n\n<!-- x"), Err(SvgError::Syntax { line: 3, .. })));
+        assert_eq!(unescape("a &amp; &#x41;&#66; &bogus;"), "a & AB &bogus;");
+    }
+}
@@ -8,11 +8,18 @@ mod shapes;
 mod effects;
 mod animations;
 mod transitions;
+mod raster;
+mod path_data;
+mod import;
+mod export;
 
 pub use canvas::SvgCanvas;
+pub use raster::PixelBuffer;
+pub use import::{SvgDocument, SvgError};
+pub use export::Exporter;
 pub use shapes::{Shape, Rectangle, Circle, Line, Path, Point};
-pub use effects::{GlowEffect, GradientFill, Filter};
-pub use animations::{Animation, Easing};
+pub use effects::{GlowEffect, GlowIntensity, GradientFill, GradientDirection, Filter, BlurLevel};
+pub use animations::{Animation, AnimationState, Easing};
 pub use transitions::{
     Transition, Keyframe, TransitionState, TransitionPresets, 
     TransitionManager, Interpolate
@@ -25,8 +32,10 @@ pub enum Resolution {
     CharCell,
     /// High-resolution Braille patterns (2×4 pixels per cell)
     Braille,
-    /// Block elements for filled shapes
+    /// Quadrant block elements (2×2 pixels per cell)
     Block,
+    /// Half blocks (1×2 pixels per cell)
+    HalfBlock,
 }
 
 /// Color scheme presets for futuristic UIs
@@ -130,4 +139,21 @@ impl ColorScheme {
             Self::Holographic { glow, .. } => *glow,
         }
     }
+
+    /// Get (primary, secondary, accent, background, glow) colors
+    pub fn colors(&self) -> (ratatui::style::Color, ratatui::style::Color, ratatui::style::Color, ratatui::style::Color, ratatui::style::Color) {
+        match self {
+            Self::CyberPunk { primary, secondary, accent, background, glow }
+            | Self::NeonTokyo { primary, secondary, accent, background, glow }
+            | Self::Matrix { primary, secondary, accent, background, glow }
+            | Self::Holographic { primary, secondary, accent, background, glow } => {
+                (*primary, *secondary, *accent, *background, *glow)
+            }
+        }
+    }
+
+    /// Get the matching `style::Theme`
+    pub fn theme(&self) -> crate::style::Theme {
+        crate::style::Theme::from(self)
+    }
 }
@@ -0,0 +1,399 @@
+//! SVG path data (`d` attribute) parsing and curve flattening
+//!
+//! Every command is supported: M, L, H, V, C, S, Q, T, A and Z, absolute and
+//! relative. Curves and arcs are flattened into straight segments. As in
+//! browsers, a syntax error ends the path but keeps what came before it.
+
+use super::shapes::Point;
+use std::f32::consts::PI;
+
+/// Segments per Bézier curve
+const CURVE_SEGMENTS: usize = 16;
+
+/// Largest angle covered by one arc segment
+const ARC_STEP: f32 = PI / 16.0;
+
+/// A flattened subpath
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Subpath {
+    pub points: Vec<Point>,
+    pub closed: bool,
+}
+
+/// Parse path data into flattened subpaths
+pub(super) fn parse(data: &str) -> Vec<Subpath> {
+    let mut parser = Parser {
+        cursor: Cursor { bytes: data.as_bytes(), position: 0 },
+        subpaths: Vec::new(),
+        current: Point::new(0.0, 0.0),
+        start: Point::new(0.0, 0.0),
+        control: None,
+    };
+    parser.run();
+    parser.subpaths.retain(|subpath| subpath.points.len() > 1);
+    parser.subpaths
+}
+
+/// Parse a list of numbers such as a `points` attribute, stopping at the first error
+pub(super) fn numbers(text: &str) -> Vec<f32> {
+    let mut cursor = Cursor { bytes: text.as_bytes(), position: 0 };
+    std::iter::from_fn(|| cursor.number()).collect()
+}
+
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl Cursor<'_> {
+    fn skip_separators(&mut self) {
+        while self
+            .bytes
+            .get(self.position)
+            .is_some_and(|byte| byte.is_ascii_whitespace() || *byte == b',')
+        {
+            self.position += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_separators();
+        self.bytes.get(self.position).copied()
+    }
+
+    /// Check whether a number follows, for implicitly repeated commands
+    fn at_number(&mut self) -> bool {
+        self.peek().is_some_and(|byte| byte.is_ascii_digit() || matches!(byte, b'-' | b'+' | b'.'))
+    }
+
+    fn number(&mut self) -> Option<f32> {
+        self.skip_separators();
+        let start = self.position;
+        let bytes = self.bytes;
+        let mut end = start;
+        if matches!(bytes.get(end), Some(b'-' | b'+')) {
+            end += 1;
+        }
+        let digits = |end: &mut usize| {
+            let from = *end;
+            while bytes.get(*end).is_some_and(u8::is_ascii_digit) {
+                *end += 1;
+            }
+            *end > from
+        };
+        let mut any = digits(&mut end);
+        // A second dot starts the next number: "1.5.5" is 1.5 and .5
+        if bytes.get(end) == Some(&b'.') {
+            end += 1;
+            any |= digits(&mut end);
+        }
+        if !any {
+            return None;
+        }
+        if matches!(bytes.get(end), Some(b'e' | b'E')) {
+            let mut exponent = end + 1;
+            if matches!(bytes.get(exponent), Some(b'-' | b'+')) {
+                exponent += 1;
+            }
+            if digits(&mut exponent) {
+                end = exponent;
+            }
+        }
+        self.position = end;
+        std::str::from_utf8(&bytes[start..end]).ok()?.parse().ok()
+    }
+
+    /// Arc flags are single digits and may be written without separators
+    fn flag(&mut self) -> Option<bool> {
+        let flag = match self.peek()? {
+            b'0' => false,
+            b'1' => true,
+            _ => return None,
+        };
+        self.position += 1;
+        Some(flag)
+    }
+
+    fn point(&mut self) -> Option<Point> {
+        Some(Point::new(self.number()?, self.number()?))
+    }
+}
+
+/// Resolve a point relative to `current` for lowercase commands
+fn resolve(current: Point, point: Point, relative: bool) -> Point {
+    if relative {
+        Point::new(current.x + point.x, current.y + point.y)
+    } else {
+        point
+    }
+}
+
+struct Parser<'a> {
+    cursor: Cursor<'a>,
+    subpaths: Vec<Subpath>,
+    current: Point,
+    /// Start of the current subpath, where Z returns to
+    start: Point,
+    /// Control point of the previous curve and whether it was cubic, for S and T
+    control: Option<(Point, bool)>,
+}
+
+impl Parser<'_> {
+    fn run(&mut self) {
+        let mut command = None;
+        loop {
+            let Some(byte) = self.cursor.peek() else {
+                return;
+            };
+            if byte.is_ascii_alphabetic() {
+                command = Some(byte);
+                self.cursor.position += 1;
+            } else if command.is_none() {
+                return;
+            }
+            let Some(current) = command else {
+                return;
+            };
+            if self.command(current).is_none() {
+                return;
+            }
+            // After a moveto, further coordinate pairs are linetos
+            command = match current {
+                b'M' => Some(b'L'),
+                b'm' => Some(b'l'),
+                b'Z' | b'z' => None,
+                other => Some(other),
+            };
+            if command.is_some() && !self.cursor.at_number() {
+                command = None;
+            }
+        }
+    }
+
+    fn line_to(&mut self, point: Point) {
+        if self.subpaths.last().is_none_or(|subpath| subpath.closed) {
+            // Drawing after Z continues from the subpath's start
+            self.subpaths.push(Subpath { points: vec![self.current], closed: false });
+        }
+        if let Some(subpath) = self.subpaths.last_mut() {
+            subpath.points.push(point);
+        }
+        self.current = point;
+    }
+
+    fn command(&mut self, command: u8) -> Option<()> {
+        let relative = command.is_ascii_lowercase();
+        let mut control = None;
+        match command.to_ascii_uppercase() {
+            b'M' => {
+                let point = resolve(self.current, self.cursor.point()?, relative);
+                self.subpaths.push(Subpath { points: vec![point], closed: false });
+                self.current = point;
+                self.start = point;
+            }
+            b'L' => {
+                let point = resolve(self.current, self.cursor.point()?, relative);
+                self.line_to(point);
+            }
+            b'H' => {
+                let x = self.cursor.number()?;
+                let x = if relative { self.current.x + x } else { x };
+                self.line_to(Point::new(x, self.current.y));
+            }
+            b'V' => {
+                let y = self.cursor.number()?;
+                let y = if relative { self.current.y + y } else { y };
+                self.line_to(Point::new(self.current.x, y));
+            }
+            b'C' | b'S' => {
+                let first = if command.eq_ignore_ascii_case(&b'C') {
+                    resolve(self.current, self.cursor.point()?, relative)
+                } else {
+                    self.reflected_control(true)
+                };
+                let second = resolve(self.current, self.cursor.point()?, relative);
+                let end = resolve(self.current, self.cursor.point()?, relative);
+                let start = self.current;
+                for step in 1..=CURVE_SEGMENTS {
+                    let t = step as f32 / CURVE_SEGMENTS as f32;
+                    self.line_to(cubic(start, first, second, end, t));
+                }
+                control = Some((second, true));
+            }
+            b'Q' | b'T' => {
+                let handle = if command.eq_ignore_ascii_case(&b'Q') {
+                    resolve(self.current, self.cursor.point()?, relative)
+                } else {
+                    self.reflected_control(false)
+                };
+                let end = resolve(self.current, self.cursor.point()?, relative);
+                let start = self.current;
+                for step in 1..=CURVE_SEGMENTS {
+                    let t = step as f32 / CURVE_SEGMENTS as f32;
+                    self.line_to(quadratic(start, handle, end, t));
+                }
+                control = Some((handle, false));
+            }
+            b'A' => {
+                let rx = self.cursor.number()?;
+                let ry = self.cursor.number()?;
+                let rotation = self.cursor.number()?;
+                let large_arc = self.cursor.flag()?;
+                let sweep = self.cursor.flag()?;
+                let end = resolve(self.current, self.cursor.point()?, relative);
+                let start = self.current;
+                for point in arc(start, end, rx, ry, rotation, large_arc, sweep) {
+                    self.line_to(point);
+                }
+            }
+            b'Z' => {
+                if let Some(subpath) = self.subpaths.last_mut().filter(|subpath| !subpath.closed) {
+                    subpath.closed = true;
+                }
+                self.current = self.start;
+            }
+            _ => return None,
+        }
+        self.control = control;
+        Some(())
+    }
+
+    /// First control point of a smooth curve: the previous one mirrored
+    fn reflected_control(&self, cubic: bool) -> Point {
+        match self.control {
+            Some((control, was_cubic)) if was_cubic == cubic => {
+                Point::new(2.0 * self.current.x - control.x, 2.0 * self.current.y - control.y)
+            }
+            _ => self.current,
+        }
+    }
+}
+
+fn cubic(p0: Point, p1: Point, p2: Point, p3: Point, t: f32) -> Point {
+    let u = 1.0 - t;
+    let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+    Point::new(
+        a * p0.x + b * p1.x + c * p2.x + d * p3.x,
+        a * p0.y + b * p1.y + c * p2.y + d * p3.y,
+    )
+}
+
+fn quadratic(p0: Point, p1: Point, p2: Point, t: f32) -> Point {
+    let u = 1.0 - t;
+    let (a, b, c) = (u * u, 2.0 * u * t, t * t);
+    Point::new(a * p0.x + b * p1.x + c * p2.x, a * p0.y + b * p1.y + c * p2.y)
+}
+
+/// Points along an elliptical arc, excluding the start
+///
+/// Converts the endpoint form to centre form as in the SVG specification,
+/// appendix F.6.5, scaling up radii that are too small to reach the end.
+pub(super) fn arc(start: Point, end: Point, rx: f32, ry: f32, rotation: f32, large_arc: bool, sweep: bool) -> Vec<Point> {
+    if start == end {
+        return Vec::new();
+    }
+    let (mut rx, mut ry) = (rx.abs(), ry.abs());
+    if rx == 0.0 || ry == 0.0 {
+        return vec![end];
+    }
+
+    let (sin, cos) = rotation.to_radians().sin_cos();
+    let (dx, dy) = ((start.x - end.x) / 2.0, (start.y - end.y) / 2.0);
+    let x1 = cos * dx + sin * dy;
+    let y1 = -sin * dx + cos * dy;
+
+    let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+    if lambda > 1.0 {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+
+    let numerator = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+    let denominator = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+    let sign = if large_arc == sweep { -1.0 } else { 1.0 };
+    let coefficient = sign * (numerator / denominator).max(0.0).sqrt();
+    let (cx1, cy1) = (coefficient * rx * y1 / ry, -coefficient * ry * x1 / rx);
+    let cx = cos * cx1 - sin * cy1 + (start.x + end.x) / 2.0;
+    let cy = sin * cx1 + cos * cy1 + (start.y + end.y) / 2.0;
+
+    let angle = |ux: f32, uy: f32, vx: f32, vy: f32| (ux * vy - uy * vx).atan2(ux * vx + uy * vy);
+    let theta = angle(1.0, 0.0, (x1 - cx1) / rx, (y1 - cy1) / ry);
+    let mut delta = angle((x1 - cx1) / rx, (y1 - cy1) / ry, (-x1 - cx1) / rx, (-y1 - cy1) / ry);
+    if !sweep && delta > 0.0 {
+        delta -= 2.0 * PI;
+    } else if sweep && delta < 0.0 {
+        delta += 2.0 * PI;
+    }
+
+    let segments = (delta.abs() / ARC_STEP).ceil().max(1.0) as usize;
+    let mut points: Vec<Point> = (1..segments)
+        .map(|step| {
+            let t = theta + delta * step as f32 / segments as f32;
+            let (x, y) = (rx * t.cos(), ry * t.sin());
+            Point::new(cx + cos * x - sin * y, cy + sin * x + cos * y)
+        })
+        .collect();
+    points.push(end);
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: Point, b: Point) -> bool {
+        (a.x - b.x).abs() < 1e-3 && (a.y - b.y).abs() < 1e-3
+    }
+
+    #[test]
+    fn test_lines_relative_and_implicit_commands() {
+        let subpaths = parse("M10,10 20 10 v5 h-10z m5-2 l1-1.5.5 0");
+        assert_eq!(subpaths.len(), 2);
+        assert_eq!(
+            subpaths[0].points,
+            [Point::new(10.0, 10.0), Point::new(20.0, 10.0), Point::new(20.0, 15.0), Point::new(10.0, 15.0)]
+        );
+        assert!(subpaths[0].closed);
+        // Relative moveto after Z starts from the closed subpath's start
+        assert_eq!(subpaths[1].points, [Point::new(15.0, 8.0), Point::new(16.0, 6.5), Point::new(16.5, 6.5)]);
+        assert!(!subpaths[1].closed);
+    }
+
+    #[test]
+    fn test_curves_end_on_their_endpoints() {
+        let subpaths = parse("M0 0C0 10 10 10 10 0S20-10 20 0Q25 5 30 0T40 0");
+        let points = &subpaths[0].points;
+        assert_eq!(points.len(), 1 + 4 * CURVE_SEGMENTS);
+        assert!(close(points[CURVE_SEGMENTS], Point::new(10.0, 0.0)));
+        assert!(close(points[CURVE_SEGMENTS / 2], Point::new(5.0, 7.5)));
+        // The smooth cubic mirrors (10, 10) to (10, -10)
+        assert!(close(points[CURVE_SEGMENTS + CURVE_SEGMENTS / 2], Point::new(15.0, -7.5)));
+        assert!(close(points[4 * CURVE_SEGMENTS], Point::new(40.0, 0.0)));
+        // The smooth quadratic mirrors (25, 5) to (35, -5)
+        assert!(close(points[3 * CURVE_SEGMENTS + CURVE_SEGMENTS / 2], Point::new(35.0, -2.5)));
+    }
+
+    #[test]
+    fn test_arcs_and_compact_flags() {
+        let subpaths = parse("M0 0A10 10 0 0110 10");
+        let points = &subpaths[0].points;
+        assert!(close(*points.last().unwrap(), Point::new(10.0, 10.0)));
+        // Small sweep arc around (0, 10): every point is 10 from the centre
+        for point in points {
+            let distance = (point.x.powi(2) + (point.y - 10.0).powi(2)).sqrt();
+            assert!((distance - 10.0).abs() < 1e-3);
+        }
+
+        // Radii too small are scaled up to a half circle
+        let half = arc(Point::new(0.0, 0.0), Point::new(10.0, 0.0), 1.0, 1.0, 0.0, false, true);
+        assert!(half.iter().any(|point| close(*point, Point::new(5.0, -5.0))));
+    }
+
+    #[test]
+    fn test_errors_keep_earlier_segments() {
+        let subpaths = parse("M0 0 L10 0 L5 x L0 5");
+        assert_eq!(subpaths[0].points, [Point::new(0.0, 0.0), Point::new(10.0, 0.0)]);
+        assert!(parse("10 10").is_empty());
+        assert_eq!(numbers("1,2 -3.5e1.5 x 7"), [1.0, 2.0, -35.0, 0.5]);
+    }
+}
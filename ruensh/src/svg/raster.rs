@@ -0,0 +1,553 @@
+//! Sub-cell pixel buffer and rasterization algorithms
+//!
+//! Shapes are drawn into a grid of pixels several times finer than the
+//! terminal grid and encoded back into cells as Braille dots or block
+//! elements. Each pixel keeps a colour and a coverage; coverage below 1 only
+//! comes from anti-aliased lines.
+
+use super::Resolution;
+use ratatui::style::Color;
+use std::ops::RangeInclusive;
+
+/// Coverage at which a pixel counts as set when encoding
+const THRESHOLD: f32 = 0.5;
+
+/// Braille dot bit for each pixel of a 2×4 cell, indexed by `[y][x]`
+const BRAILLE_DOTS: [[u8; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// Quadrant glyphs indexed by bits: top-left 1, top-right 2, bottom-left 4, bottom-right 8
+const QUADRANTS: [char; 16] = [
+    ' ', '▘', '▝', '▀', '▖', '▌', '▞', '▛', '▗', '▚', '▐', '▜', '▄', '▙', '▟', '█',
+];
+
+/// Half-block glyphs indexed by bits: top 1, bottom 2
+const HALVES: [char; 4] = [' ', '▀', '▄', '█'];
+
+impl Resolution {
+    /// Pixels per cell as (columns, rows)
+    pub fn pixels_per_cell(self) -> (u16, u16) {
+        match self {
+            Resolution::CharCell => (1, 1),
+            Resolution::Braille => (2, 4),
+            Resolution::Block => (2, 2),
+            Resolution::HalfBlock => (1, 2),
+        }
+    }
+
+    /// Glyph for a cell whose set pixels are given as bits in row-major order
+    fn glyph(self, bits: u8) -> char {
+        match self {
+            Resolution::CharCell => if bits == 0 { ' ' } else { '█' },
+            Resolution::Braille => {
+                let mut dots = 0u32;
+                for (y, row) in BRAILLE_DOTS.iter().enumerate() {
+                    for (x, dot) in row.iter().enumerate() {
+                        if bits & (1 << (y * 2 + x)) != 0 {
+                            dots |= *dot as u32;
+                        }
+                    }
+                }
+                char::from_u32(0x2800 + dots).unwrap_or(' ')
+            }
+            Resolution::Block => QUADRANTS[bits as usize & 0xf],
+            Resolution::HalfBlock => HALVES[bits as usize & 0x3],
+        }
+    }
+}
+
+/// Grid of coloured pixels
+#[derive(Debug, Clone, PartialEq)]
+pub struct PixelBuffer {
+    width: u32,
+    height: u32,
+    /// Colour and coverage per pixel, row-major
+    pixels: Vec<Option<(Color, f32)>>,
+}
+
+impl PixelBuffer {
+    /// Create an empty buffer of `width` × `height` pixels
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![None; (width * height) as usize],
+        }
+    }
+
+    /// Create a buffer covering `columns` × `rows` cells at a resolution
+    pub fn for_cells(columns: u16, rows: u16, resolution: Resolution) -> Self {
+        let (px, py) = resolution.pixels_per_cell();
+        Self::new(columns as u32 * px as u32, rows as u32 * py as u32)
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Colour and coverage of a pixel
+    pub fn get(&self, x: i32, y: i32) -> Option<(Color, f32)> {
+        self.index(x, y).and_then(|index| self.pixels[index])
+    }
+
+    /// Check whether a pixel counts as set
+    pub fn is_set(&self, x: i32, y: i32) -> bool {
+        self.get(x, y).is_some_and(|(_, coverage)| coverage >= THRESHOLD)
+    }
+
+    /// Clear every pixel
+    pub fn clear(&mut self) {
+        self.pixels.fill(None);
+    }
+
+    /// Clear the pixels in a rectangle
+    pub fn clear_rect(&mut self, x: i32, y: i32, width: u32, height: u32) {
+        for py in y..y + height as i32 {
+            for px in x..x + width as i32 {
+                if let Some(index) = self.index(px, py) {
+                    self.pixels[index] = None;
+                }
+            }
+        }
+    }
+
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        if x >= 0 && y >= 0 && (x as u32) < self.width && (y as u32) < self.height {
+            Some(y as usize * self.width as usize + x as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Set a pixel at full coverage
+    pub fn set(&mut self, x: i32, y: i32, color: Color) {
+        if let Some(index) = self.index(x, y) {
+            self.pixels[index] = Some((color, 1.0));
+        }
+    }
+
+    /// Set a pixel at partial coverage; a weaker coverage never replaces a stronger one
+    pub fn blend(&mut self, x: i32, y: i32, color: Color, coverage: f32) {
+        if let Some(index) = self.index(x, y) {
+            let coverage = coverage.clamp(0.0, 1.0);
+            if self.pixels[index].is_none_or(|(_, existing)| coverage >= existing) {
+                self.pixels[index] = Some((color, coverage));
+            }
+        }
+    }
+
+    /// Set a horizontal run of pixels from `x0` to `x1` inclusive
+    ///
+    /// The run is clipped to the buffer first.
+    pub fn span(&mut self, x0: i32, x1: i32, y: i32, color: Color) {
+        if y < 0 || y as u32 >= self.height {
+            return;
+        }
+        let last = self.width as i64 - 1;
+        let start = (x0.min(x1) as i64).max(0);
+        let end = (x0.max(x1) as i64).min(last);
+        for x in start..=end {
+            self.set(x as i32, y, color);
+        }
+    }
+
+    /// Vertical distances from row `cy` that land inside the buffer
+    fn reach(&self, cy: i32) -> RangeInclusive<i32> {
+        let (cy, last) = (cy as i64, self.height as i64 - 1);
+        let near = if cy < 0 { -cy } else { (cy - last).max(0) };
+        let far = cy.max(last - cy);
+        let clamp = |distance: i64| distance.min(i32::MAX as i64) as i32;
+        clamp(near)..=clamp(far)
+    }
+
+    /// Bresenham line between two pixels, endpoints included
+    ///
+    /// The segment is clipped to the buffer first, so far-away endpoints
+    /// cost nothing.
+    pub fn line(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, color: Color) {
+        let max = (self.width as f64 - 1.0, self.height as f64 - 1.0);
+        let clipped = clip_segment((x0 as f64, y0 as f64), (x1 as f64, y1 as f64), (0.0, 0.0), max);
+        let Some(((x0, y0), (x1, y1))) = clipped else {
+            return;
+        };
+        let (x0, y0, x1, y1) = (x0.round() as i64, y0.round() as i64, x1.round() as i64, y1.round() as i64);
+        let (dx, dy) = ((x1 - x0).abs(), -(y1 - y0).abs());
+        let (sx, sy) = ((x1 - x0).signum(), (y1 - y0).signum());
+        let (mut x, mut y, mut error) = (x0, y0, dx + dy);
+        loop {
+            self.set(x as i32, y as i32, color);
+            if x == x1 && y == y1 {
+                break;
+            }
+            let doubled = 2 * error;
+            if doubled >= dy {
+                error += dy;
+                x += sx;
+            }
+            if doubled <= dx {
+                error += dx;
+                y += sy;
+            }
+        }
+    }
+
+    /// Anti-aliased line (Xiaolin Wu) between pixel-space points
+    ///
+    /// The segment is clipped to the buffer, with a pixel of margin for the
+    /// partial coverage along the edges.
+    pub fn line_aa(&mut self, x0: f32, y0: f32, x1: f32, y1: f32, color: Color) {
+        let max = (self.width as f64, self.height as f64);
+        let clipped = clip_segment((x0 as f64, y0 as f64), (x1 as f64, y1 as f64), (-1.0, -1.0), max);
+        let Some(((x0, y0), (x1, y1))) = clipped else {
+            return;
+        };
+        let (x0, y0, x1, y1) = (x0 as f32, y0 as f32, x1 as f32, y1 as f32);
+        let steep = (y1 - y0).abs() > (x1 - x0).abs();
+        let (mut x0, mut y0, mut x1, mut y1) = if steep { (y0, x0, y1, x1) } else { (x0, y0, x1, y1) };
+        if x0 > x1 {
+            std::mem::swap(&mut x0, &mut x1);
+            std::mem::swap(&mut y0, &mut y1);
+        }
+        let gradient = if x1 == x0 { 1.0 } else { (y1 - y0) / (x1 - x0) };
+        let mut plot = |x: i32, y: i32, coverage: f32| {
+            if steep {
+                self.blend(y, x, color, coverage)
+            } else {
+                self.blend(x, y, color, coverage)
+            }
+        };
+
+        let start = x0.round() as i32;
+        let end = x1.round() as i32;
+        let mut y = y0 + gradient * (start as f32 - x0);
+        for x in start..=end {
+            let base = y.floor();
+            let fraction = y - base;
+            plot(x, base as i32, 1.0 - fraction);
+            plot(x, base as i32 + 1, fraction);
+            y += gradient;
+        }
+    }
+
+    /// Midpoint circle outline
+    ///
+    /// Octant points stop once they are further from `cy` than any row.
+    pub fn circle(&mut self, cx: i32, cy: i32, radius: i32, color: Color) {
+        let rows = self.reach(cy);
+        let (mut x, mut y, mut decision) = (radius, 0, 1 - radius);
+        while x >= y && y <= *rows.end() {
+            if rows.contains(&x) || rows.contains(&y) {
+                for (px, py) in [(x, y), (y, x), (-y, x), (-x, y), (-x, -y), (-y, -x), (y, -x), (x, -y)] {
+                    self.set(cx + px, cy + py, color);
+                }
+            }
+            y += 1;
+            if decision < 0 {
+                decision += 2 * y + 1;
+            } else {
+                x -= 1;
+                decision += 2 * (y - x) + 1;
+            }
+        }
+    }
+
+    /// Midpoint ellipse outline
+    pub fn ellipse(&mut self, cx: i32, cy: i32, rx: i32, ry: i32, color: Color) {
+        for (x, y) in ellipse_quadrant(rx, ry, self.reach(cy)) {
+            for (px, py) in [(x, y), (-x, y), (x, -y), (-x, -y)] {
+                self.set(cx + px, cy + py, color);
+            }
+        }
+    }
+
+    /// Filled ellipse from midpoint spans
+    pub fn fill_ellipse(&mut self, cx: i32, cy: i32, rx: i32, ry: i32, color: Color) {
+        for (x, y) in ellipse_quadrant(rx, ry, self.reach(cy)) {
+            self.span(cx - x, cx + x, cy + y, color);
+            self.span(cx - x, cx + x, cy - y, color);
+        }
+    }
+
+    /// Scanline polygon fill with the even-odd rule; vertices in pixel space
+    ///
+    /// A pixel is filled when its centre is inside the polygon.
+    pub fn fill_polygon(&mut self, points: &[(f32, f32)], color: Color) {
+        if points.len() < 3 {
+            return;
+        }
+        let top = points.iter().map(|p| p.1).fold(f32::INFINITY, f32::min).floor().max(0.0) as i32;
+        let bottom = points.iter().map(|p| p.1).fold(f32::NEG_INFINITY, f32::max).ceil() as i32;
+        let bottom = bottom.min(self.height as i32);
+
+        let mut crossings = Vec::new();
+        for y in top..bottom {
+            let scan = y as f32 + 0.5;
+            crossings.clear();
+            let mut previous = points[points.len() - 1];
+            for &current in points {
+                if (current.1 > scan) != (previous.1 > scan) {
+                    let t = (scan - current.1) / (previous.1 - current.1);
+                    crossings.push(current.0 + t * (previous.0 - current.0));
+                }
+                previous = current;
+            }
+            crossings.sort_by(f32::total_cmp);
+            for pair in crossings.chunks_exact(2) {
+                // Pixels whose centre lies in [start, end)
+                let start = (pair[0] - 0.5).ceil() as i32;
+                let end = (pair[1] - 0.5).ceil() as i32 - 1;
+                if start <= end {
+                    self.span(start.max(0), end.min(self.width as i32 - 1), y, color);
+                }
+            }
+        }
+    }
+
+    /// Encode one cell: its glyph, colour and the highest coverage of its set pixels
+    ///
+    /// Returns `None` when no pixel in the cell is set.
+    pub fn encode_cell(&self, column: u16, row: u16, resolution: Resolution) -> Option<(char, Color, f32)> {
+        let (px, py) = resolution.pixels_per_cell();
+        let (x0, y0) = (column as i32 * px as i32, row as i32 * py as i32);
+        let mut bits = 0u8;
+        let mut strongest: Option<(Color, f32)> = None;
+        for dy in 0..py as i32 {
+            for dx in 0..px as i32 {
+                let Some((color, coverage)) = self.get(x0 + dx, y0 + dy) else {
+                    continue;
+                };
+                if coverage < THRESHOLD {
+                    continue;
+                }
+                bits |= 1 << (dy * px as i32 + dx);
+                if strongest.is_none_or(|(_, best)| coverage > best) {
+                    strongest = Some((color, coverage));
+                }
+            }
+        }
+        strongest.map(|(color, coverage)| (resolution.glyph(bits), color, coverage))
+    }
+}
+
+/// Clip a segment to the box from `min` to `max` (Liang–Barsky)
+///
+/// Returns `None` when no part of the segment is inside or a coordinate is
+/// not finite. Endpoints already inside the box are returned unchanged.
+pub(crate) fn clip_segment(
+    from: (f64, f64),
+    to: (f64, f64),
+    min: (f64, f64),
+    max: (f64, f64),
+) -> Option<((f64, f64), (f64, f64))> {
+    if ![from.0, from.1, to.0, to.1].iter().all(|value| value.is_finite()) || min.0 > max.0 || min.1 > max.1 {
+        return None;
+    }
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let (mut enter, mut exit) = (0.0f64, 1.0f64);
+    // Each edge as (direction along its normal, distance inside it)
+    for (p, q) in [(-dx, from.0 - min.0), (dx, max.0 - from.0), (-dy, from.1 - min.1), (dy, max.1 - from.1)] {
+        if p == 0.0 {
+            if q < 0.0 {
+                return None;
+            }
+        } else if p < 0.0 {
+            enter = enter.max(q / p);
+        } else {
+            exit = exit.min(q / p);
+        }
+    }
+    if enter > exit {
+        return None;
+    }
+    let at = |t: f64| (from.0 + dx * t, from.1 + dy * t);
+    let start = if enter == 0.0 { from } else { at(enter) };
+    let end = if exit == 1.0 { to } else { at(exit) };
+    Some((start, end))
+}
+
+/// Points of one quadrant of a midpoint ellipse, with `x, y >= 0`
+///
+/// Only points whose `y` is in `rows` are returned, and the walk stops once
+/// `y` drops below them.
+fn ellipse_quadrant(rx: i32, ry: i32, rows: RangeInclusive<i32>) -> Vec<(i32, i32)> {
+    let mut points = Vec::new();
+    if rx <= 0 || ry <= 0 {
+        points.push((rx.max(0), ry.max(0)));
+        if rx > 0 {
+            points.extend((0..rx).map(|x| (x, 0)));
+        } else if ry > 0 {
+            let (first, last) = ((*rows.start()).max(0), (*rows.end()).min(ry - 1));
+            points.extend((first..=last).map(|y| (0, y)));
+        }
+        points.retain(|point| rows.contains(&point.1));
+        return points;
+    }
+
+    let (rx2, ry2) = (rx as i64 * rx as i64, ry as i64 * ry as i64);
+    let (mut x, mut y) = (0i64, ry as i64);
+    let (mut dx, mut dy) = (0i64, 2 * rx2 * y);
+
+    // Region 1: slope above -1
+    let mut decision = ry2 as f64 - (rx2 * ry as i64) as f64 + 0.25 * rx2 as f64;
+    while dx < dy {
+        if y < *rows.start() as i64 {
+            return points;
+        }
+        if y <= *rows.end() as i64 {
+            points.push((x as i32, y as i32));
+        }
+        x += 1;
+        dx += 2 * ry2;
+        if decision < 0.0 {
+            decision += (dx + ry2) as f64;
+        } else {
+            y -= 1;
+            dy -= 2 * rx2;
+            decision += (dx - dy + ry2) as f64;
+        }
+    }
+
+    // Region 2: slope below -1
+    let mut decision = ry2 as f64 * (x as f64 + 0.5).powi(2) + rx2 as f64 * ((y - 1) as f64).powi(2)
+        - rx2 as f64 * ry2 as f64;
+    while y >= (*rows.start()).max(0) as i64 {
+        if y <= *rows.end() as i64 {
+            points.push((x as i32, y as i32));
+        }
+        y -= 1;
+        dy -= 2 * rx2;
+        if decision > 0.0 {
+            decision += (rx2 - dy) as f64;
+        } else {
+            x += 1;
+            dx += 2 * ry2;
+            decision += (dx - dy + rx2) as f64;
+        }
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(buffer: &PixelBuffer) -> Vec<String> {
+        (0..buffer.height() as i32)
+            .map(|y| {
+                (0..buffer.width() as i32)
+                    .map(|x| if buffer.is_set(x, y) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_bresenham_and_wu_lines() {
+        let mut buffer = PixelBuffer::new(5, 3);
+        buffer.line(0, 0, 4, 2, Color::White);
+        assert_eq!(rows(&buffer), ["#....", ".##..", "...##"]);
+
+        let mut buffer = PixelBuffer::new(5, 3);
+        buffer.line_aa(0.0, 0.0, 4.0, 1.0, Color::White);
+        let (_, partial) = buffer.get(2, 0).unwrap();
+        assert!((partial - 0.5).abs() < 1e-6);
+        assert_eq!(buffer.get(0, 0).unwrap().1, 1.0);
+        assert_eq!(buffer.get(4, 1).unwrap().1, 1.0);
+    }
+
+    #[test]
+    fn test_lines_clip_far_endpoints() {
+        let mut buffer = PixelBuffer::new(5, 3);
+        buffer.line(-2_000_000_000, 1, 2_000_000_000, 1, Color::White);
+        assert_eq!(rows(&buffer), [".....", "#####", "....."]);
+
+        let mut buffer = PixelBuffer::new(5, 3);
+        buffer.line_aa(-3e9, 1.0, 3e9, 1.0, Color::White);
+        assert_eq!(rows(&buffer), [".....", "#####", "....."]);
+
+        buffer.line(-10, -10, -1, -1, Color::White);
+        assert_eq!(clip_segment((-5.0, 0.0), (-1.0, 0.0), (0.0, 0.0), (4.0, 2.0)), None);
+        assert_eq!(
+            clip_segment((-4.0, 0.0), (4.0, 4.0), (0.0, 0.0), (4.0, 4.0)),
+            Some(((0.0, 2.0), (4.0, 4.0)))
+        );
+    }
+
+    #[test]
+    fn test_large_ellipse_does_not_overflow() {
+        let points = ellipse_quadrant(50_000, 2, 0..=i32::MAX);
+        assert_eq!(points.first(), Some(&(0, 2)));
+        assert_eq!(points.last().map(|point| point.1), Some(0));
+    }
+
+    #[test]
+    fn test_huge_shapes_only_walk_visible_rows() {
+        let mut buffer = PixelBuffer::new(20, 20);
+        buffer.fill_ellipse(10, 2, 80_000, 160_000, Color::White);
+        assert!(rows(&buffer).iter().all(|row| row == "####################"));
+
+        let mut buffer = PixelBuffer::new(20, 20);
+        buffer.circle(10, 1_000_002, 1_000_000, Color::White);
+        buffer.ellipse(10, 1_000_002, 400_000, 1_000_000, Color::White);
+        assert!(buffer.is_set(10, 2));
+        assert!(!buffer.is_set(10, 3) && !buffer.is_set(10, 1));
+
+        let points = ellipse_quadrant(3, 3, 2..=2);
+        assert!(!points.is_empty() && points.iter().all(|point| point.1 == 2));
+    }
+
+    #[test]
+    fn test_circle_ellipse_and_fill() {
+        let mut buffer = PixelBuffer::new(7, 7);
+        buffer.circle(3, 3, 3, Color::White);
+        assert_eq!(rows(&buffer), [
+            "..###..", ".#...#.", "#.....#", "#.....#", "#.....#", ".#...#.", "..###..",
+        ]);
+
+        let mut buffer = PixelBuffer::new(9, 5);
+        buffer.fill_ellipse(4, 2, 4, 2, Color::White);
+        assert_eq!(rows(&buffer), ["..#####..", ".#######.", "#########", ".#######.", "..#####.."]);
+
+        let mut outline = PixelBuffer::new(9, 5);
+        outline.ellipse(4, 2, 4, 2, Color::White);
+        assert!(outline.is_set(0, 2) && outline.is_set(8, 2) && outline.is_set(4, 0));
+        assert!(!outline.is_set(4, 2));
+    }
+
+    #[test]
+    fn test_scanline_polygon_fill() {
+        let mut buffer = PixelBuffer::new(6, 4);
+        buffer.fill_polygon(&[(0.0, 0.0), (6.0, 0.0), (0.0, 4.0)], Color::White);
+        assert_eq!(rows(&buffer), ["#####.", "####..", "##....", "#....."]);
+
+        // Even-odd leaves the inner square empty
+        let mut buffer = PixelBuffer::new(4, 4);
+        let ring = [
+            (0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0), (0.0, 0.0),
+            (1.0, 1.0), (1.0, 3.0), (3.0, 3.0), (3.0, 1.0), (1.0, 1.0),
+        ];
+        buffer.fill_polygon(&ring, Color::White);
+        assert_eq!(rows(&buffer), ["####", "#..#", "#..#", "####"]);
+    }
+
+    #[test]
+    fn test_cell_encodings() {
+        let mut buffer = PixelBuffer::for_cells(1, 1, Resolution::Braille);
+        buffer.set(0, 0, Color::Red);
+        buffer.set(1, 3, Color::Red);
+        assert_eq!(buffer.encode_cell(0, 0, Resolution::Braille), Some(('⢁', Color::Red, 1.0)));
+
+        let mut buffer = PixelBuffer::for_cells(2, 1, Resolution::Block);
+        buffer.set(0, 0, Color::Red);
+        buffer.set(1, 1, Color::Red);
+        assert_eq!(buffer.encode_cell(0, 0, Resolution::Block).unwrap().0, '▚');
+        assert_eq!(buffer.encode_cell(1, 0, Resolution::Block), None);
+
+        let mut buffer = PixelBuffer::for_cells(1, 1, Resolution::HalfBlock);
+        buffer.set(0, 1, Color::Red);
+        assert_eq!(buffer.encode_cell(0, 0, Resolution::HalfBlock).unwrap().0, '▄');
+    }
+}
@@ -1,6 +1,10 @@
 //! Shape primitives for SVG-inspired rendering
 
-use ratatui::style::{Color, Style};
+use super::canvas::SvgCanvas;
+use ratatui::style::Color;
+
+/// Glyph used for filled cells
+const FILL: char = '█';
 
 /// 2D Point in terminal space
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -16,9 +20,12 @@ impl Point {
 }
 
 /// Base trait for all shapes
+///
+/// Coordinates are in cells: the cell `(x, y)` covers `x..x + 1` and
+/// `y..y + 1`, and a cell is filled when its centre is inside the shape.
 pub trait Shape {
     /// Render the shape to terminal using Unicode characters
-    fn render(&self, canvas: &mut super::canvas::SvgCanvas);
+    fn render(&self, canvas: &mut SvgCanvas);
     
     /// Get bounding box of the shape
     fn bounds(&self) -> (Point, Point);
@@ -68,11 +75,105 @@ impl Rectangle {
         self.corner_radius = radius;
         self
     }
+
+    pub fn stroke_width(mut self, width: u8) -> Self {
+        self.stroke_width = width;
+        self
+    }
+
+    /// Draw one ring of the outline; rings are numbered from the outside in
+    fn stroke_ring(&self, canvas: &mut SvgCanvas, ring: i32, color: Option<Color>) {
+        let left = self.x.round() as i32 + ring;
+        let top = self.y.round() as i32 + ring;
+        let right = (self.x + self.width).round() as i32 - 1 - ring;
+        let bottom = (self.y + self.height).round() as i32 - 1 - ring;
+        if right < left || bottom < top {
+            return;
+        }
+
+        let heavy = self.stroke_width > 1;
+        let (horizontal, vertical) = if heavy { ('━', '┃') } else { ('─', '│') };
+        if left == right || top == bottom {
+            let ch = if left == right && top != bottom { vertical } else { horizontal };
+            for y in top..=bottom {
+                for x in left..=right {
+                    canvas.plot(x, y, ch, color);
+                }
+            }
+            return;
+        }
+
+        let corners = if heavy {
+            ['┏', '┓', '┗', '┛']
+        } else if self.corner_radius > 0.0 && ring == 0 {
+            ['╭', '╮', '╰', '╯']
+        } else {
+            ['┌', '┐', '└', '┘']
+        };
+        for x in left + 1..right {
+            canvas.plot(x, top, horizontal, color);
+            canvas.plot(x, bottom, horizontal, color);
+        }
+        for y in top + 1..bottom {
+            canvas.plot(left, y, vertical, color);
+            canvas.plot(right, y, vertical, color);
+        }
+        canvas.plot(left, top, corners[0], color);
+        canvas.plot(right, top, corners[1], color);
+        canvas.plot(left, bottom, corners[2], color);
+        canvas.plot(right, bottom, corners[3], color);
+    }
 }
 
 impl Shape for Rectangle {
-    fn render(&self, canvas: &mut super::canvas::SvgCanvas) {
-        // Will be implemented in canvas module
+    fn render(&self, canvas: &mut SvgCanvas) {
+        if let Some(fill) = self.fill {
+            let (min, max) = self.bounds();
+            canvas.fill_region(min, max, |point| self.contains(point), FILL, Some(fill));
+        }
+        let Some(stroke) = self.stroke else {
+            return;
+        };
+        let (px, py) = canvas.pixels_per_cell();
+        if (px, py) == (1, 1) {
+            for ring in 0..self.stroke_width as i32 {
+                self.stroke_ring(canvas, ring, Some(stroke));
+            }
+        } else if self.corner_radius <= 0.0 {
+            // One pixel line per ring, so fills and neighbouring pixels in
+            // the same cells are kept
+            let (left, top) = ((self.x * px as f32).round() as i32, (self.y * py as f32).round() as i32);
+            let right = ((self.x + self.width) * px as f32).round() as i32 - 1;
+            let bottom = ((self.y + self.height) * py as f32).round() as i32 - 1;
+            let centre = |x: i32, y: i32| Point::new((x as f32 + 0.5) / px as f32, (y as f32 + 0.5) / py as f32);
+            for ring in 0..self.stroke_width as i32 {
+                let (left, top, right, bottom) = (left + ring, top + ring, right - ring, bottom - ring);
+                if right < left || bottom < top {
+                    break;
+                }
+                let corners = [centre(left, top), centre(right, top), centre(right, bottom), centre(left, bottom)];
+                for (index, from) in corners.iter().enumerate() {
+                    canvas.draw_line(*from, corners[(index + 1) % 4], 1, Some(stroke));
+                }
+            }
+        } else if self.stroke_width > 0 {
+            // A band `stroke_width` pixels deep inside the edge
+            let depth = self.stroke_width as f32;
+            let (inset_x, inset_y) = (depth / px as f32, depth / py as f32);
+            let inner = Rectangle {
+                x: self.x + inset_x,
+                y: self.y + inset_y,
+                width: self.width - 2.0 * inset_x,
+                height: self.height - 2.0 * inset_y,
+                corner_radius: (self.corner_radius - inset_x.max(inset_y)).max(0.0),
+                ..self.clone()
+            };
+            let (min, max) = self.bounds();
+            let on_band = |point: Point| {
+                self.contains(point) && (inner.width <= 0.0 || inner.height <= 0.0 || !inner.contains(point))
+            };
+            canvas.fill_region(min, max, on_band, FILL, Some(stroke));
+        }
     }
 
     fn bounds(&self) -> (Point, Point) {
@@ -83,10 +184,20 @@ impl Shape for Rectangle {
     }
 
     fn contains(&self, point: Point) -> bool {
-        point.x >= self.x
+        let inside = point.x >= self.x
             && point.x <= self.x + self.width
             && point.y >= self.y
-            && point.y <= self.y + self.height
+            && point.y <= self.y + self.height;
+        if !inside || self.corner_radius <= 0.0 {
+            return inside;
+        }
+
+        // Distance from the nearest corner circle's centre
+        let radius = self.corner_radius.min(self.width / 2.0).min(self.height / 2.0);
+        let cx = point.x.clamp(self.x + radius, self.x + self.width - radius);
+        let cy = point.y.clamp(self.y + radius, self.y + self.height - radius);
+        let (dx, dy) = (point.x - cx, point.y - cy);
+        dx * dx + dy * dy <= radius * radius
     }
 }
 
@@ -122,11 +233,22 @@ impl Circle {
         self.stroke = Some(color);
         self
     }
+
+    pub fn stroke_width(mut self, width: u8) -> Self {
+        self.stroke_width = width;
+        self
+    }
 }
 
 impl Shape for Circle {
-    fn render(&self, canvas: &mut super::canvas::SvgCanvas) {
-        // Will be implemented in canvas module
+    fn render(&self, canvas: &mut SvgCanvas) {
+        let center = Point::new(self.cx, self.cy);
+        if let Some(fill) = self.fill {
+            canvas.fill_ellipse(center, self.radius, self.radius, Some(fill));
+        }
+        if let Some(stroke) = self.stroke {
+            canvas.draw_ellipse(center, self.radius, self.radius, self.stroke_width, Some(stroke));
+        }
     }
 
     fn bounds(&self) -> (Point, Point) {
@@ -166,11 +288,18 @@ impl Line {
         self.stroke = color;
         self
     }
+
+    pub fn stroke_width(mut self, width: u8) -> Self {
+        self.stroke_width = width;
+        self
+    }
 }
 
 impl Shape for Line {
-    fn render(&self, canvas: &mut super::canvas::SvgCanvas) {
-        // Will be implemented in canvas module
+    fn render(&self, canvas: &mut SvgCanvas) {
+        if self.stroke_width > 0 {
+            canvas.draw_line(self.from, self.to, self.stroke_width, Some(self.stroke));
+        }
     }
 
     fn bounds(&self) -> (Point, Point) {
@@ -221,6 +350,16 @@ impl Path {
         self.fill = Some(color);
         self
     }
+
+    pub fn stroke(mut self, color: Color) -> Self {
+        self.stroke = Some(color);
+        self
+    }
+
+    pub fn stroke_width(mut self, width: u8) -> Self {
+        self.stroke_width = width;
+        self
+    }
 }
 
 impl Default for Path {
@@ -230,8 +369,30 @@ impl Default for Path {
 }
 
 impl Shape for Path {
-    fn render(&self, canvas: &mut super::canvas::SvgCanvas) {
-        // Will be implemented in canvas module
+    fn render(&self, canvas: &mut SvgCanvas) {
+        // Fills close the path implicitly, as in SVG
+        if let Some(fill) = self.fill {
+            canvas.fill_polygon(&self.points, Some(fill));
+        }
+
+        let Some(stroke) = self.stroke.filter(|_| self.stroke_width > 0) else {
+            return;
+        };
+        if let [point] = self.points[..] {
+            if canvas.pixels_per_cell() == (1, 1) {
+                canvas.plot(point.x.floor() as i32, point.y.floor() as i32, '•', Some(stroke));
+            } else {
+                canvas.draw_line(point, point, 1, Some(stroke));
+            }
+            return;
+        }
+        for segment in self.points.windows(2) {
+            canvas.draw_line(segment[0], segment[1], self.stroke_width, Some(stroke));
+        }
+        if self.closed && self.points.len() > 2 {
+            let (first, last) = (self.points[0], self.points[self.points.len() - 1]);
+            canvas.draw_line(last, first, self.stroke_width, Some(stroke));
+        }
     }
 
     fn bounds(&self) -> (Point, Point) {
@@ -254,8 +415,149 @@ impl Shape for Path {
         (Point::new(min_x, min_y), Point::new(max_x, max_y))
     }
 
-    fn contains(&self, _point: Point) -> bool {
-        // Complex polygon containment test would go here
-        false
+    fn contains(&self, point: Point) -> bool {
+        (self.closed || self.fill.is_some()) && polygon_contains(&self.points, point)
+    }
+}
+
+/// Even-odd point-in-polygon test
+fn polygon_contains(points: &[Point], point: Point) -> bool {
+    let mut inside = false;
+    let mut previous = match points.last() {
+        Some(last) => *last,
+        None => return false,
+    };
+    for &current in points {
+        if (current.y > point.y) != (previous.y > point.y) {
+            let x = current.x + (point.y - current.y) * (previous.x - current.x) / (previous.y - current.y);
+            if point.x < x {
+                inside = !inside;
+            }
+        }
+        previous = current;
+    }
+    inside
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::svg::Resolution;
+
+    fn row(canvas: &SvgCanvas, y: u16) -> String {
+        (0..canvas.width()).map(|x| canvas.cell(x, y).unwrap().0).collect()
+    }
+
+    #[test]
+    fn test_rectangle_fill_stroke_and_corners() {
+        let mut canvas = SvgCanvas::new(8, 5);
+        Rectangle::new(1.0, 0.0, 6.0, 4.0).fill(Color::Blue).stroke(Color::Red).render(&mut canvas);
+        assert_eq!(row(&canvas, 0), " ┌────┐ ");
+        assert_eq!(row(&canvas, 1), " │████│ ");
+        assert_eq!(row(&canvas, 3), " └────┘ ");
+        assert_eq!(canvas.cell(2, 1), Some((FILL, Some(Color::Blue))));
+        assert_eq!(canvas.cell(1, 1), Some(('│', Some(Color::Red))));
+
+        let mut canvas = SvgCanvas::new(6, 4);
+        let mut heavy = Rectangle::new(0.0, 0.0, 6.0, 4.0).rounded(1.0).stroke_width(2);
+        heavy.render(&mut canvas);
+        assert_eq!(row(&canvas, 0), "┏━━━━┓");
+        assert_eq!(row(&canvas, 1), "┃┏━━┓┃");
+
+        heavy.stroke_width = 1;
+        canvas.clear();
+        heavy.render(&mut canvas);
+        assert_eq!(row(&canvas, 0), "╭────╮");
+    }
+
+    #[test]
+    fn test_rounded_fill_clips_corners() {
+        let rect = Rectangle::new(0.0, 0.0, 6.0, 4.0).rounded(2.0);
+        assert!(!rect.contains(Point::new(0.2, 0.2)));
+        assert!(rect.contains(Point::new(3.0, 0.2)));
+
+        let mut canvas = SvgCanvas::new(6, 4);
+        let mut fill_only = rect.fill(Color::Green);
+        fill_only.stroke = None;
+        fill_only.render(&mut canvas);
+        assert_eq!(row(&canvas, 0), " ████ ");
+        assert_eq!(row(&canvas, 1), "██████");
+    }
+
+    #[test]
+    fn test_circle_fill_and_ring() {
+        let mut canvas = SvgCanvas::new(10, 10);
+        Circle::new(5.0, 5.0, 4.0).fill(Color::Blue).stroke(Color::Red).render(&mut canvas);
+        assert_eq!(canvas.cell(5, 5), Some((FILL, Some(Color::Blue))));
+        assert_eq!(canvas.cell(8, 5), Some(('●', Some(Color::Red))));
+        assert_eq!(canvas.cell(1, 1), Some((' ', None)));
+        assert_eq!(row(&canvas, 5), " ●██████● ");
+    }
+
+    #[test]
+    fn test_shapes_at_sub_cell_resolution() {
+        let mut canvas = SvgCanvas::new(4, 2).resolution(Resolution::Braille);
+        Rectangle::new(0.0, 0.0, 4.0, 2.0).render(&mut canvas);
+        assert_eq!(row(&canvas, 0), "⡏⠉⠉⢹");
+        assert_eq!(row(&canvas, 1), "⣇⣀⣀⣸");
+
+        // Strokes add pixels to a fill instead of replacing whole cells
+        let mut canvas = SvgCanvas::new(4, 2).resolution(Resolution::Braille);
+        let mut fill = Rectangle::new(0.0, 0.0, 4.0, 2.0).fill(Color::Blue);
+        fill.stroke = None;
+        fill.render(&mut canvas);
+        Rectangle::new(0.5, 0.25, 3.0, 1.5).stroke(Color::Red).render(&mut canvas);
+        Path::new().add_point(Point::new(3.9, 1.9)).stroke(Color::Red).render(&mut canvas);
+        assert_eq!(row(&canvas, 0), "⣿⣿⣿⣿");
+        assert_eq!(row(&canvas, 1), "⣿⣿⣿⣿");
+        assert_eq!(canvas.pixels_mut().get(1, 1).map(|pixel| pixel.0), Some(Color::Red));
+        assert_eq!(canvas.pixels_mut().get(2, 2).map(|pixel| pixel.0), Some(Color::Blue));
+
+        let mut canvas = SvgCanvas::new(4, 2).resolution(Resolution::Block);
+        let mut disc = Circle::new(2.0, 1.0, 1.0).fill(Color::Blue);
+        disc.stroke = None;
+        disc.render(&mut canvas);
+        assert_eq!(canvas.cell(1, 0), Some(('▟', Some(Color::Blue))));
+        assert_eq!(canvas.cell(2, 1).unwrap().0, '█');
+    }
+
+    #[test]
+    fn test_huge_circle_on_small_canvas() {
+        let mut canvas = SvgCanvas::new(10, 5).resolution(Resolution::Braille);
+        let mut disc = Circle::new(5.0, 2.0, 20_000.0).fill(Color::Blue);
+        disc.render(&mut canvas);
+        disc.stroke = None;
+        disc.render(&mut canvas);
+        assert!((0..5).all(|y| row(&canvas, y) == "⣿".repeat(10)));
+    }
+
+    #[test]
+    fn test_line_and_path() {
+        let mut canvas = SvgCanvas::new(6, 6);
+        Line::new(Point::new(0.0, 0.0), Point::new(5.0, 0.0)).render(&mut canvas);
+        assert_eq!(row(&canvas, 0), "──────");
+
+        let triangle = Path::new()
+            .add_point(Point::new(0.0, 1.0))
+            .add_point(Point::new(6.0, 1.0))
+            .add_point(Point::new(0.0, 6.0))
+            .close()
+            .fill(Color::Yellow);
+        assert!(triangle.contains(Point::new(1.0, 2.0)));
+        assert!(!triangle.contains(Point::new(5.0, 5.0)));
+
+        let mut canvas = SvgCanvas::new(6, 6);
+        let mut fill_only = triangle.clone();
+        fill_only.stroke = None;
+        fill_only.render(&mut canvas);
+        assert_eq!(row(&canvas, 1), "█████ ");
+        assert_eq!(row(&canvas, 5), "█     ");
+
+        let mut canvas = SvgCanvas::new(6, 6);
+        let mut outline = triangle;
+        outline.fill = None;
+        outline.render(&mut canvas);
+        assert_eq!(canvas.cell(0, 3).unwrap().0, '│');
+        assert_eq!(canvas.cell(3, 1).unwrap().0, '─');
     }
 }
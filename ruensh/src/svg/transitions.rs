@@ -65,7 +65,7 @@ impl Interpolate for Color {
                 let b = (*b1 as f32 + ((*b2 as f32) - (*b1 as f32)) * t) as u8;
                 Color::Rgb(r, g, b)
             }
-            _ => self.clone(),
+            _ => *self,
         }
     }
 }
@@ -137,9 +137,15 @@ impl<T: Interpolate> Transition<T> {
 
     /// Start the transition
     pub fn start(&mut self) {
-        self.start_time = Some(Instant::now());
+        self.start_at(Instant::now());
+    }
+
+    /// Start the transition as if it began at `now`
+    pub fn start_at(&mut self, now: Instant) {
+        self.start_time = Some(now);
         self.state = TransitionState::Running;
         self.current_loop = 0;
+        self.current_value = Some(self.value_at(0.0));
     }
 
     /// Pause the transition
@@ -176,30 +182,39 @@ impl<T: Interpolate> Transition<T> {
 
     /// Update transition and get current value
     pub fn update(&mut self) -> Option<&T> {
+        self.update_at(Instant::now())
+    }
+
+    /// Update transition using `now` as the current time
+    pub fn update_at(&mut self, now: Instant) -> Option<&T> {
         if self.state != TransitionState::Running {
             return self.current_value.as_ref();
         }
 
         let start = self.start_time?;
-        let elapsed = start.elapsed();
+        let elapsed = now.saturating_duration_since(start);
 
         // Calculate progress (0.0 to 1.0)
-        let mut progress = elapsed.as_secs_f32() / self.duration.as_secs_f32();
+        let mut progress = if self.duration.is_zero() {
+            1.0
+        } else {
+            elapsed.as_secs_f32() / self.duration.as_secs_f32()
+        };
 
         // Handle looping
         if progress >= 1.0 {
             if let Some(max_loops) = self.loop_count {
-                if self.current_loop >= max_loops - 1 {
+                if self.current_loop >= max_loops.saturating_sub(1) {
                     self.state = TransitionState::Complete;
                     progress = 1.0;
                 } else {
                     self.current_loop += 1;
-                    self.start_time = Some(Instant::now());
+                    self.start_time = Some(now);
                     progress = 0.0;
                 }
             } else {
                 // Infinite loop
-                self.start_time = Some(Instant::now());
+                self.start_time = Some(now);
                 progress = 0.0;
             }
         }
@@ -453,4 +468,18 @@ mod tests {
             assert!(b > 100 && b < 150);
         }
     }
+
+    #[test]
+    fn test_transition_completes_after_duration() {
+        let start = Instant::now();
+        let mut transition = TransitionPresets::fade_in(Duration::from_millis(100)).with_loop(Some(1));
+        transition.start_at(start);
+
+        let halfway = *transition.update_at(start + Duration::from_millis(50)).unwrap();
+        assert!(halfway > 0.0 && halfway < 1.0);
+        assert!(transition.is_running());
+
+        assert_eq!(transition.update_at(start + Duration::from_millis(150)), Some(&1.0));
+        assert_eq!(transition.state(), TransitionState::Complete);
+    }
 }
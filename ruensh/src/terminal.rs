@@ -1,14 +1,74 @@
 //! Terminal abstraction and initialization
 
+use crate::style::{ColorSupport, Theme};
 use crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use std::io::{self, Stdout};
+use ratatui::style::Color;
+use ratatui::Frame;
+use std::io::{self, Stdout, Write};
+use std::time::Duration;
+
+/// How long to wait for the terminal to report its background colour
+pub const BACKGROUND_QUERY_TIMEOUT: Duration = Duration::from_millis(100);
 
 /// Terminal wrapper managing raw mode and alternate screen
 pub struct Terminal {
     stdout: Stdout,
+    color_support: ColorSupport,
+}
+
+/// Whether the terminal background is light or dark
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Background {
+    Light,
+    Dark,
+}
+
+impl Background {
+    /// Classify a background colour by its relative luminance
+    pub fn from_color(color: Color) -> Self {
+        let (r, g, b) = match color {
+            Color::Rgb(r, g, b) => (r, g, b),
+            Color::White | Color::Gray => return Background::Light,
+            Color::LightRed
+            | Color::LightGreen
+            | Color::LightYellow
+            | Color::LightBlue
+            | Color::LightMagenta
+            | Color::LightCyan => return Background::Light,
+            _ => return Background::Dark,
+        };
+        let luminance = 0.2126 * r as f32 + 0.7152 * g as f32 + 0.0722 * b as f32;
+        if luminance > 127.5 {
+            Background::Light
+        } else {
+            Background::Dark
+        }
+    }
+
+    /// Read the background from a `COLORFGBG` value such as `15;0`
+    ///
+    /// The last field is the ANSI index of the background; white and the
+    /// bright colours other than dark grey count as light.
+    pub fn from_colorfgbg(value: &str) -> Option<Self> {
+        let index: u8 = value.rsplit(';').next()?.trim().parse().ok()?;
+        match index {
+            7 | 9..=15 => Some(Background::Light),
+            0..=6 | 8 => Some(Background::Dark),
+            _ => None,
+        }
+    }
+
+    /// Pick the theme matching this background
+    pub fn pick(self, light: Theme, dark: Theme) -> Theme {
+        match self {
+            Background::Light => light,
+            Background::Dark => dark,
+        }
+    }
 }
 
 impl Terminal {
@@ -17,9 +77,12 @@ impl Terminal {
         let mut stdout = io::stdout();
         
         enable_raw_mode()?;
-        execute!(stdout, EnterAlternateScreen)?;
+        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
         
-        Ok(Terminal { stdout })
+        Ok(Terminal {
+            stdout,
+            color_support: ColorSupport::detect(),
+        })
     }
 
     /// Get mutable reference to stdout
@@ -31,12 +94,130 @@ impl Terminal {
     pub fn stdout(&self) -> &Stdout {
         &self.stdout
     }
+
+    /// Colours the terminal was detected to support
+    pub fn color_support(&self) -> ColorSupport {
+        self.color_support
+    }
+
+    /// Override the detected colour support
+    pub fn set_color_support(&mut self, support: ColorSupport) {
+        self.color_support = support;
+    }
+
+    /// Downsample the frame's colours to what the terminal supports
+    ///
+    /// Call as the last step of `draw`.
+    pub fn adapt_frame(&self, frame: &mut Frame<'_>) {
+        self.color_support.adapt_buffer(frame.buffer_mut());
+    }
+
+    /// Ask the terminal for its background colour with an OSC 11 query
+    ///
+    /// Returns `None` if the terminal does not answer within `timeout`.
+    /// Call before starting the event loop so the reply is not read as input.
+    pub fn query_background_color(&mut self, timeout: Duration) -> Option<Color> {
+        // The device attributes query (`CSI c`) is answered by every terminal,
+        // so its reply marks the end of the wait when OSC 11 is unsupported
+        self.stdout.write_all(b"\x1b]11;?\x1b\\\x1b[c").ok()?;
+        self.stdout.flush().ok()?;
+        let reply = read_reply(timeout)?;
+        parse_osc11(&reply)
+    }
+
+    /// Detect whether the background is light or dark
+    ///
+    /// Tries an OSC 11 query, then `COLORFGBG`, and assumes dark otherwise.
+    pub fn detect_background(&mut self) -> Background {
+        self.query_background_color(BACKGROUND_QUERY_TIMEOUT)
+            .map(Background::from_color)
+            .or_else(|| std::env::var("COLORFGBG").ok().and_then(|value| Background::from_colorfgbg(&value)))
+            .unwrap_or(Background::Dark)
+    }
+
+    /// Pick `Theme::light()` or `Theme::dark()` for the terminal background
+    pub fn detect_theme(&mut self) -> Theme {
+        self.select_theme(Theme::light(), Theme::dark())
+    }
+
+    /// Pick between user-supplied light and dark themes for the terminal background
+    pub fn select_theme(&mut self, light: Theme, dark: Theme) -> Theme {
+        self.detect_background().pick(light, dark)
+    }
+}
+
+/// Read a terminal reply from stdin until the device attributes answer arrives
+#[cfg(unix)]
+fn read_reply(timeout: Duration) -> Option<Vec<u8>> {
+    use std::time::Instant;
+
+    // SAFETY: isatty only inspects the descriptor
+    if unsafe { libc::isatty(libc::STDIN_FILENO) } != 1 {
+        return None;
+    }
+    let deadline = Instant::now() + timeout;
+    let mut reply = Vec::new();
+    while !ends_with_device_attributes(&reply) {
+        let remaining = deadline.checked_duration_since(Instant::now())?;
+        let mut fds = libc::pollfd {
+            fd: libc::STDIN_FILENO,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        // SAFETY: fds is a single valid pollfd for the duration of the call
+        let ready = unsafe { libc::poll(&mut fds, 1, remaining.as_millis() as libc::c_int) };
+        if ready <= 0 {
+            return (!reply.is_empty()).then_some(reply);
+        }
+        let mut buf = [0u8; 64];
+        // SAFETY: buf is valid for writes of its full length
+        let read = unsafe { libc::read(libc::STDIN_FILENO, buf.as_mut_ptr().cast(), buf.len()) };
+        if read <= 0 {
+            break;
+        }
+        reply.extend_from_slice(&buf[..read as usize]);
+    }
+    Some(reply)
+}
+
+#[cfg(not(unix))]
+fn read_reply(_timeout: Duration) -> Option<Vec<u8>> {
+    None
+}
+
+/// Check whether a reply ends with a `CSI ? ... c` device attributes answer
+#[cfg_attr(not(unix), allow(dead_code))]
+fn ends_with_device_attributes(reply: &[u8]) -> bool {
+    reply.ends_with(b"c")
+        && reply
+            .windows(3)
+            .rposition(|window| window == b"\x1b[?")
+            .is_some_and(|start| reply[start + 3..reply.len() - 1].iter().all(|b| b.is_ascii_digit() || *b == b';'))
+}
+
+/// Parse an OSC 11 reply such as `ESC ] 11 ; rgb:ffff/ffff/ffff BEL`
+fn parse_osc11(reply: &[u8]) -> Option<Color> {
+    let text = String::from_utf8_lossy(reply);
+    let start = text.find("]11;rgb:")? + "]11;rgb:".len();
+    let body = &text[start..];
+    let end = body.find(['\x07', '\x1b']).unwrap_or(body.len());
+    let mut channels = body[..end].split('/').map(|hex| {
+        let digits = hex.len() as u32;
+        if digits == 0 || digits > 4 {
+            return None;
+        }
+        let value = u32::from_str_radix(hex, 16).ok()?;
+        let max = 16u32.pow(digits) - 1;
+        Some((value * 255 / max) as u8)
+    });
+    let color = Color::Rgb(channels.next()??, channels.next()??, channels.next()??);
+    channels.next().is_none().then_some(color)
 }
 
 impl Drop for Terminal {
     fn drop(&mut self) {
         let _ = disable_raw_mode();
-        let _ = execute!(self.stdout, LeaveAlternateScreen);
+        let _ = execute!(self.stdout, DisableMouseCapture, LeaveAlternateScreen);
     }
 }
 
@@ -45,3 +226,28 @@ impl Default for Terminal {
         Self::new().expect("Failed to initialize terminal")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_osc11_reply() {
+        let reply = b"\x1b]11;rgb:ffff/ffff/f0f0\x1b\\\x1b[?62;22c";
+        assert_eq!(parse_osc11(reply), Some(Color::Rgb(255, 255, 240)));
+        assert_eq!(parse_osc11(b"\x1b]11;rgb:1e/1e/2e\x07"), Some(Color::Rgb(30, 30, 46)));
+        assert_eq!(parse_osc11(b"\x1b[?1;2c"), None);
+        assert!(ends_with_device_attributes(reply));
+        assert!(!ends_with_device_attributes(b"\x1b]11;rgb:ffff/ffff/f0f0"));
+    }
+
+    #[test]
+    fn test_background_classification() {
+        assert_eq!(Background::from_color(Color::Rgb(253, 246, 227)), Background::Light);
+        assert_eq!(Background::from_color(Color::Rgb(30, 30, 46)), Background::Dark);
+        assert_eq!(Background::from_colorfgbg("0;15"), Some(Background::Light));
+        assert_eq!(Background::from_colorfgbg("15;default;0"), Some(Background::Dark));
+        assert_eq!(Background::from_colorfgbg("default"), None);
+        assert_eq!(Background::Light.pick(Theme::light(), Theme::dark()).name, "light");
+    }
+}